@@ -25,4 +25,4 @@ pub enum AptosError {
         #[source]
         source: Box<dyn std::error::Error + Sync + Send>,
     },
-}
\ No newline at end of file
+}
@@ -76,7 +76,7 @@ mod test {
                 .to_compressed()
                 .as_slice(),
         )
-            .unwrap();
+        .unwrap();
         validator_info[0] = ValidatorConsensusInfo::new(address, key, 500);
 
         let res = intern_li.verify_signatures(&ValidatorVerifier::new(validator_info));
@@ -208,4 +208,167 @@ mod test {
             _ => panic!("Expected epoch change"),
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_ratchet_from_waypoint() {
+        let mut aptos_wrapper = AptosWrapper::new(4, 3, 3).unwrap();
+
+        aptos_wrapper.generate_traffic().unwrap();
+        aptos_wrapper.commit_new_epoch().unwrap();
+
+        // Bootstrap from the epoch change ledger info that ended epoch 1
+        let epoch_change_li: LedgerInfoWithSignatures =
+            bcs::from_bytes(&aptos_wrapper.get_latest_li_bytes().unwrap()).unwrap();
+        let waypoint = Waypoint::new_epoch_boundary(epoch_change_li.ledger_info()).unwrap();
+        let intern_trusted_state = TrustedState::EpochWaypoint(waypoint);
+
+        assert_eq!(intern_trusted_state.version(), waypoint.version());
+
+        aptos_wrapper.generate_traffic().unwrap();
+
+        let state_proof = aptos_wrapper.new_state_proof(waypoint.version()).unwrap();
+
+        let intern_epoch_change_proof: EpochChangeProof =
+            bcs::from_bytes(&bcs::to_bytes(state_proof.epoch_changes()).unwrap()).unwrap();
+        let intern_li_w_sigs: LedgerInfoWithSignatures =
+            bcs::from_bytes(&bcs::to_bytes(state_proof.latest_ledger_info_w_sigs()).unwrap())
+                .unwrap();
+
+        // The proof has to start with the ledger info committed to by the waypoint
+        assert_eq!(
+            intern_epoch_change_proof.ledger_info_with_sigs[0],
+            epoch_change_li
+        );
+
+        let trusted_state_change = intern_trusted_state
+            .verify_and_ratchet_inner(&intern_epoch_change_proof)
+            .expect("Failed to ratchet");
+
+        match trusted_state_change {
+            Epoch {
+                new_state,
+                latest_epoch_change_li,
+            } => {
+                assert_eq!(
+                    latest_epoch_change_li, &intern_li_w_sigs,
+                    "expected li match after ratcheting"
+                );
+                match new_state {
+                    TrustedState::EpochState { epoch_state, .. } => {
+                        assert_eq!(*epoch_state.epoch(), 3);
+                        assert_eq!(
+                            intern_li_w_sigs.ledger_info().next_epoch_state().unwrap(),
+                            &epoch_state,
+                            "expected epoch state match after ratcheting"
+                        );
+                    }
+                    _ => panic!("Expected epoch state"),
+                }
+            }
+            _ => panic!("Expected epoch change"),
+        }
+
+        // Ratcheting starts from the epoch of the ledger info committed to by the waypoint
+        assert_eq!(
+            intern_epoch_change_proof
+                .epoch_range(&intern_trusted_state)
+                .unwrap(),
+            (1, 3)
+        );
+
+        // A waypoint that does not commit to the first ledger info must be rejected
+        let wrong_waypoint = Waypoint::new_epoch_boundary(intern_li_w_sigs.ledger_info()).unwrap();
+        let wrong_trusted_state =
+            TrustedState::EpochWaypoint(Waypoint::new(waypoint.version(), wrong_waypoint.value()));
+        assert!(wrong_trusted_state
+            .verify_and_ratchet_inner(&intern_epoch_change_proof)
+            .is_err());
+    }
+
+    #[test]
+    fn test_ratchet_multiple_epochs() {
+        let mut aptos_wrapper = AptosWrapper::new(4, 3, 3).unwrap();
+        let intern_trusted_state: TrustedState =
+            bcs::from_bytes(&bcs::to_bytes(aptos_wrapper.trusted_state()).unwrap()).unwrap();
+
+        assert_eq!(intern_trusted_state.epoch(), Some(1));
+
+        // Go through several epochs while the client is offline
+        let mut intermediate_trusted_state = None;
+        for i in 0..3 {
+            aptos_wrapper.generate_traffic().unwrap();
+            aptos_wrapper.commit_new_epoch().unwrap();
+
+            if i == 1 {
+                intermediate_trusted_state = Some(
+                    bcs::from_bytes::<TrustedState>(
+                        &bcs::to_bytes(aptos_wrapper.trusted_state()).unwrap(),
+                    )
+                    .unwrap(),
+                );
+            }
+        }
+        aptos_wrapper.generate_traffic().unwrap();
+
+        let state_proof = aptos_wrapper
+            .new_state_proof(intern_trusted_state.version())
+            .unwrap();
+
+        let intern_epoch_change_proof: EpochChangeProof =
+            bcs::from_bytes(&bcs::to_bytes(state_proof.epoch_changes()).unwrap()).unwrap();
+        let intern_li_w_sigs: LedgerInfoWithSignatures =
+            bcs::from_bytes(&bcs::to_bytes(state_proof.latest_ledger_info_w_sigs()).unwrap())
+                .unwrap();
+
+        // One ledger info per epoch change
+        assert_eq!(intern_epoch_change_proof.ledger_info_with_sigs.len(), 4);
+        assert_eq!(
+            intern_epoch_change_proof
+                .epoch_range(&intern_trusted_state)
+                .unwrap(),
+            (1, 5)
+        );
+
+        let trusted_state_change = intern_trusted_state
+            .verify_and_ratchet_inner(&intern_epoch_change_proof)
+            .expect("Failed to ratchet");
+
+        match trusted_state_change {
+            Epoch {
+                new_state,
+                latest_epoch_change_li,
+            } => {
+                assert_eq!(
+                    latest_epoch_change_li, &intern_li_w_sigs,
+                    "expected li match after ratcheting"
+                );
+                assert_eq!(new_state.epoch(), Some(5));
+            }
+            _ => panic!("Expected epoch change"),
+        }
+
+        // Ledger infos already trusted are skipped when ratcheting
+        let intermediate_trusted_state = intermediate_trusted_state.unwrap();
+        assert_eq!(intermediate_trusted_state.epoch(), Some(3));
+        assert_eq!(
+            intern_epoch_change_proof
+                .epoch_range(&intermediate_trusted_state)
+                .unwrap(),
+            (3, 5)
+        );
+        match intermediate_trusted_state
+            .verify_and_ratchet_inner(&intern_epoch_change_proof)
+            .expect("Failed to ratchet")
+        {
+            Epoch { new_state, .. } => assert_eq!(new_state.epoch(), Some(5)),
+            _ => panic!("Expected epoch change"),
+        }
+
+        // Dropping an intermediate epoch change breaks the chain
+        let mut missing_epoch_proof = intern_epoch_change_proof.clone();
+        missing_epoch_proof.ledger_info_with_sigs.remove(1);
+        assert!(intern_trusted_state
+            .verify_and_ratchet_inner(&missing_epoch_proof)
+            .is_err());
+    }
+}
@@ -1,6 +1,6 @@
 use crate::aptos_test_utils::error::AptosError;
 use aptos_crypto::bls12381::Signature;
-use aptos_crypto::hash::{CryptoHash, TransactionAccumulatorHasher};
+use aptos_crypto::hash::{CryptoHash, TransactionAccumulatorHasher, ACCUMULATOR_PLACEHOLDER_HASH};
 use aptos_crypto::HashValue;
 use aptos_executor::block_executor::BlockExecutor;
 use aptos_executor_test_helpers::gen_block_id;
@@ -11,13 +11,19 @@ use aptos_sdk::transaction_builder::aptos_stdlib::version_set_version;
 use aptos_sdk::transaction_builder::{aptos_stdlib, TransactionFactory};
 use aptos_sdk::types::{AccountKey, LocalAccount};
 use aptos_storage_interface::DbReaderWriter;
+use aptos_types::account_address::AccountAddress;
 use aptos_types::account_config::{aptos_test_root_address, AccountResource};
 use aptos_types::aggregate_signature::PartialSignatures;
 use aptos_types::block_info::BlockInfo;
 use aptos_types::block_metadata::BlockMetadata;
 use aptos_types::chain_id::ChainId;
+use aptos_types::contract_event::ContractEvent;
 use aptos_types::ledger_info::LedgerInfoWithSignatures;
-use aptos_types::proof::{AccumulatorProof, SparseMerkleProof};
+use aptos_types::proof::{
+    AccumulatorConsistencyProof, AccumulatorProof, EventAccumulatorInternalNode,
+    EventAccumulatorProof, SparseMerkleProof, TransactionInfoListWithProof,
+    TransactionInfoWithProof,
+};
 use aptos_types::state_proof::StateProof;
 use aptos_types::state_store::state_key::StateKey;
 use aptos_types::state_store::state_value::StateValue;
@@ -274,7 +280,7 @@ impl AptosWrapper {
             validator_consensus_info,
             self.signers_per_block as u128,
         )
-            .expect("Incorrect quorum size.");
+        .expect("Incorrect quorum size.");
 
         let li = LedgerInfoWithSignatures::new(
             ledger_info,
@@ -442,6 +448,36 @@ impl AptosWrapper {
         self.execute_block(ExecuteBlockArgs::Block(block_id, block(block_txs)))
     }
 
+    /// Executes a block with a transfer exceeding the balance of its sender, which is committed
+    /// with an aborted execution status.
+    ///
+    /// # Returns
+    ///
+    /// * `u64` - The version of the aborted transaction.
+    pub fn generate_aborted_transaction(&mut self) -> Result<u64, AptosError> {
+        let (block_id, block_meta) = self.gen_block_id_and_metadata();
+        let sender = self
+            .accounts()
+            .first()
+            .ok_or(AptosError::UnexpectedNone("sender".to_string()))?;
+        let receiver = self
+            .accounts()
+            .last()
+            .ok_or(AptosError::UnexpectedNone("receiver".to_string()))?;
+        let transfer_tx = sender.sign_with_transaction_builder(
+            self.txn_factory().transfer(receiver.address(), u64::MAX),
+        );
+
+        // The transfer comes right after the block metadata
+        let version = self.current_version + 2;
+        self.execute_block(ExecuteBlockArgs::Block(
+            block_id,
+            block(vec![block_meta, UserTransaction(transfer_tx)]),
+        ))?;
+
+        Ok(version)
+    }
+
     /// Commits a new epoch by executing a block with a reconfiguration transaction.
     ///
     /// This method increments the major version, executes a block with a reconfiguration transaction,
@@ -504,25 +540,173 @@ impl AptosWrapper {
         &self,
         account_idx: usize,
     ) -> Result<SparseMerkleProofAssets, AptosError> {
-        // Create a state key to get the info
-        let account_0_resource_path = StateKey::resource(
+        self.get_latest_proof_address(
             &self
                 .accounts()
                 .get(account_idx)
                 .ok_or(AptosError::UnexpectedNone("get accounts".into()))?
                 .address(),
-            &AccountResource::struct_tag(),
         )
+    }
+
+    /// Returns the `TransactionInfo`s of a range of versions, with their range proof in the
+    /// transaction accumulator of the latest `LedgerInfoWithSignatures`.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_version` - The version of the first transaction of the range.
+    /// * `limit` - The number of transactions in the range.
+    ///
+    /// # Returns
+    ///
+    /// * `TransactionInfoListWithProof` - The `TransactionInfo`s and their range proof.
+    pub fn get_latest_transaction_infos_with_proof(
+        &self,
+        start_version: u64,
+        limit: u64,
+    ) -> Result<TransactionInfoListWithProof, AptosError> {
+        self.db()
+            .reader
+            .get_transactions(start_version, limit, *self.current_version(), false)
+            .map(|txn_list_w_proof| txn_list_w_proof.proof)
+            .map_err(|e| AptosError::Internal { source: e.into() })
+    }
+
+    /// Returns the `TransactionInfo` of a version, with its proof in the transaction accumulator
+    /// of the latest `LedgerInfoWithSignatures`.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The version of the transaction.
+    ///
+    /// # Returns
+    ///
+    /// * `TransactionInfoWithProof` - The `TransactionInfo` and its proof.
+    pub fn get_transaction_info_with_proof(
+        &self,
+        version: u64,
+    ) -> Result<TransactionInfoWithProof, AptosError> {
+        self.db()
+            .reader
+            .get_transaction_by_version(version, *self.current_version(), false)
+            .map(|txn_w_proof| txn_w_proof.proof)
+            .map_err(|e| AptosError::Internal { source: e.into() })
+    }
+
+    /// Returns an event emitted by the transaction at a version, with its proof in the event
+    /// accumulator of the `TransactionInfo`.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The version of the transaction that emitted the event.
+    /// * `event_index` - The index of the event among the events of the transaction.
+    ///
+    /// # Returns
+    ///
+    /// * `(ContractEvent, EventAccumulatorProof)` - The event and its proof.
+    pub fn get_event_with_proof(
+        &self,
+        version: u64,
+        event_index: u64,
+    ) -> Result<(ContractEvent, EventAccumulatorProof), AptosError> {
+        let txn_w_proof = self
+            .db()
+            .reader
+            .get_transaction_by_version(version, *self.current_version(), true)
+            .map_err(|e| AptosError::Internal { source: e.into() })?;
+        let events = txn_w_proof
+            .events
+            .ok_or(AptosError::UnexpectedNone("events".to_string()))?;
+        let event = events
+            .get(event_index as usize)
+            .cloned()
+            .ok_or(AptosError::UnexpectedNone("event".to_string()))?;
+
+        // The storage does not serve event proofs, so the event accumulator is rebuilt from the
+        // events of the transaction
+        let event_hashes: Vec<HashValue> = events.iter().map(CryptoHash::hash).collect();
+        let event_proof =
+            EventAccumulatorProof::new(accumulator_siblings(&event_hashes, event_index as usize));
+        event_proof
+            .verify(
+                txn_w_proof.proof.transaction_info.event_root_hash(),
+                event.hash(),
+                event_index,
+            )
+            .map_err(|e| AptosError::Internal { source: e.into() })?;
+
+        Ok((event, event_proof))
+    }
+
+    /// Returns the `AccumulatorConsistencyProof` from a known version to a ledger version.
+    ///
+    /// # Arguments
+    ///
+    /// * `known_version` - The version of the accumulator to extend, or `None` to get the
+    ///   frozen subtrees of the whole accumulator at `ledger_version`.
+    /// * `ledger_version` - The version of the extended accumulator.
+    ///
+    /// # Returns
+    ///
+    /// * `AccumulatorConsistencyProof` - The subtrees to append to the accumulator at `known_version`.
+    pub fn get_accumulator_consistency_proof(
+        &self,
+        known_version: Option<u64>,
+        ledger_version: u64,
+    ) -> Result<AccumulatorConsistencyProof, AptosError> {
+        self.db()
+            .reader
+            .get_accumulator_consistency_proof(known_version, ledger_version)
+            .map_err(|e| AptosError::Internal { source: e.into() })
+    }
+
+    /// Returns a `SparseMerkleProofAssets` for an account that does not exist on the chain.
+    /// The contained `SparseMerkleProof` is a non-inclusion proof.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The address of the absent account.
+    ///
+    /// # Returns
+    ///
+    /// * `SparseMerkleProofAssets` - The `SparseMerkleProofAssets` for the absent account.
+    pub fn get_latest_proof_absent_account(
+        &self,
+        address: AccountAddress,
+    ) -> Result<SparseMerkleProofAssets, AptosError> {
+        let proof_assets = self.get_latest_proof_address(&address)?;
+
+        if proof_assets.state_value.is_some() {
+            return Err(AptosError::Internal {
+                source: format!("Account {address} exists on the chain").into(),
+            });
+        }
+
+        Ok(proof_assets)
+    }
+
+    /// Returns a `SparseMerkleProofAssets` for the account resource of a given address.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The address of the account.
+    ///
+    /// # Returns
+    ///
+    /// * `SparseMerkleProofAssets` - The `SparseMerkleProofAssets` for the account resource.
+    fn get_latest_proof_address(
+        &self,
+        address: &AccountAddress,
+    ) -> Result<SparseMerkleProofAssets, AptosError> {
+        // Create a state key to get the info
+        let account_resource_path = StateKey::resource(address, &AccountResource::struct_tag())
             .map_err(|e| AptosError::Internal { source: e.into() })?;
 
         // Get the state proof for the current version
         let (state_value, state_proof) = self
             .db()
             .reader
-            .get_state_value_with_proof_by_version(
-                &account_0_resource_path,
-                *self.current_version(),
-            )
+            .get_state_value_with_proof_by_version(&account_resource_path, *self.current_version())
             .map_err(|e| AptosError::Internal { source: e.into() })?;
 
         // Get the transaction with proof for the current version
@@ -539,7 +723,7 @@ impl AptosWrapper {
 
         Ok(SparseMerkleProofAssets {
             state_proof,
-            key: account_0_resource_path.hash(),
+            key: account_resource_path.hash(),
             state_value,
             root_hash: txn_info
                 .state_checkpoint_hash()
@@ -553,6 +737,42 @@ impl AptosWrapper {
     }
 }
 
+/// Returns the siblings of a leaf in the event accumulator built from the given leaves.
+///
+/// Subtrees without leaves hash to the placeholder hash, as in the accumulators of Aptos.
+///
+/// # Arguments
+///
+/// * `leaves` - The hashes of the leaves of the accumulator.
+/// * `index` - The index of the leaf.
+///
+/// # Returns
+///
+/// * `Vec<HashValue>` - The siblings of the leaf, ordered from the bottom level to the root level.
+fn accumulator_siblings(leaves: &[HashValue], mut index: usize) -> Vec<HashValue> {
+    let placeholder = *ACCUMULATOR_PLACEHOLDER_HASH;
+    let mut level = leaves.to_vec();
+    level.resize(leaves.len().next_power_of_two(), placeholder);
+
+    let mut siblings = vec![];
+    while level.len() > 1 {
+        siblings.push(level[index ^ 1]);
+        level = level
+            .chunks_exact(2)
+            .map(|pair| {
+                if pair[0] == placeholder && pair[1] == placeholder {
+                    placeholder
+                } else {
+                    EventAccumulatorInternalNode::new(pair[0], pair[1]).hash()
+                }
+            })
+            .collect();
+        index /= 2;
+    }
+
+    siblings
+}
+
 /// Generates a specified number of local accounts.
 ///
 /// This function creates a new random number generator with a fixed seed, and then generates the specified number of local accounts.
@@ -623,4 +843,4 @@ fn test_multiple_signers() {
 
     aptos_wrapper.generate_traffic().unwrap();
     assert_eq!(aptos_wrapper.trusted_state().version(), 22);
-}
\ No newline at end of file
+}
@@ -12,4 +12,4 @@ pub enum CryptoError {
     SignatureVerificationFailed,
     #[error("Failed to decompress data for {structure}")]
     DecompressionError { structure: String },
-}
\ No newline at end of file
+}
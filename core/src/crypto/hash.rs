@@ -14,6 +14,16 @@ pub const HASH_PREFIX: &[u8] = b"APTOS::";
 /// Length in bytes of a given `HashValue`.
 pub const HASH_LENGTH: usize = 32;
 
+/// Placeholder hash of an empty subtree in a Sparse Merkle Tree. It is the
+/// literal string padded with zeros, as done in the Aptos codebase.
+pub const SPARSE_MERKLE_PLACEHOLDER_HASH: HashValue =
+    HashValue::new(*b"SPARSE_MERKLE_PLACEHOLDER_HASH\0\0");
+
+/// Placeholder hash of an empty subtree in an accumulator. It is the
+/// literal string padded with zeros, as done in the Aptos codebase.
+pub const ACCUMULATOR_PLACEHOLDER_HASH: HashValue =
+    HashValue::new(*b"ACCUMULATOR_PLACEHOLDER_HASH\0\0\0\0");
+
 /// `CryptoHash` is a trait to implement on types that can be hashed.
 pub trait CryptoHash {
     /// Hashes the object and produces a `HashValue`.
@@ -122,6 +132,23 @@ impl HashValue {
         HashValueBitIterator::new(self)
     }
 
+    /// Returns the length of the common prefix between this `HashValue`
+    /// and another one, in bits.
+    ///
+    /// # Arguments
+    ///
+    /// * `other: HashValue` - The `HashValue` to compare with.
+    ///
+    /// # Returns
+    ///
+    /// The number of leading bits that are equal in both `HashValue`s.
+    pub fn common_prefix_bits_len(&self, other: HashValue) -> usize {
+        self.iter_bits()
+            .zip(other.iter_bits())
+            .take_while(|(x, y)| x == y)
+            .count()
+    }
+
     /// Converts the `HashValue` into a vector.
     ///
     /// This method takes the hash value and converts it into a vector of bytes.
@@ -194,4 +221,4 @@ impl DoubleEndedIterator for HashValueBitIterator<'_> {
     fn next_back(&mut self) -> Option<Self::Item> {
         self.pos.next_back().map(|x| self.get_bit(x))
     }
-}
\ No newline at end of file
+}
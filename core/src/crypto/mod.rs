@@ -1,3 +1,3 @@
 mod error;
 pub mod hash;
-pub mod sig;
\ No newline at end of file
+pub mod sig;
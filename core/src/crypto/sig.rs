@@ -1,10 +1,12 @@
 use crate::crypto::error::CryptoError;
 use crate::serde_error;
 use crate::types::error::TypesError;
-use crate::types::utils::{read_leb128, write_leb128};
+use crate::types::utils::{read_leb128, read_u8, write_leb128};
 use anyhow::Result;
 use bls12_381::hash_to_curve::{ExpandMsgXmd, HashToCurve};
-use bls12_381::{multi_miller_loop, G1Affine, G1Projective, G2Affine, G2Prepared, G2Projective, Gt};
+use bls12_381::{
+    multi_miller_loop, G1Affine, G1Projective, G2Affine, G2Prepared, G2Projective, Gt,
+};
 use bytes::{Buf, BufMut, BytesMut};
 use getset::Getters;
 use serde::de::Error;
@@ -51,9 +53,9 @@ pub struct PublicKey {
 
 pub fn add_affine(lhs: &G1Affine, rhs: &G1Affine) -> G1Affine {
     if lhs.is_identity().into() {
-        return *rhs
+        return *rhs;
     } else if rhs.is_identity().into() {
-        return *lhs
+        return *lhs;
     } else {
         let proj = G1Projective::from(rhs);
         let res = proj + lhs;
@@ -100,9 +102,9 @@ impl PublicKey {
     /// A `Result` which is `Ok` if the public keys could be aggregated successfully. If the aggregation fails,
     /// the `Result` is `Err` with an error message.
     pub fn aggregate(pubkeys: &[&Self]) -> Result<PublicKey> {
-        let aggregate = pubkeys
-            .iter()
-            .fold(G1Affine::identity(), |acc, pk| add_affine(&acc, pk.pubkey()));
+        let aggregate = pubkeys.iter().fold(G1Affine::identity(), |acc, pk| {
+            add_affine(&acc, pk.pubkey())
+        });
 
         let pubkey = OnceCell::new();
         pubkey.set(aggregate).unwrap();
@@ -324,7 +326,7 @@ impl TryFrom<&[u8]> for Signature {
                     source: e.into(),
                 }
             })?)
-                .into();
+            .into();
 
         if let Some(g2_affine) = g2_affine_option {
             Ok(Self { sig: g2_affine })
@@ -504,7 +506,7 @@ impl AggregateSignature {
     /// successfully. If the slice has an invalid length, the `Result`
     /// is `Err` with a `TypesError`.
     pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, TypesError> {
-        let bitvec_len = bytes.get_u8() as usize;
+        let bitvec_len = read_u8(&mut bytes, "AggregateSignature")? as usize;
 
         let validator_bitmask = BitVec::from_bytes(
             bytes
@@ -515,7 +517,7 @@ impl AggregateSignature {
 
         bytes.advance(bitvec_len);
 
-        let sig = match bytes.get_u8() {
+        let sig = match read_u8(&mut bytes, "AggregateSignature")? {
             1 => {
                 let (slice_len, bytes_read) = read_leb128(bytes).map_err(|e| {
                     serde_error!(
@@ -572,4 +574,4 @@ mod test {
 
         assert_eq!(bytes, intern_bytes);
     }
-}
\ No newline at end of file
+}
@@ -2,4 +2,5 @@
 pub mod aptos_test_utils;
 pub mod crypto;
 pub mod merkle;
-pub mod types;
\ No newline at end of file
+pub mod types;
+pub mod verify;
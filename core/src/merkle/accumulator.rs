@@ -0,0 +1,222 @@
+use crate::crypto::hash::{CryptoHash, HashValue, HASH_LENGTH};
+use crate::merkle::node::{MerkleInternalNode, NodeHasher};
+use crate::serde_error;
+use crate::types::error::TypesError;
+use crate::types::utils::{read_leb128, write_leb128};
+use anyhow::{anyhow, ensure, Result};
+use bytes::{Buf, BufMut, BytesMut};
+
+pub const MAX_ACCUMULATOR_PROOF_DEPTH: usize = 63;
+
+/// Computes the root hash of an accumulator from the hash of one of its
+/// elements and the siblings of its path, as the accumulator proofs of
+/// Aptos do.
+///
+/// # Arguments
+///
+/// * `siblings: &[HashValue]` - The siblings of the path of the element,
+///   ordered from the bottom level to the root level.
+/// * `element_hash: HashValue` - The hash of the element.
+/// * `element_index: u64` - The index of the element.
+///
+/// # Returns
+///
+/// A `Result` containing the root hash of the accumulator, or an error if
+/// there are more than `MAX_ACCUMULATOR_PROOF_DEPTH` siblings.
+pub(crate) fn accumulator_root_hash<H: NodeHasher + Default>(
+    siblings: &[HashValue],
+    element_hash: HashValue,
+    element_index: u64,
+) -> Result<HashValue> {
+    ensure!(
+        siblings.len() <= MAX_ACCUMULATOR_PROOF_DEPTH,
+        "Accumulator proof has more than {} ({}) siblings.",
+        MAX_ACCUMULATOR_PROOF_DEPTH,
+        siblings.len()
+    );
+
+    Ok(siblings
+        .iter()
+        .fold(
+            (element_hash, element_index),
+            // `index` denotes the index of the ancestor of the element at the current level.
+            |(hash, index), sibling_hash| {
+                (
+                    if index % 2 == 0 {
+                        // the current node is a left child.
+                        MerkleInternalNode::<H>::new(hash, *sibling_hash).hash()
+                    } else {
+                        // the current node is a right child.
+                        MerkleInternalNode::<H>::new(*sibling_hash, hash).hash()
+                    },
+                    // The index of the parent at its level.
+                    index / 2,
+                )
+            },
+        )
+        .0)
+}
+
+/// Computes the root hash of an accumulator from the hashes of a
+/// contiguous range of its elements and the siblings on the left and on
+/// the right of the range, as the accumulator range proofs of Aptos do.
+///
+/// # Arguments
+///
+/// * `left_siblings: &[HashValue]` - The siblings on the left of the path
+///   of the first element, ordered from the bottom level to the root level.
+/// * `right_siblings: &[HashValue]` - The siblings on the right of the path
+///   of the last element, ordered from the bottom level to the root level.
+/// * `first_element_index: u64` - The index of the first element.
+/// * `element_hashes: &[HashValue]` - The hashes of the elements, in order.
+///
+/// # Returns
+///
+/// A `Result` containing the root hash of the accumulator, or an error if
+/// there are no elements or if the siblings do not match the range.
+pub(crate) fn accumulator_range_root_hash<H: NodeHasher + Default>(
+    left_siblings: &[HashValue],
+    right_siblings: &[HashValue],
+    first_element_index: u64,
+    element_hashes: &[HashValue],
+) -> Result<HashValue> {
+    ensure!(
+        left_siblings.len() <= MAX_ACCUMULATOR_PROOF_DEPTH,
+        "Accumulator range proof has more than {} ({}) left siblings.",
+        MAX_ACCUMULATOR_PROOF_DEPTH,
+        left_siblings.len()
+    );
+    ensure!(
+        right_siblings.len() <= MAX_ACCUMULATOR_PROOF_DEPTH,
+        "Accumulator range proof has more than {} ({}) right siblings.",
+        MAX_ACCUMULATOR_PROOF_DEPTH,
+        right_siblings.len()
+    );
+    ensure!(
+        !element_hashes.is_empty(),
+        "Accumulator range proof is for an empty range."
+    );
+
+    let mut left_siblings = left_siblings.iter();
+    let mut right_siblings = right_siblings.iter();
+    // `first_index` denotes the index of the ancestor of the first element at the current level.
+    let mut first_index = first_element_index;
+    let mut current_hashes = element_hashes.to_vec();
+
+    // Hash the nodes of each level into the next one, until the root.
+    while current_hashes.len() > 1 || left_siblings.len() != 0 || right_siblings.len() != 0 {
+        let mut children = current_hashes.as_slice();
+        let mut parent_hashes = Vec::with_capacity(children.len() / 2 + 1);
+
+        // If the first node is a right child, it is hashed with a sibling on the left.
+        if first_index % 2 == 1 {
+            let left_sibling = left_siblings
+                .next()
+                .ok_or_else(|| anyhow!("Accumulator range proof ran out of left siblings."))?;
+            parent_hashes.push(MerkleInternalNode::<H>::new(*left_sibling, children[0]).hash());
+            children = &children[1..];
+        }
+
+        while let [left, right, rest @ ..] = children {
+            parent_hashes.push(MerkleInternalNode::<H>::new(*left, *right).hash());
+            children = rest;
+        }
+
+        // If the last node is a left child, it is hashed with a sibling on the right.
+        if let [last] = children {
+            let right_sibling = right_siblings
+                .next()
+                .ok_or_else(|| anyhow!("Accumulator range proof ran out of right siblings."))?;
+            parent_hashes.push(MerkleInternalNode::<H>::new(*last, *right_sibling).hash());
+        }
+
+        first_index /= 2;
+        current_hashes = parent_hashes;
+    }
+
+    Ok(current_hashes[0])
+}
+
+/// Converts the siblings of an accumulator proof to a byte vector, as a
+/// BCS-serialized vector of hashes.
+///
+/// # Arguments
+///
+/// * `siblings: &[HashValue]` - The siblings of the proof.
+///
+/// # Returns
+///
+/// A `Vec<u8>` representing the siblings.
+pub(crate) fn siblings_to_bytes(siblings: &[HashValue]) -> Vec<u8> {
+    let mut bytes = BytesMut::new();
+    bytes.put_slice(&write_leb128(siblings.len() as u64));
+    for sibling in siblings {
+        bytes.put_slice(sibling.as_ref());
+    }
+    bytes.to_vec()
+}
+
+/// Reads the siblings of an accumulator proof from a byte slice, which
+/// should contain nothing else.
+///
+/// # Arguments
+///
+/// * `bytes: &[u8]` - A byte slice from which to read the siblings.
+/// * `structure: &str` - The name of the proof, for error messages.
+///
+/// # Returns
+///
+/// A `Result` which is `Ok` with the siblings if they could be read, and
+/// `Err` otherwise.
+pub(crate) fn siblings_from_bytes(
+    bytes: &[u8],
+    structure: &str,
+) -> std::result::Result<Vec<HashValue>, TypesError> {
+    let mut buf = bytes;
+    let siblings = read_siblings(&mut buf, structure)?;
+
+    if buf.remaining() != 0 {
+        return Err(serde_error!(
+            structure,
+            "Unexpected data after completing deserialization"
+        ));
+    }
+
+    Ok(siblings)
+}
+
+/// Reads the siblings of an accumulator proof from the start of `bytes`,
+/// advancing it.
+///
+/// # Arguments
+///
+/// * `bytes: &mut &[u8]` - The bytes from which to read the siblings.
+/// * `structure: &str` - The name of the proof, for error messages.
+///
+/// # Returns
+///
+/// A `Result` which is `Ok` with the siblings if they could be read, and
+/// `Err` otherwise.
+pub(crate) fn read_siblings(
+    bytes: &mut &[u8],
+    structure: &str,
+) -> std::result::Result<Vec<HashValue>, TypesError> {
+    let (len, read_bytes) =
+        read_leb128(bytes).map_err(|_| serde_error!(structure, "Not enough data for length"))?;
+    bytes.advance(read_bytes);
+    // The length is not trusted, so the siblings are not preallocated
+    let mut siblings = Vec::new();
+    for _ in 0..len {
+        if bytes.remaining() < HASH_LENGTH {
+            return Err(serde_error!(
+                structure,
+                "Not enough bytes to read HashValue"
+            ));
+        }
+        let mut hash_value = [0u8; HASH_LENGTH];
+        bytes.copy_to_slice(&mut hash_value);
+        siblings.push(HashValue::from_slice(hash_value).map_err(|e| serde_error!(structure, e))?);
+    }
+
+    Ok(siblings)
+}
@@ -0,0 +1,120 @@
+use crate::crypto::hash::{CryptoHash, HashValue, SPARSE_MERKLE_PLACEHOLDER_HASH};
+use crate::merkle::node::{BatchInclusionHasher, MerkleInternalNode, SparseMerkleLeafNode};
+
+/// Computes the root of the binary Merkle tree committing to a batch
+/// of proven leaves.
+///
+/// The leaves of the tree are the hashes of the given `SparseMerkleLeafNode`s,
+/// in order. Each level with an odd number of nodes is padded with
+/// `SPARSE_MERKLE_PLACEHOLDER_HASH` before being hashed into the next one.
+///
+/// # Arguments
+///
+/// * `leaves: &[SparseMerkleLeafNode]` - The proven leaves, in the order they were proven.
+///
+/// # Returns
+///
+/// A `HashValue` representing the root of the tree. If there are no
+/// leaves, this is `SPARSE_MERKLE_PLACEHOLDER_HASH`.
+pub fn batch_leaves_root(leaves: &[SparseMerkleLeafNode]) -> HashValue {
+    let mut level: Vec<HashValue> = leaves.iter().map(CryptoHash::hash).collect();
+
+    if level.is_empty() {
+        return SPARSE_MERKLE_PLACEHOLDER_HASH;
+    }
+
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| {
+                MerkleInternalNode::<BatchInclusionHasher>::new(
+                    pair[0],
+                    pair.get(1)
+                        .copied()
+                        .unwrap_or(SPARSE_MERKLE_PLACEHOLDER_HASH),
+                )
+                .hash()
+            })
+            .collect();
+    }
+
+    level[0]
+}
+
+#[cfg(test)]
+mod test {
+    use crate::crypto::hash::{CryptoHash, HashValue, HASH_LENGTH, SPARSE_MERKLE_PLACEHOLDER_HASH};
+    use crate::merkle::batch::batch_leaves_root;
+    use crate::merkle::node::{BatchInclusionHasher, MerkleInternalNode, SparseMerkleLeafNode};
+
+    #[test]
+    fn test_batch_leaves_root() {
+        let leaves: Vec<SparseMerkleLeafNode> = (0..3u8)
+            .map(|i| {
+                SparseMerkleLeafNode::new(
+                    HashValue::new([i; HASH_LENGTH]),
+                    HashValue::new([i + 10; HASH_LENGTH]),
+                )
+            })
+            .collect();
+
+        assert_eq!(batch_leaves_root(&[]), SPARSE_MERKLE_PLACEHOLDER_HASH);
+        assert_eq!(batch_leaves_root(&leaves[..1]), leaves[0].hash());
+
+        let left =
+            MerkleInternalNode::<BatchInclusionHasher>::new(leaves[0].hash(), leaves[1].hash())
+                .hash();
+        let right = MerkleInternalNode::<BatchInclusionHasher>::new(
+            leaves[2].hash(),
+            SPARSE_MERKLE_PLACEHOLDER_HASH,
+        )
+        .hash();
+        let expected = MerkleInternalNode::<BatchInclusionHasher>::new(left, right).hash();
+
+        assert_eq!(batch_leaves_root(&leaves), expected);
+
+        // The order of the leaves is committed to
+        let mut reversed = leaves.clone();
+        reversed.reverse();
+        assert_ne!(batch_leaves_root(&reversed), expected);
+    }
+
+    #[cfg(feature = "aptos")]
+    #[test]
+    fn test_batch_aptos_data() {
+        use crate::aptos_test_utils::wrapper::AptosWrapper;
+        use crate::merkle::sparse_proof::SparseMerkleProof;
+
+        let mut aptos_wrapper = AptosWrapper::new(10, 1, 1).unwrap();
+        aptos_wrapper.generate_traffic().unwrap();
+
+        let proofs_assets: Vec<_> = (0..5)
+            .map(|idx| aptos_wrapper.get_latest_proof_account(idx).unwrap())
+            .collect();
+
+        let root_hash = HashValue::from_slice(proofs_assets[0].root_hash().to_vec()).unwrap();
+
+        let leaves: Vec<SparseMerkleLeafNode> = proofs_assets
+            .iter()
+            .map(|proof_assets| {
+                let intern_proof = SparseMerkleProof::from_bytes(
+                    &bcs::to_bytes(proof_assets.state_proof()).unwrap(),
+                )
+                .unwrap();
+                let key = HashValue::from_slice(proof_assets.key().to_vec()).unwrap();
+                let element_hash =
+                    HashValue::from_slice(proof_assets.state_value_hash().unwrap().to_vec())
+                        .unwrap();
+
+                // All proofs are against the same state checkpoint
+                intern_proof
+                    .verify_by_hash(root_hash, key, element_hash)
+                    .unwrap();
+
+                SparseMerkleLeafNode::new(key, element_hash)
+            })
+            .collect();
+
+        assert_ne!(batch_leaves_root(&leaves), SPARSE_MERKLE_PLACEHOLDER_HASH);
+    }
+}
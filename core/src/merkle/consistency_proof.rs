@@ -0,0 +1,446 @@
+use crate::crypto::hash::{CryptoHash, HashValue, ACCUMULATOR_PLACEHOLDER_HASH};
+use crate::merkle::accumulator::{read_siblings, siblings_from_bytes, siblings_to_bytes};
+use crate::merkle::node::{MerkleInternalNode, TransactionAccumulatorHasher};
+use crate::serde_error;
+use crate::types::error::TypesError;
+use crate::types::ledger_info::LedgerInfo;
+use crate::types::utils::read_u64;
+use anyhow::{anyhow, ensure, Result};
+use bytes::{BufMut, BytesMut};
+use getset::Getters;
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of leaves of an accumulator.
+pub const MAX_ACCUMULATOR_LEAVES: u64 = 1 << 63;
+
+/// A proof that the transaction accumulator at a version extends the one
+/// at a previous version. It contains the roots of the frozen subtrees to
+/// append to the previous accumulator to obtain the new one.
+#[derive(Clone, Debug, Serialize, Deserialize, Getters)]
+#[getset(get = "pub")]
+pub struct AccumulatorConsistencyProof {
+    /// The roots of the frozen subtrees covering the new leaves, from the
+    /// leftmost to the rightmost.
+    subtrees: Vec<HashValue>,
+}
+
+impl AccumulatorConsistencyProof {
+    /// Creates a new `AccumulatorConsistencyProof`.
+    ///
+    /// # Arguments
+    ///
+    /// * `subtrees: Vec<HashValue>` - The roots of the frozen subtrees covering the new leaves.
+    ///
+    /// # Returns
+    ///
+    /// A new `AccumulatorConsistencyProof` instance.
+    pub const fn new(subtrees: Vec<HashValue>) -> Self {
+        Self { subtrees }
+    }
+
+    /// Converts the `AccumulatorConsistencyProof` to a byte vector.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<u8>` representing the `AccumulatorConsistencyProof`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        siblings_to_bytes(&self.subtrees)
+    }
+
+    /// Creates an `AccumulatorConsistencyProof` from a byte slice.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes: &[u8]` - A byte slice from which to create the `AccumulatorConsistencyProof`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which is `Ok` if the `AccumulatorConsistencyProof` could be successfully created, and `Err` otherwise.
+    pub fn from_bytes(bytes: &[u8]) -> std::result::Result<Self, TypesError> {
+        Ok(Self {
+            subtrees: siblings_from_bytes(bytes, "AccumulatorConsistencyProof")?,
+        })
+    }
+}
+
+/// `TransactionAccumulatorSummary` summarizes the transaction accumulator
+/// at a version by the roots of its frozen subtrees, which is enough to
+/// compute its root hash and to extend it with new leaves.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TransactionAccumulatorSummary {
+    /// The roots of the frozen subtrees, from the leftmost to the
+    /// rightmost, i.e. from the largest to the smallest.
+    frozen_subtree_roots: Vec<HashValue>,
+    /// The number of leaves of the accumulator.
+    num_leaves: u64,
+}
+
+impl TransactionAccumulatorSummary {
+    /// Creates a new `TransactionAccumulatorSummary`.
+    ///
+    /// # Arguments
+    ///
+    /// * `frozen_subtree_roots: Vec<HashValue>` - The roots of the frozen subtrees, from the
+    ///   leftmost to the rightmost.
+    /// * `num_leaves: u64` - The number of leaves of the accumulator.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `TransactionAccumulatorSummary`, or an error if there is not
+    /// one frozen subtree per bit set in `num_leaves`.
+    pub fn new(frozen_subtree_roots: Vec<HashValue>, num_leaves: u64) -> Result<Self> {
+        ensure!(
+            num_leaves <= MAX_ACCUMULATOR_LEAVES,
+            "Accumulator has too many leaves ({}).",
+            num_leaves
+        );
+        ensure!(
+            frozen_subtree_roots.len() == num_leaves.count_ones() as usize,
+            "The number of frozen subtrees ({}) does not match the number of leaves ({}).",
+            frozen_subtree_roots.len(),
+            num_leaves
+        );
+
+        Ok(Self {
+            frozen_subtree_roots,
+            num_leaves,
+        })
+    }
+
+    /// Returns the version of the last transaction of the accumulator.
+    ///
+    /// # Returns
+    ///
+    /// The version of the last transaction, or 0 if the accumulator is empty.
+    pub const fn version(&self) -> u64 {
+        self.num_leaves.saturating_sub(1)
+    }
+
+    /// Computes the root hash of the accumulator. Subtrees on the right of
+    /// the frozen ones are empty and hash to `ACCUMULATOR_PLACEHOLDER_HASH`.
+    ///
+    /// # Returns
+    ///
+    /// The root hash of the accumulator.
+    pub fn root_hash(&self) -> HashValue {
+        match self.frozen_subtree_roots.as_slice() {
+            [] => return ACCUMULATOR_PLACEHOLDER_HASH,
+            [root] => return *root,
+            _ => (),
+        }
+
+        // Bits below the smallest frozen subtree are covered by its root.
+        let mut bitmap = self.num_leaves >> self.num_leaves.trailing_zeros();
+        let mut frozen_subtree_roots = self.frozen_subtree_roots.iter().rev();
+        let mut current_hash = ACCUMULATOR_PLACEHOLDER_HASH;
+
+        while bitmap > 0 {
+            current_hash = if bitmap & 1 != 0 {
+                // A frozen subtree is on the left of the current node.
+                let frozen_subtree_root = frozen_subtree_roots
+                    .next()
+                    .expect("There is one frozen subtree per bit set");
+                MerkleInternalNode::<TransactionAccumulatorHasher>::new(
+                    *frozen_subtree_root,
+                    current_hash,
+                )
+            } else {
+                // An empty subtree is on the right of the current node.
+                MerkleInternalNode::<TransactionAccumulatorHasher>::new(
+                    current_hash,
+                    ACCUMULATOR_PLACEHOLDER_HASH,
+                )
+            }
+            .hash();
+            bitmap >>= 1;
+        }
+
+        current_hash
+    }
+
+    /// Appends the frozen subtrees of a consistency proof to the
+    /// accumulator, and checks that the result is the accumulator of a
+    /// `LedgerInfo` at the same or at a later version.
+    ///
+    /// # Arguments
+    ///
+    /// * `consistency_proof: &AccumulatorConsistencyProof` - The proof from the version of the
+    ///   accumulator to the version of `target_li`.
+    /// * `target_li: &LedgerInfo` - The `LedgerInfo` whose accumulator extends this one.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `TransactionAccumulatorSummary` at the version of `target_li`,
+    /// or an error if the proof does not reconstruct its accumulator.
+    pub fn try_extend_with_proof(
+        &self,
+        consistency_proof: &AccumulatorConsistencyProof,
+        target_li: &LedgerInfo,
+    ) -> Result<Self> {
+        ensure!(
+            self.num_leaves > 0,
+            "Cannot extend an empty accumulator summary."
+        );
+        ensure!(
+            target_li.version() >= self.version(),
+            "The target version ({}) is older than the accumulator version ({}).",
+            target_li.version(),
+            self.version()
+        );
+
+        let num_new_leaves = target_li.version() - self.version();
+        let extension = self.append_subtrees(&consistency_proof.subtrees, num_new_leaves)?;
+        ensure!(
+            extension.root_hash() == target_li.transaction_accumulator_hash(),
+            "{}: Root hashes do not match.",
+            "AccumulatorConsistencyProof",
+        );
+
+        Ok(extension)
+    }
+
+    /// Appends frozen subtrees covering `num_new_leaves` leaves to the
+    /// accumulator.
+    fn append_subtrees(&self, subtrees: &[HashValue], num_new_leaves: u64) -> Result<Self> {
+        ensure!(
+            num_new_leaves <= MAX_ACCUMULATOR_LEAVES - self.num_leaves,
+            "Too many new leaves ({}) for an accumulator of {} leaves.",
+            num_new_leaves,
+            self.num_leaves
+        );
+
+        let mut frozen_subtree_roots = self.frozen_subtree_roots.clone();
+        let mut num_leaves = self.num_leaves;
+        let mut remaining_new_leaves = num_new_leaves;
+        let mut subtrees = subtrees.iter();
+
+        // While there are enough new leaves, the next subtree has the size of
+        // the rightmost frozen subtree, and both are merged.
+        let mut rightmost_frozen_subtree_size = 1 << num_leaves.trailing_zeros();
+        while remaining_new_leaves >= rightmost_frozen_subtree_size {
+            // Merging two subtrees of size X gives one of size 2X, which is
+            // merged again if there already is a frozen subtree of size 2X.
+            let mut mask = rightmost_frozen_subtree_size;
+            let mut current_hash = *subtrees
+                .next()
+                .ok_or_else(|| anyhow!("Accumulator consistency proof has too few subtrees."))?;
+            while num_leaves & mask != 0 {
+                let frozen_subtree_root = frozen_subtree_roots
+                    .pop()
+                    .expect("There is one frozen subtree per bit set");
+                current_hash = MerkleInternalNode::<TransactionAccumulatorHasher>::new(
+                    frozen_subtree_root,
+                    current_hash,
+                )
+                .hash();
+                mask <<= 1;
+            }
+            frozen_subtree_roots.push(current_hash);
+
+            num_leaves += rightmost_frozen_subtree_size;
+            remaining_new_leaves -= rightmost_frozen_subtree_size;
+            rightmost_frozen_subtree_size = mask;
+        }
+
+        // The remaining subtrees are smaller than the rightmost frozen one,
+        // and are appended as they are.
+        frozen_subtree_roots.extend(subtrees);
+        Self::new(frozen_subtree_roots, num_leaves + remaining_new_leaves)
+    }
+
+    /// Converts the `TransactionAccumulatorSummary` to a byte vector.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<u8>` representing the `TransactionAccumulatorSummary`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = BytesMut::new();
+        bytes.put_slice(&siblings_to_bytes(&self.frozen_subtree_roots));
+        bytes.put_u64_le(self.num_leaves);
+        bytes.to_vec()
+    }
+
+    /// Creates a `TransactionAccumulatorSummary` from a byte slice.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes: &[u8]` - A byte slice from which to create the `TransactionAccumulatorSummary`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which is `Ok` if the `TransactionAccumulatorSummary` could be successfully created, and `Err` otherwise.
+    pub fn from_bytes(bytes: &[u8]) -> std::result::Result<Self, TypesError> {
+        let mut buf = bytes;
+        let frozen_subtree_roots = read_siblings(&mut buf, "TransactionAccumulatorSummary")?;
+        let num_leaves = read_u64(&mut buf, "TransactionAccumulatorSummary")?;
+
+        if !buf.is_empty() {
+            return Err(serde_error!(
+                "TransactionAccumulatorSummary",
+                "Unexpected data after completing deserialization"
+            ));
+        }
+
+        Self::new(frozen_subtree_roots, num_leaves)
+            .map_err(|e| serde_error!("TransactionAccumulatorSummary", e))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::crypto::hash::{CryptoHash, HashValue, ACCUMULATOR_PLACEHOLDER_HASH};
+    use crate::merkle::consistency_proof::{
+        AccumulatorConsistencyProof, TransactionAccumulatorSummary,
+    };
+    use crate::merkle::node::{MerkleInternalNode, TransactionAccumulatorHasher};
+    use crate::types::block_info::BlockInfo;
+    use crate::types::ledger_info::LedgerInfo;
+
+    fn node(left: HashValue, right: HashValue) -> HashValue {
+        MerkleInternalNode::<TransactionAccumulatorHasher>::new(left, right).hash()
+    }
+
+    fn ledger_info(version: u64, root_hash: HashValue) -> LedgerInfo {
+        LedgerInfo::new(
+            BlockInfo::new(1, 0, HashValue::default(), root_hash, version, 0, None),
+            HashValue::default(),
+        )
+    }
+
+    #[test]
+    fn test_accumulator_summary_root_hash() {
+        let leaves: Vec<HashValue> = (0..3).map(|i| HashValue::new([i; 32])).collect();
+
+        let summary = TransactionAccumulatorSummary::new(vec![], 0).unwrap();
+        assert_eq!(summary.root_hash(), ACCUMULATOR_PLACEHOLDER_HASH);
+
+        let summary =
+            TransactionAccumulatorSummary::new(vec![node(leaves[0], leaves[1]), leaves[2]], 3)
+                .unwrap();
+        assert_eq!(summary.version(), 2);
+        assert_eq!(
+            summary.root_hash(),
+            node(
+                node(leaves[0], leaves[1]),
+                node(leaves[2], ACCUMULATOR_PLACEHOLDER_HASH)
+            )
+        );
+
+        // There is one frozen subtree per bit set in the number of leaves
+        assert!(TransactionAccumulatorSummary::new(vec![leaves[0]], 3).is_err());
+
+        let summary_bytes = summary.to_bytes();
+        assert_eq!(
+            TransactionAccumulatorSummary::from_bytes(&summary_bytes).unwrap(),
+            summary
+        );
+        assert!(TransactionAccumulatorSummary::from_bytes(&summary_bytes[..70]).is_err());
+    }
+
+    #[test]
+    fn test_accumulator_consistency_proof() {
+        let leaves: Vec<HashValue> = (0..7).map(|i| HashValue::new([i; 32])).collect();
+        let summary =
+            TransactionAccumulatorSummary::new(vec![node(leaves[0], leaves[1]), leaves[2]], 3)
+                .unwrap();
+
+        // The root of the accumulator of the 7 leaves
+        let root_hash = node(
+            node(node(leaves[0], leaves[1]), node(leaves[2], leaves[3])),
+            node(
+                node(leaves[4], leaves[5]),
+                node(leaves[6], ACCUMULATOR_PLACEHOLDER_HASH),
+            ),
+        );
+        let proof = AccumulatorConsistencyProof::new(vec![
+            leaves[3],
+            node(leaves[4], leaves[5]),
+            leaves[6],
+        ]);
+
+        let extension = summary
+            .try_extend_with_proof(&proof, &ledger_info(6, root_hash))
+            .unwrap();
+        assert_eq!(extension.version(), 6);
+        assert_eq!(extension.root_hash(), root_hash);
+
+        // The proof only holds for the root and version it was made for
+        assert!(summary
+            .try_extend_with_proof(&proof, &ledger_info(6, HashValue::default()))
+            .is_err());
+        assert!(summary
+            .try_extend_with_proof(&proof, &ledger_info(5, root_hash))
+            .is_err());
+        assert!(summary
+            .try_extend_with_proof(&proof, &ledger_info(1, root_hash))
+            .is_err());
+
+        // An accumulator extends itself with an empty proof
+        let empty_proof = AccumulatorConsistencyProof::new(vec![]);
+        summary
+            .try_extend_with_proof(&empty_proof, &ledger_info(2, summary.root_hash()))
+            .unwrap();
+
+        let proof_bytes = proof.to_bytes();
+        assert_eq!(
+            AccumulatorConsistencyProof::from_bytes(&proof_bytes)
+                .unwrap()
+                .to_bytes(),
+            proof_bytes
+        );
+    }
+
+    #[cfg(feature = "aptos")]
+    #[test]
+    fn test_accumulator_consistency_aptos() {
+        use crate::aptos_test_utils::wrapper::AptosWrapper;
+        use crate::types::ledger_info::LedgerInfoWithSignatures;
+
+        let mut aptos_wrapper = AptosWrapper::new(20, 1, 1).unwrap();
+        aptos_wrapper.generate_traffic().unwrap();
+        let prev_li =
+            LedgerInfoWithSignatures::from_bytes(&aptos_wrapper.get_latest_li_bytes().unwrap())
+                .unwrap();
+        aptos_wrapper.generate_traffic().unwrap();
+        let latest_li =
+            LedgerInfoWithSignatures::from_bytes(&aptos_wrapper.get_latest_li_bytes().unwrap())
+                .unwrap();
+        let prev_version = prev_li.ledger_info().version();
+        assert!(latest_li.ledger_info().version() > prev_version);
+
+        // From no known version, the subtrees are the frozen subtrees of the
+        // whole accumulator
+        let frozen_subtrees = AccumulatorConsistencyProof::from_bytes(
+            &bcs::to_bytes(
+                &aptos_wrapper
+                    .get_accumulator_consistency_proof(None, prev_version)
+                    .unwrap(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let summary =
+            TransactionAccumulatorSummary::new(frozen_subtrees.subtrees, prev_version + 1).unwrap();
+        assert_eq!(
+            summary.root_hash(),
+            prev_li.ledger_info().transaction_accumulator_hash()
+        );
+
+        let aptos_proof_bytes = bcs::to_bytes(
+            &aptos_wrapper
+                .get_accumulator_consistency_proof(
+                    Some(prev_version),
+                    latest_li.ledger_info().version(),
+                )
+                .unwrap(),
+        )
+        .unwrap();
+        let proof = AccumulatorConsistencyProof::from_bytes(&aptos_proof_bytes).unwrap();
+        assert_eq!(proof.to_bytes(), aptos_proof_bytes);
+
+        let extension = summary
+            .try_extend_with_proof(&proof, latest_li.ledger_info())
+            .unwrap();
+        assert_eq!(extension.version(), latest_li.ledger_info().version());
+    }
+}
@@ -0,0 +1,141 @@
+use crate::crypto::hash::HashValue;
+use crate::merkle::accumulator::{accumulator_root_hash, siblings_from_bytes, siblings_to_bytes};
+use crate::merkle::node::EventAccumulatorHasher;
+use crate::types::error::TypesError;
+use anyhow::{ensure, Result};
+use serde::{Deserialize, Serialize};
+
+/// A proof that can be used to authenticate an event in the event
+/// accumulator of a transaction, given the `event_root_hash` of its
+/// `TransactionInfo`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EventAccumulatorProof {
+    /// All siblings in this proof, including the default ones. Siblings
+    /// are ordered from the bottom level to the root level.
+    siblings: Vec<HashValue>,
+}
+
+impl EventAccumulatorProof {
+    /// Creates a new `EventAccumulatorProof`.
+    ///
+    /// # Arguments
+    ///
+    /// * `siblings: Vec<HashValue>` - The siblings of the path of the event,
+    ///   ordered from the bottom level to the root level.
+    ///
+    /// # Returns
+    ///
+    /// A new `EventAccumulatorProof` instance.
+    pub const fn new(siblings: Vec<HashValue>) -> Self {
+        Self { siblings }
+    }
+
+    /// Verifies an event whose hash is `event_hash` and index is `event_index` exists in
+    /// the event accumulator whose root hash is `expected_root_hash` using the provided proof.
+    ///
+    /// # Arguments
+    ///
+    /// * `expected_root_hash: HashValue` - The expected root hash of the Event Accumulator.
+    /// * `event_hash: HashValue` - The hash of the event to verify.
+    /// * `event_index: u64` - The index of the event among the events of the transaction.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which is `Ok` if the event exists in the Event Accumulator and the proof is valid, and `Err` otherwise.
+    pub fn verify(
+        &self,
+        expected_root_hash: HashValue,
+        event_hash: HashValue,
+        event_index: u64,
+    ) -> Result<()> {
+        let actual_root_hash = accumulator_root_hash::<EventAccumulatorHasher>(
+            &self.siblings,
+            event_hash,
+            event_index,
+        )?;
+        ensure!(
+            actual_root_hash == expected_root_hash,
+            "{}: Root hashes do not match.",
+            "EventAccumulatorProof",
+        );
+
+        Ok(())
+    }
+
+    /// Converts the `EventAccumulatorProof` to a byte vector.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<u8>` representing the `EventAccumulatorProof`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        siblings_to_bytes(&self.siblings)
+    }
+
+    /// Creates an `EventAccumulatorProof` from a byte slice.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes: &[u8]` - A byte slice from which to create the `EventAccumulatorProof`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which is `Ok` if the `EventAccumulatorProof` could be successfully created, and `Err` otherwise.
+    pub fn from_bytes(bytes: &[u8]) -> std::result::Result<Self, TypesError> {
+        Ok(Self {
+            siblings: siblings_from_bytes(bytes, "EventAccumulatorProof")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_event_accumulator_proof() {
+        use crate::crypto::hash::{CryptoHash, HashValue};
+        use crate::merkle::event_proof::EventAccumulatorProof;
+        use crate::merkle::node::{EventAccumulatorHasher, MerkleInternalNode};
+
+        let events = [[1; 32], [2; 32], [3; 32], [4; 32]].map(HashValue::new);
+        let left = MerkleInternalNode::<EventAccumulatorHasher>::new(events[0], events[1]).hash();
+        let right = MerkleInternalNode::<EventAccumulatorHasher>::new(events[2], events[3]).hash();
+        let root = MerkleInternalNode::<EventAccumulatorHasher>::new(left, right).hash();
+
+        let proof = EventAccumulatorProof::new(vec![events[3], left]);
+        proof.verify(root, events[2], 2).unwrap();
+        // The siblings only authenticate the event at its index
+        assert!(proof.verify(root, events[2], 3).is_err());
+        assert!(proof.verify(root, events[3], 2).is_err());
+
+        let proof_bytes = proof.to_bytes();
+        assert_eq!(proof_bytes.len(), 1 + 2 * 32);
+        assert_eq!(
+            EventAccumulatorProof::from_bytes(&proof_bytes)
+                .unwrap()
+                .to_bytes(),
+            proof_bytes
+        );
+        assert!(EventAccumulatorProof::from_bytes(&proof_bytes[..40]).is_err());
+    }
+
+    #[cfg(feature = "aptos")]
+    #[test]
+    fn test_event_accumulator_internal_node_hash() {
+        use crate::crypto::hash::{CryptoHash as LcCryptoHash, HashValue as LcHashValue};
+        use crate::merkle::node::{EventAccumulatorHasher, MerkleInternalNode};
+
+        use aptos_crypto::hash::CryptoHash as AptosCryptoHash;
+        use aptos_crypto::HashValue as AptosHashValue;
+        use aptos_types::proof::EventAccumulatorInternalNode;
+
+        let lc_hash = LcCryptoHash::hash(&MerkleInternalNode::<EventAccumulatorHasher>::new(
+            LcHashValue::new([10; 32]),
+            LcHashValue::new([15; 32]),
+        ));
+        let aptos_hash = AptosCryptoHash::hash(&EventAccumulatorInternalNode::new(
+            AptosHashValue::new([10; 32]),
+            AptosHashValue::new([15; 32]),
+        ));
+
+        assert_eq!(lc_hash.to_vec(), aptos_hash.to_vec());
+    }
+}
@@ -1,3 +1,7 @@
+pub mod accumulator;
+pub mod batch;
+pub mod consistency_proof;
+pub mod event_proof;
 pub mod node;
 pub mod sparse_proof;
-pub mod transaction_proof;
\ No newline at end of file
+pub mod transaction_proof;
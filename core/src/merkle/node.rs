@@ -1,4 +1,3 @@
-
 use crate::crypto::hash::{hash_data, prefixed_sha3, CryptoHash, HashValue, HASH_LENGTH};
 use crate::serde_error;
 use crate::types::error::TypesError;
@@ -76,14 +75,14 @@ impl SparseMerkleLeafNode {
                 .get(..HASH_LENGTH)
                 .ok_or_else(|| serde_error!("SparseMerkleLeafNode", "Not enough data for key"))?,
         )
-            .map_err(|e| serde_error!("SparseMerkleLeafNode", e))?;
+        .map_err(|e| serde_error!("SparseMerkleLeafNode", e))?;
         buf.advance(HASH_LENGTH);
 
         let value_hash =
             HashValue::from_slice(buf.chunk().get(..HASH_LENGTH).ok_or_else(|| {
                 serde_error!("SparseMerkleLeafNode", "Not enough data for value_hash")
             })?)
-                .map_err(|e| serde_error!("SparseMerkleLeafNode", e))?;
+            .map_err(|e| serde_error!("SparseMerkleLeafNode", e))?;
         buf.advance(HASH_LENGTH);
 
         if buf.remaining() != 0 {
@@ -210,6 +209,42 @@ impl NodeHasher for TransactionAccumulatorHasher {
     }
 }
 
+/// `EventAccumulatorHasher` is a structure representing
+/// the hasher for event accumulator in order to prove
+/// the inclusion of an event in a `TransactionInfo`.
+#[derive(Clone, Debug, Default)]
+pub struct EventAccumulatorHasher {}
+
+impl NodeHasher for EventAccumulatorHasher {
+    /// Returns the prefix used for hashing in the context of
+    /// an Event Accumulator.
+    ///
+    /// # Returns
+    ///
+    /// A static string slice representing the prefix.
+    fn prefix(&self) -> &'static str {
+        "EventAccumulator"
+    }
+}
+
+/// `BatchInclusionHasher` is a structure representing
+/// the hasher for the tree committing to all the leaves
+/// proven in a batch inclusion proof.
+#[derive(Clone, Debug, Default)]
+pub struct BatchInclusionHasher {}
+
+impl NodeHasher for BatchInclusionHasher {
+    /// Returns the prefix used for hashing in the context of
+    /// a batch of proven leaves.
+    ///
+    /// # Returns
+    ///
+    /// A static string slice representing the prefix.
+    fn prefix(&self) -> &'static str {
+        "BatchInclusion"
+    }
+}
+
 #[cfg(all(test, feature = "aptos"))]
 mod test {
     #[test]
@@ -302,4 +337,4 @@ mod test {
         assert_eq!(lc_node, lc_node_deserialized);
         assert_eq!(aptos_node, aptos_node_deserialized);
     }
-}
\ No newline at end of file
+}
@@ -1,9 +1,9 @@
-use crate::crypto::hash::{CryptoHash, HashValue, HASH_LENGTH};
+use crate::crypto::hash::{CryptoHash, HashValue, HASH_LENGTH, SPARSE_MERKLE_PLACEHOLDER_HASH};
 use crate::merkle::node::{MerkleInternalNode, SparseMerkleInternalHasher, SparseMerkleLeafNode};
 use crate::serde_error;
 use crate::types::error::TypesError;
-use crate::types::utils::{read_leb128, write_leb128};
-use anyhow::{ensure, Result};
+use crate::types::utils::{read_leb128, read_u8, write_leb128};
+use anyhow::{bail, ensure, Result};
 use bytes::{Buf, BufMut, BytesMut};
 use getset::Getters;
 use serde::{Deserialize, Serialize};
@@ -22,8 +22,11 @@ pub struct SparseMerkleProof {
     ///     - If this is `Some(leaf_node)`
     ///         - If `leaf_node.key` equals requested key, this is an inclusion proof and
     ///           `leaf_node.value_hash` equals the hash of the corresponding account blob.
-    ///         - Otherwise this is a non-inclusion proof, which we do not handle.
-    ///     - If this is `None`, this is also a non-inclusion proof, which we do not handle in the light client.
+    ///         - Otherwise this is a non-inclusion proof. `leaf_node.key` is the only key
+    ///           that exists in the subtree and `leaf_node.value_hash` equals the hash of
+    ///           the corresponding account blob.
+    ///     - If this is `None`, this is also a non-inclusion proof which indicates the subtree is
+    ///       empty.
     leaf: Option<SparseMerkleLeafNode>,
 
     /// All siblings in this proof, including the default ones. Siblings are ordered from the bottom
@@ -47,22 +50,16 @@ impl SparseMerkleProof {
     /// A `Result` which is `Ok` if the element exists in
     /// the Sparse Merkle Tree and the proof is valid, and
     /// `Err` otherwise.
-    #[allow(dead_code)]
     pub fn verify_by_hash(
         &self,
         expected_root_hash: HashValue,
         element_key: HashValue,
         element_hash: HashValue,
     ) -> Result<HashValue> {
-        ensure!(
-            self.siblings.len() <= HASH_LENGTH * 8,
-            "Sparse Merkle Tree proof has more than {} ({}) siblings.",
-            256,
-            self.siblings.len(),
-        );
-
         // Proof need to contain leaf if proof of inclusion
-        let leaf = self.leaf.unwrap();
+        let Some(leaf) = self.leaf else {
+            bail!("Expected inclusion proof. Found non-inclusion proof.");
+        };
         ensure!(
             element_key == leaf.key(),
             "Keys do not match. Key in proof: {:x}. Expected key: {:x}. \
@@ -79,6 +76,78 @@ impl SparseMerkleProof {
             element_key
         );
 
+        self.verify_root(expected_root_hash, element_key)
+    }
+
+    /// Verifies that no element whose key is `element_key`
+    /// exists in the Sparse Merkle Tree using the provided proof.
+    ///
+    /// The proof either ends in an empty subtree, or in a subtree
+    /// containing a single leaf with a different key that shares
+    /// the path to the subtree with `element_key`.
+    ///
+    /// # Arguments
+    ///
+    /// * `expected_root_hash: HashValue` - The expected root hash of the Sparse Merkle Tree.
+    /// * `element_key: HashValue` - The key of the element that should be absent.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which is `Ok` if the element does not exist in
+    /// the Sparse Merkle Tree and the proof is valid, and
+    /// `Err` otherwise.
+    pub fn verify_non_inclusion(
+        &self,
+        expected_root_hash: HashValue,
+        element_key: HashValue,
+    ) -> Result<HashValue> {
+        if let Some(leaf) = self.leaf {
+            // This is a non-inclusion proof where another leaf is the
+            // only one in the subtree the key would end up in.
+            ensure!(
+                element_key != leaf.key(),
+                "Expected non-inclusion proof, but key exists in proof."
+            );
+            ensure!(
+                element_key.common_prefix_bits_len(leaf.key()) >= self.siblings.len(),
+                "Key would not have ended up in the subtree where the provided key in proof \
+                 is the only existing key, if it existed. So this is not a valid \
+                 non-inclusion proof."
+            );
+        }
+
+        self.verify_root(expected_root_hash, element_key)
+    }
+
+    /// Reconstructs the root hash of the Sparse Merkle Tree from
+    /// the leaf of the proof, or the placeholder of an empty
+    /// subtree, and checks it against the expected one.
+    ///
+    /// # Arguments
+    ///
+    /// * `expected_root_hash: HashValue` - The expected root hash of the Sparse Merkle Tree.
+    /// * `element_key: HashValue` - The key used to walk the path to the root.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which is `Ok` with the reconstructed root hash if
+    /// it matches the expected one, and `Err` otherwise.
+    fn verify_root(
+        &self,
+        expected_root_hash: HashValue,
+        element_key: HashValue,
+    ) -> Result<HashValue> {
+        ensure!(
+            self.siblings.len() <= HASH_LENGTH * 8,
+            "Sparse Merkle Tree proof has more than {} ({}) siblings.",
+            256,
+            self.siblings.len(),
+        );
+
+        let current_hash = self
+            .leaf
+            .map_or(SPARSE_MERKLE_PLACEHOLDER_HASH, |leaf| leaf.hash());
+
         let reconstructed_root = self
             .siblings
             .iter()
@@ -89,7 +158,7 @@ impl SparseMerkleProof {
                     .rev()
                     .skip(HASH_LENGTH * 8 - self.siblings.len()),
             )
-            .fold(leaf.hash(), accumulator_update);
+            .fold(current_hash, accumulator_update);
 
         ensure!(
             reconstructed_root == expected_root_hash,
@@ -138,14 +207,14 @@ impl SparseMerkleProof {
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, TypesError> {
         let mut buf = bytes;
 
-        let leaf = match buf.get_u8() {
+        let leaf = match read_u8(&mut buf, "SparseMerkleProof")? {
             1 => {
                 let node = SparseMerkleLeafNode::from_bytes(
                     buf.chunk().get(..2 * HASH_LENGTH).ok_or_else(|| {
                         serde_error!("SparseMerkleProof", "Not enough data for leaf")
                     })?,
                 )
-                    .map_err(|e| serde_error!("SparseMerkleProof", e))?;
+                .map_err(|e| serde_error!("SparseMerkleProof", e))?;
                 buf.advance(2 * HASH_LENGTH);
 
                 Some(node)
@@ -163,7 +232,7 @@ impl SparseMerkleProof {
                 HashValue::from_slice(buf.chunk().get(..HASH_LENGTH).ok_or_else(|| {
                     serde_error!("SparseMerkleProof", "Not enough data for sibling")
                 })?)
-                    .map_err(|e| serde_error!("SparseMerkleProof", e))?;
+                .map_err(|e| serde_error!("SparseMerkleProof", e))?;
             buf.advance(HASH_LENGTH);
             siblings.push(sibling);
         }
@@ -201,7 +270,7 @@ fn accumulator_update(acc_hash: HashValue, (sibling_hash, bit): (&HashValue, boo
 #[cfg(test)]
 mod test {
     use crate::crypto::hash::CryptoHash;
-    use crate::crypto::hash::{hash_data, HashValue, HASH_LENGTH};
+    use crate::crypto::hash::{hash_data, HashValue, HASH_LENGTH, SPARSE_MERKLE_PLACEHOLDER_HASH};
     use crate::merkle::node::{
         MerkleInternalNode, SparseMerkleInternalHasher, SparseMerkleLeafNode,
     };
@@ -222,7 +291,7 @@ mod test {
                 128, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
                 0, 0, 0, 0, 0,
             ])
-                .unwrap(),
+            .unwrap(),
             HashValue::from_slice(a_leaf_hash).unwrap(),
         );
 
@@ -257,6 +326,101 @@ mod test {
             .unwrap();
     }
 
+    fn reconstruct_root(
+        siblings: &[HashValue],
+        key: HashValue,
+        start_hash: HashValue,
+    ) -> HashValue {
+        siblings
+            .iter()
+            .rev()
+            .zip(key.iter_bits().rev().skip(HASH_LENGTH * 8 - siblings.len()))
+            .fold(start_hash, |acc_hash, (sibling_hash, bit)| {
+                if bit {
+                    MerkleInternalNode::<SparseMerkleInternalHasher>::new(*sibling_hash, acc_hash)
+                        .hash()
+                } else {
+                    MerkleInternalNode::<SparseMerkleInternalHasher>::new(acc_hash, *sibling_hash)
+                        .hash()
+                }
+            })
+    }
+
+    #[test]
+    fn test_verify_non_inclusion_empty_subtree() {
+        let siblings = vec![HashValue::new(hash_data(&[], vec!["b".as_bytes()]))];
+        let mut key = [0; HASH_LENGTH];
+        key[0] = 128;
+        let key = HashValue::new(key);
+
+        let proof = SparseMerkleProof {
+            leaf: None,
+            siblings: siblings.clone(),
+        };
+
+        let expected_root_hash = reconstruct_root(&siblings, key, SPARSE_MERKLE_PLACEHOLDER_HASH);
+
+        proof.verify_non_inclusion(expected_root_hash, key).unwrap();
+
+        // An empty subtree can not prove inclusion
+        assert!(proof
+            .verify_by_hash(expected_root_hash, key, HashValue::default())
+            .is_err());
+    }
+
+    #[test]
+    fn test_verify_non_inclusion_different_leaf() {
+        let siblings = vec![HashValue::new(hash_data(&[], vec!["b".as_bytes()]))];
+        let mut leaf_key = [0; HASH_LENGTH];
+        leaf_key[0] = 128;
+        leaf_key[HASH_LENGTH - 1] = 1;
+        let leaf_node = SparseMerkleLeafNode::new(
+            HashValue::new(leaf_key),
+            HashValue::new(hash_data(&[], vec!["a".as_bytes()])),
+        );
+
+        let proof = SparseMerkleProof {
+            leaf: Some(leaf_node),
+            siblings: siblings.clone(),
+        };
+
+        let expected_root_hash = reconstruct_root(&siblings, leaf_node.key(), leaf_node.hash());
+
+        // Shares the path to the subtree with the leaf in the proof
+        let mut key = [0; HASH_LENGTH];
+        key[0] = 192;
+        proof
+            .verify_non_inclusion(expected_root_hash, HashValue::new(key))
+            .unwrap();
+
+        // The key exists in the tree
+        assert!(proof
+            .verify_non_inclusion(expected_root_hash, leaf_node.key())
+            .is_err());
+
+        // The key would not end up in the subtree of the leaf
+        assert!(proof
+            .verify_non_inclusion(expected_root_hash, HashValue::new([0; HASH_LENGTH]))
+            .is_err());
+    }
+
+    #[test]
+    fn test_common_prefix_bits_len() {
+        let mut lhs = [0; HASH_LENGTH];
+        lhs[0] = 0b1010_0000;
+        let mut rhs = [0; HASH_LENGTH];
+        rhs[0] = 0b1011_0000;
+
+        assert_eq!(
+            HashValue::new(lhs).common_prefix_bits_len(HashValue::new(rhs)),
+            3
+        );
+        assert_eq!(
+            HashValue::new(lhs).common_prefix_bits_len(HashValue::new(lhs)),
+            HASH_LENGTH * 8
+        );
+    }
+
     #[cfg(feature = "aptos")]
     #[test]
     fn test_aptos_data() {
@@ -287,6 +451,44 @@ mod test {
         );
     }
 
+    #[cfg(feature = "aptos")]
+    #[test]
+    fn test_sparse_merkle_placeholder_hash() {
+        assert_eq!(
+            SPARSE_MERKLE_PLACEHOLDER_HASH.to_vec(),
+            aptos_crypto::hash::SPARSE_MERKLE_PLACEHOLDER_HASH.to_vec()
+        );
+    }
+
+    #[cfg(feature = "aptos")]
+    #[test]
+    fn test_aptos_data_non_inclusion() {
+        use crate::aptos_test_utils::wrapper::AptosWrapper;
+
+        let mut aptos_wrapper = AptosWrapper::new(40, 1, 1).unwrap();
+        aptos_wrapper.generate_traffic().unwrap();
+
+        let proof_assets = aptos_wrapper
+            .get_latest_proof_absent_account(aptos_types::account_address::AccountAddress::new(
+                [42; 32],
+            ))
+            .unwrap();
+        assert!(proof_assets.state_value().is_none());
+
+        let intern_proof =
+            SparseMerkleProof::from_bytes(&bcs::to_bytes(proof_assets.state_proof()).unwrap())
+                .unwrap();
+        let key = HashValue::from_slice(proof_assets.key().to_vec()).unwrap();
+        let root_hash = HashValue::from_slice(proof_assets.root_hash().to_vec()).unwrap();
+
+        intern_proof.verify_non_inclusion(root_hash, key).unwrap();
+
+        // The same proof can not be used to prove an inclusion
+        assert!(intern_proof
+            .verify_by_hash(root_hash, key, HashValue::default())
+            .is_err());
+    }
+
     #[cfg(feature = "aptos")]
     #[test]
     fn test_bytes_conversion_sparse_merkle_proof() {
@@ -306,4 +508,4 @@ mod test {
 
         assert_eq!(aptos_proof_bytes, lc_sparse_proof_bytes);
     }
-}
\ No newline at end of file
+}
@@ -1,14 +1,15 @@
-use crate::crypto::hash::{CryptoHash, HashValue, HASH_LENGTH};
-use crate::merkle::node::MerkleInternalNode;
+use crate::crypto::hash::{hash_data, prefixed_sha3, HashValue};
+use crate::merkle::accumulator::{
+    accumulator_range_root_hash, accumulator_root_hash, read_siblings, siblings_from_bytes,
+    siblings_to_bytes,
+};
 use crate::merkle::node::TransactionAccumulatorHasher;
 use crate::serde_error;
 use crate::types::error::TypesError;
-use crate::types::utils::{read_leb128, write_leb128};
 use anyhow::{ensure, Result};
-use bytes::{Buf, BufMut, BytesMut};
 use serde::{Deserialize, Serialize};
 
-pub const MAX_ACCUMULATOR_PROOF_DEPTH: usize = 63;
+pub use crate::merkle::accumulator::MAX_ACCUMULATOR_PROOF_DEPTH;
 
 /// A proof that can be used authenticate an element in an
 /// accumulator given trusted root hash.
@@ -38,42 +39,11 @@ impl TransactionAccumulatorProof {
         element_hash: HashValue,
         element_index: u64,
     ) -> Result<()> {
-        ensure!(
-            self.siblings.len() <= MAX_ACCUMULATOR_PROOF_DEPTH,
-            "Accumulator proof has more than {} ({}) siblings.",
-            MAX_ACCUMULATOR_PROOF_DEPTH,
-            self.siblings.len()
-        );
-
-        let actual_root_hash = self
-            .siblings
-            .iter()
-            .fold(
-                (element_hash, element_index),
-                // `index` denotes the index of the ancestor of the element at the current level.
-                |(hash, index), sibling_hash| {
-                    (
-                        if index % 2 == 0 {
-                            // the current node is a left child.
-                            MerkleInternalNode::<TransactionAccumulatorHasher>::new(
-                                hash,
-                                *sibling_hash,
-                            )
-                                .hash()
-                        } else {
-                            // the current node is a right child.
-                            MerkleInternalNode::<TransactionAccumulatorHasher>::new(
-                                *sibling_hash,
-                                hash,
-                            )
-                                .hash()
-                        },
-                        // The index of the parent at its level.
-                        index / 2,
-                    )
-                },
-            )
-            .0;
+        let actual_root_hash = accumulator_root_hash::<TransactionAccumulatorHasher>(
+            &self.siblings,
+            element_hash,
+            element_index,
+        )?;
         ensure!(
             actual_root_hash == expected_root_hash,
             "{}: Root hashes do not match.",
@@ -89,12 +59,7 @@ impl TransactionAccumulatorProof {
     ///
     /// A `Vec<u8>` representing the `TransactionAccumulatorProof`.
     pub fn to_bytes(&self) -> Vec<u8> {
-        let mut bytes = BytesMut::new();
-        bytes.put_slice(&write_leb128(self.siblings.len() as u64));
-        for sibling in &self.siblings {
-            bytes.put_slice(sibling.as_ref());
-        }
-        bytes.to_vec()
+        siblings_to_bytes(&self.siblings)
     }
 
     /// Creates a `TransactionAccumulatorProof` from a byte slice.
@@ -107,40 +72,194 @@ impl TransactionAccumulatorProof {
     ///
     /// A `Result` which is `Ok` if the `TransactionAccumulatorProof` could be successfully created, and `Err` otherwise.
     pub fn from_bytes(bytes: &[u8]) -> std::result::Result<Self, TypesError> {
-        let mut buf = BytesMut::from(bytes);
-        let (len, read_bytes) = read_leb128(&buf).map_err(|_| {
-            serde_error!("TransactionAccumulatorProof", "Not enough data for length")
-        })?;
-        buf.advance(read_bytes);
-        let mut siblings = Vec::with_capacity(len as usize);
-        for _ in 0..len {
-            if buf.remaining() < HASH_LENGTH {
-                return Err(serde_error!(
-                    "TransactionAccumulatorProof",
-                    "Not enough bytes to read HashValue"
-                ));
-            }
-            let mut hash_value = [0u8; HASH_LENGTH];
-            buf.copy_to_slice(&mut hash_value);
-            siblings.push(
-                HashValue::from_slice(hash_value)
-                    .map_err(|e| serde_error!("TransactionAccumulatorProof", e))?,
-            );
+        Ok(Self {
+            siblings: siblings_from_bytes(bytes, "TransactionAccumulatorProof")?,
+        })
+    }
+}
+
+/// A proof that can be used to authenticate a contiguous range of
+/// elements in an accumulator given trusted root hash.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TransactionAccumulatorRangeProof {
+    /// The siblings on the left of the path from the first element to the
+    /// root. Siblings are ordered from the bottom level to the root level.
+    left_siblings: Vec<HashValue>,
+    /// The siblings on the right of the path from the last element to the
+    /// root. Siblings are ordered from the bottom level to the root level.
+    right_siblings: Vec<HashValue>,
+}
+
+impl TransactionAccumulatorRangeProof {
+    /// Creates a new `TransactionAccumulatorRangeProof`.
+    ///
+    /// # Arguments
+    ///
+    /// * `left_siblings: Vec<HashValue>` - The siblings on the left of the range.
+    /// * `right_siblings: Vec<HashValue>` - The siblings on the right of the range.
+    ///
+    /// # Returns
+    ///
+    /// A new `TransactionAccumulatorRangeProof` instance.
+    pub const fn new(left_siblings: Vec<HashValue>, right_siblings: Vec<HashValue>) -> Self {
+        Self {
+            left_siblings,
+            right_siblings,
         }
+    }
+
+    /// Verifies that the elements whose hashes are `element_hashes` exist in the accumulator
+    /// whose root hash is `expected_root_hash`, at consecutive versions starting from
+    /// `first_element_index`.
+    ///
+    /// # Arguments
+    ///
+    /// * `expected_root_hash: HashValue` - The expected root hash of the Transaction Accumulator.
+    /// * `first_element_index: u64` - The index of the first element to verify.
+    /// * `element_hashes: &[HashValue]` - The hashes of the elements to verify, in order.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which is `Ok` if the elements exist in the Transaction Accumulator and the proof is valid, and `Err` otherwise.
+    pub fn verify(
+        &self,
+        expected_root_hash: HashValue,
+        first_element_index: u64,
+        element_hashes: &[HashValue],
+    ) -> Result<()> {
+        let actual_root_hash = accumulator_range_root_hash::<TransactionAccumulatorHasher>(
+            &self.left_siblings,
+            &self.right_siblings,
+            first_element_index,
+            element_hashes,
+        )?;
+        ensure!(
+            actual_root_hash == expected_root_hash,
+            "{}: Root hashes do not match.",
+            "AccumulatorRangeProof",
+        );
+
+        Ok(())
+    }
+
+    /// Converts the `TransactionAccumulatorRangeProof` to a byte vector.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<u8>` representing the `TransactionAccumulatorRangeProof`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [
+            siblings_to_bytes(&self.left_siblings),
+            siblings_to_bytes(&self.right_siblings),
+        ]
+        .concat()
+    }
 
-        if buf.remaining() != 0 {
+    /// Creates a `TransactionAccumulatorRangeProof` from a byte slice.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes: &[u8]` - A byte slice from which to create the `TransactionAccumulatorRangeProof`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which is `Ok` if the `TransactionAccumulatorRangeProof` could be successfully created, and `Err` otherwise.
+    pub fn from_bytes(bytes: &[u8]) -> std::result::Result<Self, TypesError> {
+        let mut buf = bytes;
+        let left_siblings = read_siblings(&mut buf, "TransactionAccumulatorRangeProof")?;
+        let right_siblings = read_siblings(&mut buf, "TransactionAccumulatorRangeProof")?;
+
+        if !buf.is_empty() {
             return Err(serde_error!(
-                "TransactionAccumulatorProof",
+                "TransactionAccumulatorRangeProof",
                 "Unexpected data after completing deserialization"
             ));
         }
 
-        Ok(Self { siblings })
+        Ok(Self {
+            left_siblings,
+            right_siblings,
+        })
     }
 }
 
-#[cfg(all(test, feature = "aptos"))]
+/// Computes the digest of the hashes of a range of `TransactionInfo`s, as
+/// committed by the transaction range program.
+///
+/// # Arguments
+///
+/// * `transaction_hashes: &[HashValue]` - The hashes of the `TransactionInfo`s, in order.
+///
+/// # Returns
+///
+/// A `HashValue` representing the digest of the range.
+pub fn transaction_infos_digest(transaction_hashes: &[HashValue]) -> HashValue {
+    HashValue::new(hash_data(
+        &prefixed_sha3(b"TransactionInfoRange"),
+        transaction_hashes
+            .iter()
+            .map(|hash| hash.as_ref().as_slice())
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
 mod test {
+    use crate::crypto::hash::{CryptoHash, HashValue};
+    use crate::merkle::node::{MerkleInternalNode, TransactionAccumulatorHasher};
+    use crate::merkle::transaction_proof::{
+        transaction_infos_digest, TransactionAccumulatorRangeProof,
+    };
+
+    fn node(left: HashValue, right: HashValue) -> HashValue {
+        MerkleInternalNode::<TransactionAccumulatorHasher>::new(left, right).hash()
+    }
+
+    #[test]
+    fn test_transaction_accumulator_range_proof() {
+        let leaves = [[1; 32], [2; 32], [3; 32], [4; 32]].map(HashValue::new);
+        let left = node(leaves[0], leaves[1]);
+        let right = node(leaves[2], leaves[3]);
+        let root = node(left, right);
+
+        // A range in the middle of the tree
+        let proof = TransactionAccumulatorRangeProof::new(vec![leaves[0]], vec![leaves[3]]);
+        proof.verify(root, 1, &leaves[1..3]).unwrap();
+        assert!(proof.verify(root, 2, &leaves[1..3]).is_err());
+        assert!(proof.verify(root, 1, &leaves[1..2]).is_err());
+        assert!(proof.verify(root, 1, &[]).is_err());
+
+        // The whole tree needs no sibling
+        TransactionAccumulatorRangeProof::new(vec![], vec![])
+            .verify(root, 0, &leaves)
+            .unwrap();
+
+        // A single leaf only has siblings on one side
+        let proof = TransactionAccumulatorRangeProof::new(vec![leaves[2], left], vec![]);
+        proof.verify(root, 3, &leaves[3..]).unwrap();
+
+        let proof_bytes = proof.to_bytes();
+        assert_eq!(proof_bytes.len(), 1 + 2 * 32 + 1);
+        assert_eq!(
+            TransactionAccumulatorRangeProof::from_bytes(&proof_bytes)
+                .unwrap()
+                .to_bytes(),
+            proof_bytes
+        );
+        assert!(TransactionAccumulatorRangeProof::from_bytes(&proof_bytes[..65]).is_err());
+        assert!(TransactionAccumulatorRangeProof::from_bytes(
+            &[proof_bytes.as_slice(), &[0]].concat()
+        )
+        .is_err());
+
+        // The order of the hashes is committed to
+        assert_ne!(
+            transaction_infos_digest(&leaves[..2]),
+            transaction_infos_digest(&[leaves[1], leaves[0]])
+        );
+    }
+
+    #[cfg(feature = "aptos")]
     #[test]
     fn test_bytes_conversion_transaction_accumulator_proof() {
         use crate::aptos_test_utils::wrapper::AptosWrapper;
@@ -161,10 +280,10 @@ mod test {
         assert_eq!(aptos_proof_bytes, lc_sparse_proof_bytes);
     }
 
+    #[cfg(feature = "aptos")]
     #[test]
     fn test_transaction_accumulator() {
         use crate::aptos_test_utils::wrapper::AptosWrapper;
-        use crate::crypto::hash::HashValue;
         use crate::merkle::transaction_proof::TransactionAccumulatorProof;
         use aptos_crypto::hash::CryptoHash;
 
@@ -181,17 +300,64 @@ mod test {
                 .transaction_accumulator_hash()
                 .as_ref(),
         )
-            .unwrap();
+        .unwrap();
         let element_hash =
             HashValue::from_slice(proof_assets.transaction().hash().as_ref()).unwrap();
         let element_index = *proof_assets.transaction_version();
         let proof = TransactionAccumulatorProof::from_bytes(
             &bcs::to_bytes(proof_assets.transaction_proof()).unwrap(),
         )
-            .unwrap();
+        .unwrap();
 
         proof
             .verify(expected_root_hash, element_hash, element_index)
             .unwrap()
     }
-}
\ No newline at end of file
+
+    #[cfg(feature = "aptos")]
+    #[test]
+    fn test_transaction_accumulator_range() {
+        use crate::aptos_test_utils::wrapper::AptosWrapper;
+        use crate::types::transaction::TransactionInfo;
+
+        let mut aptos_wrapper = AptosWrapper::new(40, 1, 1).unwrap();
+        aptos_wrapper.generate_traffic().unwrap();
+
+        let latest_li = aptos_wrapper.get_latest_li().unwrap();
+        let expected_root_hash = HashValue::from_slice(
+            latest_li
+                .ledger_info()
+                .transaction_accumulator_hash()
+                .as_ref(),
+        )
+        .unwrap();
+
+        let first_version = 3;
+        let aptos_proof = aptos_wrapper
+            .get_latest_transaction_infos_with_proof(first_version, 10)
+            .unwrap();
+        let aptos_proof_bytes =
+            bcs::to_bytes(&aptos_proof.ledger_info_to_transaction_infos_proof).unwrap();
+
+        let proof = TransactionAccumulatorRangeProof::from_bytes(&aptos_proof_bytes).unwrap();
+        assert_eq!(proof.to_bytes(), aptos_proof_bytes);
+
+        let transaction_hashes: Vec<HashValue> = aptos_proof
+            .transaction_infos
+            .iter()
+            .map(|info| {
+                TransactionInfo::from_bytes(&bcs::to_bytes(info).unwrap())
+                    .unwrap()
+                    .hash()
+            })
+            .collect();
+        assert_eq!(transaction_hashes.len(), 10);
+
+        proof
+            .verify(expected_root_hash, first_version, &transaction_hashes)
+            .unwrap();
+        assert!(proof
+            .verify(expected_root_hash, first_version, &transaction_hashes[1..])
+            .is_err());
+    }
+}
@@ -0,0 +1,143 @@
+//! Serialized inputs of the proofs, as read by the programs and checked by
+//! [`crate::verify`].
+
+use getset::Getters;
+use serde::{Deserialize, Serialize};
+
+/// A `SparseMerkleProof` of a leaf in the state tree, with the key and the
+/// value hash of the leaf.
+#[derive(Clone, Debug, Getters, Serialize, Deserialize)]
+#[getset(get = "pub")]
+pub struct SparseMerkleProofAssets {
+    sparse_merkle_proof: Vec<u8>,
+    leaf_key: [u8; 32],
+    leaf_hash: [u8; 32],
+}
+
+impl SparseMerkleProofAssets {
+    pub const fn new(
+        sparse_merkle_proof: Vec<u8>,
+        leaf_key: [u8; 32],
+        leaf_hash: [u8; 32],
+    ) -> SparseMerkleProofAssets {
+        SparseMerkleProofAssets {
+            sparse_merkle_proof,
+            leaf_key,
+            leaf_hash,
+        }
+    }
+}
+
+/// A `TransactionInfo`, its version and its proof in the transaction
+/// accumulator of a `LedgerInfoWithSignatures`.
+#[derive(Clone, Debug, Getters, Serialize, Deserialize)]
+#[getset(get = "pub")]
+pub struct TransactionProofAssets {
+    transaction: Vec<u8>,
+    transaction_index: u64,
+    transaction_proof: Vec<u8>,
+    latest_li: Vec<u8>,
+}
+
+impl TransactionProofAssets {
+    pub const fn new(
+        transaction: Vec<u8>,
+        transaction_index: u64,
+        transaction_proof: Vec<u8>,
+        latest_li: Vec<u8>,
+    ) -> TransactionProofAssets {
+        TransactionProofAssets {
+            transaction,
+            transaction_index,
+            transaction_proof,
+            latest_li,
+        }
+    }
+}
+
+/// The `ValidatorVerifier` expected to have signed a `LedgerInfoWithSignatures`.
+#[derive(Clone, Debug, Getters, Serialize, Deserialize)]
+#[getset(get = "pub")]
+pub struct ValidatorVerifierAssets {
+    validator_verifier: Vec<u8>,
+}
+
+impl ValidatorVerifierAssets {
+    pub const fn new(validator_verifier: Vec<u8>) -> ValidatorVerifierAssets {
+        ValidatorVerifierAssets { validator_verifier }
+    }
+}
+
+/// A `ContractEvent`, its index among the events of its transaction and its
+/// proof in the event accumulator of the `TransactionInfo`.
+#[derive(Clone, Debug, Getters, Serialize, Deserialize)]
+#[getset(get = "pub")]
+pub struct EventProofAssets {
+    event: Vec<u8>,
+    event_index: u64,
+    event_proof: Vec<u8>,
+}
+
+impl EventProofAssets {
+    pub const fn new(event: Vec<u8>, event_index: u64, event_proof: Vec<u8>) -> EventProofAssets {
+        EventProofAssets {
+            event,
+            event_index,
+            event_proof,
+        }
+    }
+}
+
+/// Contiguous `TransactionInfo`s, the version of the first one and their
+/// range proof in the transaction accumulator of a `LedgerInfoWithSignatures`.
+#[derive(Clone, Debug, Getters, Serialize, Deserialize)]
+#[getset(get = "pub")]
+pub struct TransactionRangeProofAssets {
+    transactions: Vec<Vec<u8>>,
+    first_version: u64,
+    range_proof: Vec<u8>,
+    latest_li: Vec<u8>,
+}
+
+impl TransactionRangeProofAssets {
+    pub const fn new(
+        transactions: Vec<Vec<u8>>,
+        first_version: u64,
+        range_proof: Vec<u8>,
+        latest_li: Vec<u8>,
+    ) -> TransactionRangeProofAssets {
+        TransactionRangeProofAssets {
+            transactions,
+            first_version,
+            range_proof,
+            latest_li,
+        }
+    }
+}
+
+/// The `TransactionAccumulatorSummary` at a previous `LedgerInfoWithSignatures`,
+/// and the `AccumulatorConsistencyProof` extending it to the latest one.
+#[derive(Clone, Debug, Getters, Serialize, Deserialize)]
+#[getset(get = "pub")]
+pub struct AccumulatorConsistencyProofAssets {
+    accumulator_summary: Vec<u8>,
+    prev_li: Vec<u8>,
+    consistency_proof: Vec<u8>,
+    latest_li: Vec<u8>,
+}
+
+impl AccumulatorConsistencyProofAssets {
+    pub const fn new(
+        accumulator_summary: Vec<u8>,
+        prev_li: Vec<u8>,
+        consistency_proof: Vec<u8>,
+        latest_li: Vec<u8>,
+    ) -> AccumulatorConsistencyProofAssets {
+        AccumulatorConsistencyProofAssets {
+            accumulator_summary,
+            prev_li,
+            consistency_proof,
+            latest_li,
+        }
+    }
+}
@@ -1,4 +1,3 @@
-
 // SPDX-License-Identifier: Apache-2.0
 use crate::crypto::hash::{HashValue, HASH_LENGTH};
 use crate::serde_error;
@@ -118,7 +117,10 @@ impl BlockInfo {
     /// A `Result` which is `Ok` if the `BlockInfo` could
     /// be successfully created, and `Err` otherwise.
     pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, TypesError> {
-        let epoch_state_size = bytes.len() - 4 * U64_SIZE - 2 * HASH_LENGTH - 1;
+        let epoch_state_size = bytes
+            .len()
+            .checked_sub(4 * U64_SIZE + 2 * HASH_LENGTH + 1)
+            .ok_or_else(|| serde_error!("BlockInfo", "Not enough data for fixed fields"))?;
 
         let epoch = bytes.get_u64_le();
         let round = bytes.get_u64_le();
@@ -129,7 +131,7 @@ impl BlockInfo {
                 .get(..HASH_LENGTH)
                 .ok_or_else(|| serde_error!("BlockInfo", "Not enough data for id"))?,
         )
-            .map_err(|e| serde_error!("BlockInfo", e))?;
+        .map_err(|e| serde_error!("BlockInfo", e))?;
 
         bytes.advance(HASH_LENGTH); // Advance the buffer by the size of HashValue
 
@@ -137,7 +139,7 @@ impl BlockInfo {
             HashValue::from_slice(bytes.chunk().get(..HASH_LENGTH).ok_or_else(|| {
                 serde_error!("BlockInfo", "Not enough data for executed_state_id")
             })?)
-                .map_err(|e| serde_error!("BlockInfo", e))?;
+            .map_err(|e| serde_error!("BlockInfo", e))?;
 
         bytes.advance(HASH_LENGTH); // Advance the buffer by the size of HashValue
 
@@ -150,7 +152,7 @@ impl BlockInfo {
                     EpochState::from_bytes(bytes.chunk().get(..epoch_state_size).ok_or_else(
                         || serde_error!("BlockInfo", "Not enough data for epoch state"),
                     )?)
-                        .map_err(|e| serde_error!("BlockInfo", e))?;
+                    .map_err(|e| serde_error!("BlockInfo", e))?;
                 bytes.advance(epoch_state_size);
 
                 Some(epoch_state)
@@ -203,10 +205,10 @@ impl BlockInfo {
         Ok(fixed_size
             + 1
             + EpochState::estimate_size_from_bytes(
-            bytes
-                .get(fixed_size + 1..)
-                .ok_or_else(|| serde_error!("BlockInfo", "Not enough data for EpochState"))?,
-        )?)
+                bytes
+                    .get(fixed_size + 1..)
+                    .ok_or_else(|| serde_error!("BlockInfo", "Not enough data for EpochState"))?,
+            )?)
     }
 }
 
@@ -245,4 +247,4 @@ mod test {
             assert_eq!(bytes, block_info_serialized);
         }
     }
-}
\ No newline at end of file
+}
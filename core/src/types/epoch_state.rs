@@ -88,7 +88,10 @@ impl EpochState {
     /// A `Result` which is `Ok` if the `EpochState` could
     /// be successfully created, and `Err` otherwise.
     pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, TypesError> {
-        let validator_verifier_size = bytes.len() - U64_SIZE;
+        let validator_verifier_size = bytes
+            .len()
+            .checked_sub(U64_SIZE)
+            .ok_or_else(|| serde_error!("EpochState", "Not enough data for epoch"))?;
 
         let epoch = bytes.get_u64_le();
 
@@ -99,7 +102,7 @@ impl EpochState {
                 .get(..validator_verifier_size)
                 .ok_or_else(|| serde_error!("EpochState", "Not enough data for verifier"))?,
         )
-            .map_err(|e| serde_error!("EpochState", e))?;
+        .map_err(|e| serde_error!("EpochState", e))?;
         bytes.advance(validator_verifier_size);
 
         if bytes.remaining() != 0 {
@@ -128,10 +131,10 @@ impl EpochState {
     pub(crate) fn estimate_size_from_bytes(bytes: &[u8]) -> Result<usize, TypesError> {
         Ok(U64_SIZE
             + ValidatorVerifier::estimate_size_from_bytes(
-            bytes
-                .get(U64_SIZE..)
-                .ok_or_else(|| serde_error!("EpochState", "Not enough data for verifier"))?,
-        )?)
+                bytes
+                    .get(U64_SIZE..)
+                    .ok_or_else(|| serde_error!("EpochState", "Not enough data for verifier"))?,
+            )?)
     }
 }
 
@@ -171,4 +174,4 @@ mod test {
             assert_eq!(bytes, epoch_state_serialized);
         }
     }
-}
\ No newline at end of file
+}
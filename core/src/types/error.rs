@@ -64,4 +64,4 @@ macro_rules! serde_error {
             source: $source.into(),
         }
     };
-}
\ No newline at end of file
+}
@@ -0,0 +1,475 @@
+use crate::crypto::hash::{hash_data, prefixed_sha3, CryptoHash, HashValue};
+use crate::serde_error;
+use crate::types::error::TypesError;
+use crate::types::utils::{
+    put_bytes, put_string, read_bytes, read_len, read_slice, read_string, read_u64, read_u8,
+    write_leb128,
+};
+use crate::types::{AccountAddress, ACCOUNT_ADDRESS_SIZE};
+use bytes::{BufMut, BytesMut};
+use getset::{CopyGetters, Getters};
+use serde::{Deserialize, Serialize};
+
+/// Maximum depth of nested `TypeTag`s, as enforced by Move when
+/// deserializing them.
+pub const MAX_TYPE_TAG_NESTING: u8 = 8;
+
+/// `ContractEvent` is an event emitted by a transaction. The events of
+/// a transaction are the leaves of its event accumulator, whose root is
+/// the `event_root_hash` of its `TransactionInfo`.
+///
+/// It is implemented as an enum to follow the versions of events on Aptos.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ContractEvent {
+    V1(ContractEventV1),
+    V2(ContractEventV2),
+}
+
+impl ContractEvent {
+    /// Returns the key of the event handle that emitted the event. Only
+    /// events emitted through an event handle, `V1` events, have one.
+    ///
+    /// # Returns
+    ///
+    /// The `EventKey` of the event, if any.
+    pub const fn key(&self) -> Option<&EventKey> {
+        match self {
+            ContractEvent::V1(event) => Some(&event.key),
+            ContractEvent::V2(_) => None,
+        }
+    }
+
+    /// Returns the type of the data of the event.
+    ///
+    /// # Returns
+    ///
+    /// The `TypeTag` of the event.
+    pub const fn type_tag(&self) -> &TypeTag {
+        match self {
+            ContractEvent::V1(event) => &event.type_tag,
+            ContractEvent::V2(event) => &event.type_tag,
+        }
+    }
+
+    /// Returns the BCS-serialized data of the event.
+    ///
+    /// # Returns
+    ///
+    /// The data of the event.
+    pub fn event_data(&self) -> &[u8] {
+        match self {
+            ContractEvent::V1(event) => &event.event_data,
+            ContractEvent::V2(event) => &event.event_data,
+        }
+    }
+
+    /// Converts the `ContractEvent` to a byte vector.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<u8>` representing the `ContractEvent`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = BytesMut::new();
+        match self {
+            ContractEvent::V1(event) => {
+                bytes.put_u8(0);
+                bytes.put_slice(&event.key.to_bytes());
+                bytes.put_u64_le(event.sequence_number);
+                bytes.put_slice(&event.type_tag.to_bytes());
+                put_bytes(&mut bytes, &event.event_data);
+            }
+            ContractEvent::V2(event) => {
+                bytes.put_u8(1);
+                bytes.put_slice(&event.type_tag.to_bytes());
+                put_bytes(&mut bytes, &event.event_data);
+            }
+        }
+        bytes.to_vec()
+    }
+
+    /// Creates a `ContractEvent` from a byte slice.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes: &[u8]` - A byte slice from which to create the `ContractEvent`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which is `Ok` if the `ContractEvent`
+    /// could be successfully created, and `Err` otherwise.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, TypesError> {
+        let mut buf = bytes;
+        let event = match read_u8(&mut buf, "ContractEvent")? {
+            0 => ContractEvent::V1(ContractEventV1 {
+                key: EventKey::read(&mut buf)?,
+                sequence_number: read_u64(&mut buf, "ContractEventV1")?,
+                type_tag: TypeTag::read(&mut buf, 0)?,
+                event_data: read_bytes(&mut buf, "ContractEventV1")?.to_vec(),
+            }),
+            1 => ContractEvent::V2(ContractEventV2 {
+                type_tag: TypeTag::read(&mut buf, 0)?,
+                event_data: read_bytes(&mut buf, "ContractEventV2")?.to_vec(),
+            }),
+            _ => return Err(serde_error!("ContractEvent", "Invalid variant")),
+        };
+
+        if !buf.is_empty() {
+            return Err(serde_error!(
+                "ContractEvent",
+                "Unexpected data after completing deserialization"
+            ));
+        }
+
+        Ok(event)
+    }
+}
+
+impl CryptoHash for ContractEvent {
+    fn hash(&self) -> HashValue {
+        HashValue::new(hash_data(
+            &prefixed_sha3(b"ContractEvent"),
+            vec![&self.to_bytes()],
+        ))
+    }
+}
+
+/// `ContractEventV1` is an event emitted through an event handle, whose
+/// events are numbered by `sequence_number`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Getters, CopyGetters)]
+pub struct ContractEventV1 {
+    #[getset(get = "pub")]
+    key: EventKey,
+    #[getset(get_copy = "pub")]
+    sequence_number: u64,
+    #[getset(get = "pub")]
+    type_tag: TypeTag,
+    #[getset(get = "pub")]
+    event_data: Vec<u8>,
+}
+
+impl ContractEventV1 {
+    /// Creates a new `ContractEventV1`.
+    ///
+    /// # Arguments
+    ///
+    /// * `key: EventKey` - The key of the event handle emitting the event.
+    /// * `sequence_number: u64` - The number of the event in its handle.
+    /// * `type_tag: TypeTag` - The type of the data of the event.
+    /// * `event_data: Vec<u8>` - The BCS-serialized data of the event.
+    ///
+    /// # Returns
+    ///
+    /// A new `ContractEventV1` instance.
+    pub const fn new(
+        key: EventKey,
+        sequence_number: u64,
+        type_tag: TypeTag,
+        event_data: Vec<u8>,
+    ) -> Self {
+        Self {
+            key,
+            sequence_number,
+            type_tag,
+            event_data,
+        }
+    }
+}
+
+/// `ContractEventV2` is a module event, identified by its type only.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Getters)]
+#[getset(get = "pub")]
+pub struct ContractEventV2 {
+    type_tag: TypeTag,
+    event_data: Vec<u8>,
+}
+
+impl ContractEventV2 {
+    /// Creates a new `ContractEventV2`.
+    ///
+    /// # Arguments
+    ///
+    /// * `type_tag: TypeTag` - The type of the data of the event.
+    /// * `event_data: Vec<u8>` - The BCS-serialized data of the event.
+    ///
+    /// # Returns
+    ///
+    /// A new `ContractEventV2` instance.
+    pub const fn new(type_tag: TypeTag, event_data: Vec<u8>) -> Self {
+        Self {
+            type_tag,
+            event_data,
+        }
+    }
+}
+
+/// `EventKey` identifies an event handle, by the account that created it
+/// and its creation number in that account.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct EventKey {
+    creation_number: u64,
+    account_address: AccountAddress,
+}
+
+impl EventKey {
+    /// Creates a new `EventKey`.
+    ///
+    /// # Arguments
+    ///
+    /// * `creation_number: u64` - The creation number of the event handle.
+    /// * `account_address: AccountAddress` - The account owning the event handle.
+    ///
+    /// # Returns
+    ///
+    /// A new `EventKey` instance.
+    pub const fn new(creation_number: u64, account_address: AccountAddress) -> Self {
+        Self {
+            creation_number,
+            account_address,
+        }
+    }
+
+    /// Converts the `EventKey` to a byte vector.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<u8>` representing the `EventKey`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = BytesMut::new();
+        bytes.put_u64_le(self.creation_number);
+        bytes.put_slice(&self.account_address.to_bytes());
+        bytes.to_vec()
+    }
+
+    /// Reads an `EventKey` from the start of `bytes`, advancing it.
+    fn read(bytes: &mut &[u8]) -> Result<Self, TypesError> {
+        Ok(Self {
+            creation_number: read_u64(bytes, "EventKey")?,
+            account_address: AccountAddress::from_bytes(read_slice(
+                bytes,
+                ACCOUNT_ADDRESS_SIZE,
+                "EventKey",
+            )?)?,
+        })
+    }
+}
+
+/// `TypeTag` is the type of a Move value, here the data of an event.
+///
+/// The order of the variants is the one of their BCS encoding.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum TypeTag {
+    Bool,
+    U8,
+    U64,
+    U128,
+    Address,
+    Signer,
+    Vector(Box<TypeTag>),
+    Struct(Box<StructTag>),
+    U16,
+    U32,
+    U256,
+}
+
+impl TypeTag {
+    /// Converts the `TypeTag` to a byte vector.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<u8>` representing the `TypeTag`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = BytesMut::new();
+        match self {
+            TypeTag::Bool => bytes.put_u8(0),
+            TypeTag::U8 => bytes.put_u8(1),
+            TypeTag::U64 => bytes.put_u8(2),
+            TypeTag::U128 => bytes.put_u8(3),
+            TypeTag::Address => bytes.put_u8(4),
+            TypeTag::Signer => bytes.put_u8(5),
+            TypeTag::Vector(type_tag) => {
+                bytes.put_u8(6);
+                bytes.put_slice(&type_tag.to_bytes());
+            }
+            TypeTag::Struct(struct_tag) => {
+                bytes.put_u8(7);
+                bytes.put_slice(&struct_tag.address.to_bytes());
+                put_string(&mut bytes, &struct_tag.module);
+                put_string(&mut bytes, &struct_tag.name);
+                bytes.put_slice(&write_leb128(struct_tag.type_args.len() as u64));
+                for type_arg in &struct_tag.type_args {
+                    bytes.put_slice(&type_arg.to_bytes());
+                }
+            }
+            TypeTag::U16 => bytes.put_u8(8),
+            TypeTag::U32 => bytes.put_u8(9),
+            TypeTag::U256 => bytes.put_u8(10),
+        }
+        bytes.to_vec()
+    }
+
+    /// Creates a `TypeTag` from a byte slice.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes: &[u8]` - A byte slice from which to create the `TypeTag`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which is `Ok` if the `TypeTag`
+    /// could be successfully created, and `Err` otherwise.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, TypesError> {
+        let mut buf = bytes;
+        let type_tag = Self::read(&mut buf, 0)?;
+
+        if !buf.is_empty() {
+            return Err(serde_error!(
+                "TypeTag",
+                "Unexpected data after completing deserialization"
+            ));
+        }
+
+        Ok(type_tag)
+    }
+
+    /// Reads a `TypeTag` nested in `depth` other type tags from the start
+    /// of `bytes`, advancing it.
+    fn read(bytes: &mut &[u8], depth: u8) -> Result<Self, TypesError> {
+        if depth > MAX_TYPE_TAG_NESTING {
+            return Err(serde_error!("TypeTag", "Exceeded max type tag nesting"));
+        }
+
+        let type_tag = match read_u8(bytes, "TypeTag")? {
+            0 => TypeTag::Bool,
+            1 => TypeTag::U8,
+            2 => TypeTag::U64,
+            3 => TypeTag::U128,
+            4 => TypeTag::Address,
+            5 => TypeTag::Signer,
+            6 => TypeTag::Vector(Box::new(Self::read(bytes, depth + 1)?)),
+            7 => {
+                let address = AccountAddress::from_bytes(read_slice(
+                    bytes,
+                    ACCOUNT_ADDRESS_SIZE,
+                    "StructTag",
+                )?)?;
+                let module = read_string(bytes, "StructTag")?;
+                let name = read_string(bytes, "StructTag")?;
+                let type_args = (0..read_len(bytes, "StructTag")?)
+                    .map(|_| Self::read(bytes, depth + 1))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                TypeTag::Struct(Box::new(StructTag {
+                    address,
+                    module,
+                    name,
+                    type_args,
+                }))
+            }
+            8 => TypeTag::U16,
+            9 => TypeTag::U32,
+            10 => TypeTag::U256,
+            _ => return Err(serde_error!("TypeTag", "Invalid variant")),
+        };
+
+        Ok(type_tag)
+    }
+}
+
+/// `StructTag` is the type of a Move struct, by the module declaring it,
+/// its name and its type arguments.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct StructTag {
+    pub address: AccountAddress,
+    pub module: String,
+    pub name: String,
+    pub type_args: Vec<TypeTag>,
+}
+
+#[cfg(test)]
+mod test {
+    use crate::types::event::{
+        ContractEvent, ContractEventV1, ContractEventV2, EventKey, StructTag, TypeTag,
+    };
+    use crate::types::AccountAddress;
+
+    fn coin_type_tag() -> TypeTag {
+        TypeTag::Struct(Box::new(StructTag {
+            address: AccountAddress::new([1; 32]),
+            module: "coin".to_string(),
+            name: "CoinDeposit".to_string(),
+            type_args: vec![TypeTag::Vector(Box::new(TypeTag::U8)), TypeTag::U256],
+        }))
+    }
+
+    #[test]
+    fn test_bytes_conversion_contract_event() {
+        let events = [
+            ContractEvent::V1(ContractEventV1::new(
+                EventKey::new(2, AccountAddress::new([3; 32])),
+                7,
+                coin_type_tag(),
+                vec![1, 2, 3],
+            )),
+            ContractEvent::V2(ContractEventV2::new(coin_type_tag(), vec![4; 200])),
+        ];
+
+        for event in events {
+            let bytes = event.to_bytes();
+            assert_eq!(ContractEvent::from_bytes(&bytes).unwrap(), event);
+            assert!(ContractEvent::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+            assert!(ContractEvent::from_bytes(&[bytes.as_slice(), &[0]].concat()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_type_tag_nesting() {
+        let nested = |depth| {
+            (0..depth).fold(TypeTag::Bool, |type_tag, _| {
+                TypeTag::Vector(Box::new(type_tag))
+            })
+        };
+
+        let type_tag = nested(8);
+        assert_eq!(TypeTag::from_bytes(&type_tag.to_bytes()).unwrap(), type_tag);
+        assert!(TypeTag::from_bytes(&nested(9).to_bytes()).is_err());
+        assert!(TypeTag::from_bytes(&[11]).is_err());
+    }
+
+    #[cfg(feature = "aptos")]
+    #[test]
+    fn test_contract_event_aptos() {
+        use crate::crypto::hash::CryptoHash as LcCryptoHash;
+        use aptos_crypto::hash::CryptoHash as AptosCryptoHash;
+        use aptos_sdk::move_types::language_storage::TypeTag as AptosTypeTag;
+        use aptos_types::account_address::AccountAddress as AptosAccountAddress;
+        use aptos_types::contract_event::ContractEvent as AptosContractEvent;
+        use aptos_types::event::EventKey as AptosEventKey;
+        use std::str::FromStr;
+
+        let aptos_type_tag = AptosTypeTag::from_str(
+            "0x1::coin::CoinDeposit<vector<u8>, 0x1::aptos_coin::AptosCoin>",
+        )
+        .unwrap();
+        let aptos_events = [
+            AptosContractEvent::new_v1(
+                AptosEventKey::new(2, AptosAccountAddress::new([3; 32])),
+                7,
+                aptos_type_tag.clone(),
+                vec![1, 2, 3],
+            ),
+            AptosContractEvent::new_v2(aptos_type_tag, vec![4; 200]),
+        ];
+
+        for aptos_event in aptos_events {
+            let aptos_bytes = bcs::to_bytes(&aptos_event).unwrap();
+            let lc_event = ContractEvent::from_bytes(&aptos_bytes).unwrap();
+
+            assert_eq!(lc_event.to_bytes(), aptos_bytes);
+            assert_eq!(
+                LcCryptoHash::hash(&lc_event).to_vec(),
+                AptosCryptoHash::hash(&aptos_event).to_vec()
+            );
+        }
+    }
+}
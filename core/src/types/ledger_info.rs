@@ -123,21 +123,26 @@ impl LedgerInfo {
     /// A `Result` which is `Ok` if the `LedgerInfo` could be
     /// successfully created, and `Err` otherwise.
     pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, TypesError> {
+        let commit_info_size = bytes
+            .len()
+            .checked_sub(HASH_LENGTH)
+            .ok_or_else(|| serde_error!("LedgerInfo", "Not enough data for consensus data hash"))?;
+
         let commit_info = BlockInfo::from_bytes(
             bytes
                 .chunk()
-                .get(..bytes.len() - HASH_LENGTH)
+                .get(..commit_info_size)
                 .ok_or_else(|| serde_error!("LedgerInfo", "Not enough data for BlockInfo"))?,
         )
-            .map_err(|e| serde_error!("LedgerInfo", e))?;
+        .map_err(|e| serde_error!("LedgerInfo", e))?;
 
-        bytes.advance(bytes.len() - HASH_LENGTH); // Advance the buffer to get the hash
+        bytes.advance(commit_info_size); // Advance the buffer to get the hash
 
         let consensus_data_hash =
             HashValue::from_slice(bytes.chunk().get(..HASH_LENGTH).ok_or_else(|| {
                 serde_error!("LedgerInfo", "Not enough data for consensus data hash")
             })?)
-                .map_err(|e| serde_error!("LedgerInfo", e))?;
+            .map_err(|e| serde_error!("LedgerInfo", e))?;
 
         bytes.advance(HASH_LENGTH);
 
@@ -208,7 +213,10 @@ impl LedgerInfoWithV0 {
 
     pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, TypesError> {
         let ledger_info_size = LedgerInfo::estimate_size_from_bytes(bytes)?;
-        let agg_sig_size = bytes.len() - ledger_info_size;
+        let agg_sig_size = bytes
+            .len()
+            .checked_sub(ledger_info_size)
+            .ok_or_else(|| serde_error!("LedgerInfoWithV0", "Not enough data for LedgerInfo"))?;
 
         let ledger_info =
             LedgerInfo::from_bytes(bytes.chunk().get(..ledger_info_size).ok_or_else(|| {
@@ -273,7 +281,9 @@ impl LedgerInfoWithSignatures {
     /// A `Result` which is `Ok` if the `LedgerInfoWithSignatures`
     /// could be successfully created, and `Err` otherwise.
     pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, TypesError> {
-        let ledger_v0_size = bytes.len() - ENUM_VARIANT_LEN;
+        let ledger_v0_size = bytes.len().checked_sub(ENUM_VARIANT_LEN).ok_or_else(|| {
+            serde_error!("LedgerInfoWithSignatures", "Not enough data for variant")
+        })?;
         let li_w_sig = match bytes.get_u8() {
             0 => {
                 let ledger_info_with_v0 = LedgerInfoWithV0::from_bytes(
@@ -429,4 +439,4 @@ mod test {
             test_li(&aptos_wrapper);
         }
     }
-}
\ No newline at end of file
+}
@@ -4,16 +4,18 @@ use crate::types::error::TypesError;
 use anyhow::Result;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+pub mod assets;
 pub mod block_info;
 pub mod epoch_state;
 pub mod error;
+pub mod event;
 pub mod ledger_info;
+pub mod output;
 pub mod transaction;
 pub mod trusted_state;
 pub mod utils;
 pub mod validator;
 pub mod waypoint;
-pub mod output;
 
 pub type Round = u64;
 pub type Version = u64;
@@ -73,4 +75,4 @@ impl<'de> Deserialize<'de> for AccountAddress {
         let value = Value::deserialize(deserializer)?;
         Ok(AccountAddress::new(value.0))
     }
-}
\ No newline at end of file
+}
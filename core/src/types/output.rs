@@ -1,7 +1,146 @@
+use crate::crypto::hash::HASH_LENGTH;
+use crate::serde_error;
+use crate::types::error::TypesError;
+use crate::types::event::ContractEvent;
+use crate::types::transaction::ExecutionStatus;
+use crate::types::utils::{ENUM_VARIANT_LEN, U64_SIZE};
+use bytes::{Buf, BufMut, BytesMut};
 use serde::{Deserialize, Serialize};
 
+/// Version of the encoding of the journals of the programs committing
+/// their output with its `to_bytes` encoding. It is written as the first
+/// byte of the journal so that consumers can reject journals they do not
+/// know how to decode.
+pub const OUTPUT_VERSION: u8 = 1;
+
+/// Length in bytes of an encoded `InclusionOutput`.
+pub const INCLUSION_OUTPUT_SIZE: usize = ENUM_VARIANT_LEN + 3 * U64_SIZE + 6 * HASH_LENGTH;
+
+/// Length in bytes of the fields of an encoded `TransactionExecutionOutput`
+/// preceding its variable length `status`.
+pub const TRANSACTION_EXECUTION_OUTPUT_FIXED_SIZE: usize =
+    ENUM_VARIANT_LEN + 2 * U64_SIZE + 4 * HASH_LENGTH;
+
+/// Length in bytes of the fields of an encoded `EventInclusionOutput`
+/// preceding its variable length `event`.
+pub const EVENT_INCLUSION_OUTPUT_FIXED_SIZE: usize =
+    ENUM_VARIANT_LEN + 2 * U64_SIZE + 3 * HASH_LENGTH;
+
+/// Length in bytes of an encoded `TransactionRangeOutput`.
+pub const TRANSACTION_RANGE_OUTPUT_SIZE: usize = ENUM_VARIANT_LEN + 2 * U64_SIZE + 4 * HASH_LENGTH;
+
+/// Length in bytes of an encoded `AccumulatorConsistencyOutput`.
+pub const ACCUMULATOR_CONSISTENCY_OUTPUT_SIZE: usize =
+    ENUM_VARIANT_LEN + 2 * U64_SIZE + 4 * HASH_LENGTH;
+
+/// Length in bytes of an encoded `EpochChangeOutput`.
+pub const EPOCH_CHANGE_OUTPUT_SIZE: usize = ENUM_VARIANT_LEN + 4 * U64_SIZE + 5 * HASH_LENGTH;
+
+/// Journal of the inclusion program.
+///
+/// The journal is committed with the encoding of [`InclusionOutput::to_bytes`]
+/// rather than the risc0 serde format, so that it can be decoded by consumers
+/// outside of Rust. See [`InclusionOutput::to_bytes`] for the layout.
 #[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct InclusionOutput {
+    /// Epoch of the signed `LedgerInfo`.
+    pub epoch: u64,
+    /// Version of the signed `LedgerInfo`.
+    pub version: u64,
+    /// Timestamp of the signed `LedgerInfo`, in microseconds.
+    pub timestamp_usecs: u64,
+    /// Block ID of the signed `LedgerInfo`.
+    pub current_block_id: [u8; 32],
+    /// Root of the transaction accumulator of the signed `LedgerInfo`.
+    pub transaction_accumulator_hash: [u8; 32],
+    /// Hash of the `ValidatorVerifier` that signed the `LedgerInfo`.
+    pub validator_verifier_hash: [u8; 32],
+    /// Root of the state tree the leaf is included in.
+    pub reconstructed_root_hash: [u8; 32],
+    pub key: [u8; 32],
+    pub leaf_value_hash: [u8; 32],
+}
+
+impl InclusionOutput {
+    /// Converts the `InclusionOutput` to a byte vector.
+    ///
+    /// The encoding has a fixed length of `INCLUSION_OUTPUT_SIZE` bytes,
+    /// with integers written in little endian:
+    ///
+    /// | Bytes     | Field                          |
+    /// |-----------|--------------------------------|
+    /// | 0         | `OUTPUT_VERSION`               |
+    /// | 1..9      | `epoch`                        |
+    /// | 9..17     | `version`                      |
+    /// | 17..25    | `timestamp_usecs`              |
+    /// | 25..57    | `current_block_id`             |
+    /// | 57..89    | `transaction_accumulator_hash` |
+    /// | 89..121   | `validator_verifier_hash`      |
+    /// | 121..153  | `reconstructed_root_hash`      |
+    /// | 153..185  | `key`                          |
+    /// | 185..217  | `leaf_value_hash`              |
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<u8>` representing the `InclusionOutput`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = BytesMut::new();
+        bytes.put_u8(OUTPUT_VERSION);
+        bytes.put_u64_le(self.epoch);
+        bytes.put_u64_le(self.version);
+        bytes.put_u64_le(self.timestamp_usecs);
+        bytes.put_slice(&self.current_block_id);
+        bytes.put_slice(&self.transaction_accumulator_hash);
+        bytes.put_slice(&self.validator_verifier_hash);
+        bytes.put_slice(&self.reconstructed_root_hash);
+        bytes.put_slice(&self.key);
+        bytes.put_slice(&self.leaf_value_hash);
+        bytes.to_vec()
+    }
+
+    /// Creates an `InclusionOutput` from a byte slice, as encoded by
+    /// [`InclusionOutput::to_bytes`].
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes: &[u8]` - A byte slice from which to create the `InclusionOutput`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which is `Ok` if the `InclusionOutput` could
+    /// be successfully created, and `Err` otherwise.
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, TypesError> {
+        check_output_bytes("InclusionOutput", bytes, INCLUSION_OUTPUT_SIZE)?;
+        bytes.advance(ENUM_VARIANT_LEN);
+
+        Ok(Self {
+            epoch: bytes.get_u64_le(),
+            version: bytes.get_u64_le(),
+            timestamp_usecs: bytes.get_u64_le(),
+            current_block_id: read_hash(&mut bytes),
+            transaction_accumulator_hash: read_hash(&mut bytes),
+            validator_verifier_hash: read_hash(&mut bytes),
+            reconstructed_root_hash: read_hash(&mut bytes),
+            key: read_hash(&mut bytes),
+            leaf_value_hash: read_hash(&mut bytes),
+        })
+    }
+}
+
+/// Journal of the composed inclusion program. The validator set that
+/// signed the ledger info is bound to the end of an epoch change chain,
+/// whose receipt is verified as an assumption, so a single receipt
+/// attests to the inclusion from the start of the chain.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComposedInclusionOutput {
+    /// Image ID of the epoch change chain program. Consumers have to
+    /// check it against the expected image ID, as the host's
+    /// `verify_composed_inclusion` does.
+    pub epoch_change_image_id: [u32; 8],
+    /// Hash of the `ValidatorVerifier` trusted at the start of the chain.
+    pub genesis_validator_verifier_hash: [u8; 32],
+    /// Epoch of the ledger info, which is the end epoch of the chain.
+    pub epoch: u64,
     pub validator_verifier_hash: [u8; 32],
     pub reconstructed_root_hash: [u8; 32],
     pub current_block_id: [u8; 32],
@@ -9,8 +148,715 @@ pub struct InclusionOutput {
     pub leaf_value_hash: [u8; 32],
 }
 
+/// Journal of the batch inclusion program. All the proven leaves are
+/// committed to through `leaves_root`, which can be recomputed from the
+/// expected `(key, leaf_value_hash)` pairs with
+/// [`crate::merkle::batch::batch_leaves_root`].
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchInclusionOutput {
+    pub validator_verifier_hash: [u8; 32],
+    pub reconstructed_root_hash: [u8; 32],
+    pub current_block_id: [u8; 32],
+    pub leaves_root: [u8; 32],
+    pub leaves_count: u64,
+}
+
+/// Journal of the non-inclusion program, attesting that no leaf
+/// exists for `key` in the state tree whose root is
+/// `reconstructed_root_hash`.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NonInclusionOutput {
+    pub validator_verifier_hash: [u8; 32],
+    pub reconstructed_root_hash: [u8; 32],
+    pub current_block_id: [u8; 32],
+    pub key: [u8; 32],
+}
+
+/// Journal of the transaction execution program, attesting that the
+/// transaction whose hash is `transaction_hash` was committed at `version`
+/// with `status`, in the ledger whose root is `transaction_accumulator_hash`.
+///
+/// The journal is committed with the encoding of
+/// [`TransactionExecutionOutput::to_bytes`].
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionExecutionOutput {
+    pub validator_verifier_hash: [u8; 32],
+    pub current_block_id: [u8; 32],
+    pub transaction_accumulator_hash: [u8; 32],
+    pub version: u64,
+    pub transaction_hash: [u8; 32],
+    pub status: ExecutionStatus,
+    pub gas_used: u64,
+}
+
+impl TransactionExecutionOutput {
+    /// Converts the `TransactionExecutionOutput` to a byte vector.
+    ///
+    /// The `status` has a variable length, so it comes last, after
+    /// `TRANSACTION_EXECUTION_OUTPUT_FIXED_SIZE` bytes. Integers are written
+    /// in little endian:
+    ///
+    /// | Bytes     | Field                                               |
+    /// |-----------|-----------------------------------------------------|
+    /// | 0         | `OUTPUT_VERSION`                                    |
+    /// | 1..33     | `validator_verifier_hash`                           |
+    /// | 33..65    | `current_block_id`                                  |
+    /// | 65..97    | `transaction_accumulator_hash`                      |
+    /// | 97..105   | `version`                                           |
+    /// | 105..137  | `transaction_hash`                                  |
+    /// | 137..145  | `gas_used`                                          |
+    /// | 145..     | `status`, encoded as in the `TransactionInfo`       |
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<u8>` representing the `TransactionExecutionOutput`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = BytesMut::new();
+        bytes.put_u8(OUTPUT_VERSION);
+        bytes.put_slice(&self.validator_verifier_hash);
+        bytes.put_slice(&self.current_block_id);
+        bytes.put_slice(&self.transaction_accumulator_hash);
+        bytes.put_u64_le(self.version);
+        bytes.put_slice(&self.transaction_hash);
+        bytes.put_u64_le(self.gas_used);
+        bytes.put_slice(&self.status.to_bytes());
+        bytes.to_vec()
+    }
+
+    /// Creates a `TransactionExecutionOutput` from a byte slice, as encoded
+    /// by [`TransactionExecutionOutput::to_bytes`].
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes: &[u8]` - A byte slice from which to create the `TransactionExecutionOutput`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which is `Ok` if the `TransactionExecutionOutput` could
+    /// be successfully created, and `Err` otherwise.
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, TypesError> {
+        check_output_prefix(
+            "TransactionExecutionOutput",
+            bytes,
+            TRANSACTION_EXECUTION_OUTPUT_FIXED_SIZE,
+        )?;
+        bytes.advance(ENUM_VARIANT_LEN);
+
+        let validator_verifier_hash = read_hash(&mut bytes);
+        let current_block_id = read_hash(&mut bytes);
+        let transaction_accumulator_hash = read_hash(&mut bytes);
+        let version = bytes.get_u64_le();
+        let transaction_hash = read_hash(&mut bytes);
+        let gas_used = bytes.get_u64_le();
+
+        Ok(Self {
+            validator_verifier_hash,
+            current_block_id,
+            transaction_accumulator_hash,
+            version,
+            transaction_hash,
+            status: ExecutionStatus::from_bytes(bytes)?,
+            gas_used,
+        })
+    }
+}
+
+/// Journal of the event inclusion program, attesting that `event` was
+/// emitted at `event_index` by the transaction committed at `version`, in
+/// the ledger whose root is `transaction_accumulator_hash`.
+///
+/// The journal is committed with the encoding of
+/// [`EventInclusionOutput::to_bytes`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventInclusionOutput {
+    pub validator_verifier_hash: [u8; 32],
+    pub current_block_id: [u8; 32],
+    pub transaction_accumulator_hash: [u8; 32],
+    pub version: u64,
+    pub event_index: u64,
+    pub event: ContractEvent,
+}
+
+impl EventInclusionOutput {
+    /// Converts the `EventInclusionOutput` to a byte vector.
+    ///
+    /// The `event` has a variable length, so it comes last, after
+    /// `EVENT_INCLUSION_OUTPUT_FIXED_SIZE` bytes. Integers are written in
+    /// little endian:
+    ///
+    /// | Bytes     | Field                                    |
+    /// |-----------|------------------------------------------|
+    /// | 0         | `OUTPUT_VERSION`                         |
+    /// | 1..33     | `validator_verifier_hash`                |
+    /// | 33..65    | `current_block_id`                       |
+    /// | 65..97    | `transaction_accumulator_hash`           |
+    /// | 97..105   | `version`                                |
+    /// | 105..113  | `event_index`                            |
+    /// | 113..     | `event`, see [`ContractEvent::to_bytes`] |
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<u8>` representing the `EventInclusionOutput`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = BytesMut::new();
+        bytes.put_u8(OUTPUT_VERSION);
+        bytes.put_slice(&self.validator_verifier_hash);
+        bytes.put_slice(&self.current_block_id);
+        bytes.put_slice(&self.transaction_accumulator_hash);
+        bytes.put_u64_le(self.version);
+        bytes.put_u64_le(self.event_index);
+        bytes.put_slice(&self.event.to_bytes());
+        bytes.to_vec()
+    }
+
+    /// Creates an `EventInclusionOutput` from a byte slice, as encoded by
+    /// [`EventInclusionOutput::to_bytes`].
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes: &[u8]` - A byte slice from which to create the `EventInclusionOutput`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which is `Ok` if the `EventInclusionOutput` could
+    /// be successfully created, and `Err` otherwise.
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, TypesError> {
+        check_output_prefix(
+            "EventInclusionOutput",
+            bytes,
+            EVENT_INCLUSION_OUTPUT_FIXED_SIZE,
+        )?;
+        bytes.advance(ENUM_VARIANT_LEN);
+
+        Ok(Self {
+            validator_verifier_hash: read_hash(&mut bytes),
+            current_block_id: read_hash(&mut bytes),
+            transaction_accumulator_hash: read_hash(&mut bytes),
+            version: bytes.get_u64_le(),
+            event_index: bytes.get_u64_le(),
+            event: ContractEvent::from_bytes(bytes)?,
+        })
+    }
+}
+
+/// Journal of the transaction range program, attesting that the
+/// `TransactionInfo`s committed from `first_version` to `last_version`,
+/// included, are the ones digested in `transaction_infos_digest`, see
+/// [`crate::merkle::transaction_proof::transaction_infos_digest`].
+///
+/// The journal is committed with the encoding of
+/// [`TransactionRangeOutput::to_bytes`].
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionRangeOutput {
+    pub validator_verifier_hash: [u8; 32],
+    pub current_block_id: [u8; 32],
+    pub transaction_accumulator_hash: [u8; 32],
+    pub first_version: u64,
+    pub last_version: u64,
+    pub transaction_infos_digest: [u8; 32],
+}
+
+impl TransactionRangeOutput {
+    /// Converts the `TransactionRangeOutput` to a byte vector.
+    ///
+    /// The encoding has a fixed length of `TRANSACTION_RANGE_OUTPUT_SIZE`
+    /// bytes, with integers written in little endian:
+    ///
+    /// | Bytes     | Field                          |
+    /// |-----------|--------------------------------|
+    /// | 0         | `OUTPUT_VERSION`               |
+    /// | 1..33     | `validator_verifier_hash`      |
+    /// | 33..65    | `current_block_id`             |
+    /// | 65..97    | `transaction_accumulator_hash` |
+    /// | 97..105   | `first_version`                |
+    /// | 105..113  | `last_version`                 |
+    /// | 113..145  | `transaction_infos_digest`     |
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<u8>` representing the `TransactionRangeOutput`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = BytesMut::new();
+        bytes.put_u8(OUTPUT_VERSION);
+        bytes.put_slice(&self.validator_verifier_hash);
+        bytes.put_slice(&self.current_block_id);
+        bytes.put_slice(&self.transaction_accumulator_hash);
+        bytes.put_u64_le(self.first_version);
+        bytes.put_u64_le(self.last_version);
+        bytes.put_slice(&self.transaction_infos_digest);
+        bytes.to_vec()
+    }
+
+    /// Creates a `TransactionRangeOutput` from a byte slice, as encoded by
+    /// [`TransactionRangeOutput::to_bytes`].
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes: &[u8]` - A byte slice from which to create the `TransactionRangeOutput`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which is `Ok` if the `TransactionRangeOutput` could
+    /// be successfully created, and `Err` otherwise.
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, TypesError> {
+        check_output_bytes(
+            "TransactionRangeOutput",
+            bytes,
+            TRANSACTION_RANGE_OUTPUT_SIZE,
+        )?;
+        bytes.advance(ENUM_VARIANT_LEN);
+
+        Ok(Self {
+            validator_verifier_hash: read_hash(&mut bytes),
+            current_block_id: read_hash(&mut bytes),
+            transaction_accumulator_hash: read_hash(&mut bytes),
+            first_version: bytes.get_u64_le(),
+            last_version: bytes.get_u64_le(),
+            transaction_infos_digest: read_hash(&mut bytes),
+        })
+    }
+}
+
+/// Journal of the accumulator consistency program, attesting that the
+/// transaction accumulator of the `LedgerInfo` at `version` extends the one
+/// of the `LedgerInfo` at `prev_version`, so that the history up to
+/// `prev_version` was not rewritten.
+///
+/// The journal is committed with the encoding of
+/// [`AccumulatorConsistencyOutput::to_bytes`].
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccumulatorConsistencyOutput {
+    /// Hash of the `ValidatorVerifier` that signed the previous `LedgerInfo`.
+    pub prev_validator_verifier_hash: [u8; 32],
+    pub prev_version: u64,
+    pub prev_transaction_accumulator_hash: [u8; 32],
+    /// Hash of the `ValidatorVerifier` that signed the latest `LedgerInfo`.
+    pub validator_verifier_hash: [u8; 32],
+    pub version: u64,
+    pub transaction_accumulator_hash: [u8; 32],
+}
+
+impl AccumulatorConsistencyOutput {
+    /// Converts the `AccumulatorConsistencyOutput` to a byte vector.
+    ///
+    /// The encoding has a fixed length of
+    /// `ACCUMULATOR_CONSISTENCY_OUTPUT_SIZE` bytes, with integers written in
+    /// little endian:
+    ///
+    /// | Bytes     | Field                               |
+    /// |-----------|-------------------------------------|
+    /// | 0         | `OUTPUT_VERSION`                    |
+    /// | 1..33     | `prev_validator_verifier_hash`      |
+    /// | 33..41    | `prev_version`                      |
+    /// | 41..73    | `prev_transaction_accumulator_hash` |
+    /// | 73..105   | `validator_verifier_hash`           |
+    /// | 105..113  | `version`                           |
+    /// | 113..145  | `transaction_accumulator_hash`      |
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<u8>` representing the `AccumulatorConsistencyOutput`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = BytesMut::new();
+        bytes.put_u8(OUTPUT_VERSION);
+        bytes.put_slice(&self.prev_validator_verifier_hash);
+        bytes.put_u64_le(self.prev_version);
+        bytes.put_slice(&self.prev_transaction_accumulator_hash);
+        bytes.put_slice(&self.validator_verifier_hash);
+        bytes.put_u64_le(self.version);
+        bytes.put_slice(&self.transaction_accumulator_hash);
+        bytes.to_vec()
+    }
+
+    /// Creates an `AccumulatorConsistencyOutput` from a byte slice, as
+    /// encoded by [`AccumulatorConsistencyOutput::to_bytes`].
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes: &[u8]` - A byte slice from which to create the `AccumulatorConsistencyOutput`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which is `Ok` if the `AccumulatorConsistencyOutput` could
+    /// be successfully created, and `Err` otherwise.
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, TypesError> {
+        check_output_bytes(
+            "AccumulatorConsistencyOutput",
+            bytes,
+            ACCUMULATOR_CONSISTENCY_OUTPUT_SIZE,
+        )?;
+        bytes.advance(ENUM_VARIANT_LEN);
+
+        Ok(Self {
+            prev_validator_verifier_hash: read_hash(&mut bytes),
+            prev_version: bytes.get_u64_le(),
+            prev_transaction_accumulator_hash: read_hash(&mut bytes),
+            validator_verifier_hash: read_hash(&mut bytes),
+            version: bytes.get_u64_le(),
+            transaction_accumulator_hash: read_hash(&mut bytes),
+        })
+    }
+}
+
+/// Journal of the epoch change program. A single proof can ratchet
+/// through several epochs, from `start_epoch` to `end_epoch`.
+///
+/// The journal is committed with the encoding of [`EpochChangeOutput::to_bytes`]
+/// rather than the risc0 serde format, so that it can be decoded by consumers
+/// outside of Rust. See [`EpochChangeOutput::to_bytes`] for the layout.
 #[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct EpochChangeOutput {
+    /// Epoch of the first `LedgerInfoWithSignatures` verified by the proof.
+    pub start_epoch: u64,
+    /// Epoch of the validator set trusted after ratcheting.
+    pub end_epoch: u64,
+    /// Version of the last epoch change `LedgerInfo`.
+    pub version: u64,
+    /// Timestamp of the last epoch change `LedgerInfo`, in microseconds.
+    pub timestamp_usecs: u64,
+    /// Block ID of the last epoch change `LedgerInfo`.
+    pub block_id: [u8; 32],
+    /// Root of the transaction accumulator of the last epoch change `LedgerInfo`.
+    pub transaction_accumulator_hash: [u8; 32],
+    /// Hash of the `ValidatorVerifier` trusted before ratcheting. If the
+    /// proof started from a `TrustedState::EpochWaypoint`, this is the
+    /// value of the waypoint instead.
     pub prev_epoch_validator_verifier_hash: [u8; 32],
+    /// Hash of the `ValidatorVerifier` of `end_epoch`.
+    pub validator_verifier_hash: [u8; 32],
+    /// Value of the epoch boundary `Waypoint` committing to the last epoch
+    /// change `LedgerInfo`. Its version is `version`.
+    pub new_waypoint: [u8; 32],
+}
+
+impl EpochChangeOutput {
+    /// Converts the `EpochChangeOutput` to a byte vector.
+    ///
+    /// The encoding has a fixed length of `EPOCH_CHANGE_OUTPUT_SIZE` bytes,
+    /// with integers written in little endian:
+    ///
+    /// | Bytes     | Field                                |
+    /// |-----------|--------------------------------------|
+    /// | 0         | `OUTPUT_VERSION`                     |
+    /// | 1..9      | `start_epoch`                        |
+    /// | 9..17     | `end_epoch`                          |
+    /// | 17..25    | `version`                            |
+    /// | 25..33    | `timestamp_usecs`                    |
+    /// | 33..65    | `block_id`                           |
+    /// | 65..97    | `transaction_accumulator_hash`       |
+    /// | 97..129   | `prev_epoch_validator_verifier_hash` |
+    /// | 129..161  | `validator_verifier_hash`            |
+    /// | 161..193  | `new_waypoint`                       |
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<u8>` representing the `EpochChangeOutput`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = BytesMut::new();
+        bytes.put_u8(OUTPUT_VERSION);
+        bytes.put_u64_le(self.start_epoch);
+        bytes.put_u64_le(self.end_epoch);
+        bytes.put_u64_le(self.version);
+        bytes.put_u64_le(self.timestamp_usecs);
+        bytes.put_slice(&self.block_id);
+        bytes.put_slice(&self.transaction_accumulator_hash);
+        bytes.put_slice(&self.prev_epoch_validator_verifier_hash);
+        bytes.put_slice(&self.validator_verifier_hash);
+        bytes.put_slice(&self.new_waypoint);
+        bytes.to_vec()
+    }
+
+    /// Creates an `EpochChangeOutput` from a byte slice, as encoded by
+    /// [`EpochChangeOutput::to_bytes`].
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes: &[u8]` - A byte slice from which to create the `EpochChangeOutput`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which is `Ok` if the `EpochChangeOutput` could
+    /// be successfully created, and `Err` otherwise.
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, TypesError> {
+        check_output_bytes("EpochChangeOutput", bytes, EPOCH_CHANGE_OUTPUT_SIZE)?;
+        bytes.advance(ENUM_VARIANT_LEN);
+
+        Ok(Self {
+            start_epoch: bytes.get_u64_le(),
+            end_epoch: bytes.get_u64_le(),
+            version: bytes.get_u64_le(),
+            timestamp_usecs: bytes.get_u64_le(),
+            block_id: read_hash(&mut bytes),
+            transaction_accumulator_hash: read_hash(&mut bytes),
+            prev_epoch_validator_verifier_hash: read_hash(&mut bytes),
+            validator_verifier_hash: read_hash(&mut bytes),
+            new_waypoint: read_hash(&mut bytes),
+        })
+    }
+}
+
+/// Journal of the epoch change chain program. A receipt attests to the
+/// whole chain of epoch changes from `start_epoch` to `end_epoch`, each
+/// link verifying the receipt of the previous one as an assumption.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpochChangeChainOutput {
+    /// Image ID of the epoch change chain program, used to verify the
+    /// previous receipt of the chain. Consumers have to check it against
+    /// the expected image ID.
+    pub image_id: [u32; 8],
+    /// Epoch of the first `LedgerInfoWithSignatures` verified by the chain.
+    pub start_epoch: u64,
+    /// Epoch of the validator set trusted at the end of the chain.
+    pub end_epoch: u64,
+    /// Hash of the `ValidatorVerifier` trusted at the start of the chain.
+    /// If the chain started from a `TrustedState::EpochWaypoint`, this is
+    /// the value of the waypoint instead.
+    pub genesis_validator_verifier_hash: [u8; 32],
+    /// Hash of the `ValidatorVerifier` of `end_epoch`.
     pub validator_verifier_hash: [u8; 32],
-}
\ No newline at end of file
+}
+
+/// Checks that the given bytes have the expected length and start with
+/// the supported `OUTPUT_VERSION`.
+fn check_output_bytes(structure: &str, bytes: &[u8], size: usize) -> Result<(), TypesError> {
+    if bytes.len() != size {
+        return Err(serde_error!(
+            structure,
+            format!("Expected {} bytes, got {}", size, bytes.len())
+        ));
+    }
+
+    check_output_version(structure, bytes)
+}
+
+/// Checks that the given bytes hold at least the fixed length fields of
+/// an output of variable length, and start with the supported
+/// `OUTPUT_VERSION`.
+fn check_output_prefix(structure: &str, bytes: &[u8], size: usize) -> Result<(), TypesError> {
+    if bytes.len() < size {
+        return Err(serde_error!(
+            structure,
+            format!("Expected at least {} bytes, got {}", size, bytes.len())
+        ));
+    }
+
+    check_output_version(structure, bytes)
+}
+
+/// Checks that the given non-empty bytes start with the supported
+/// `OUTPUT_VERSION`.
+fn check_output_version(structure: &str, bytes: &[u8]) -> Result<(), TypesError> {
+    if bytes[0] != OUTPUT_VERSION {
+        return Err(serde_error!(
+            structure,
+            format!("Unsupported output version {}", bytes[0])
+        ));
+    }
+
+    Ok(())
+}
+
+/// Reads a hash from the given bytes, advancing them. The length of the
+/// bytes is expected to have been checked beforehand.
+fn read_hash(bytes: &mut &[u8]) -> [u8; HASH_LENGTH] {
+    let mut hash = [0u8; HASH_LENGTH];
+    bytes.copy_to_slice(&mut hash);
+    hash
+}
+
+#[cfg(test)]
+mod test {
+    use crate::types::event::{ContractEvent, ContractEventV2, TypeTag};
+    use crate::types::output::{
+        AccumulatorConsistencyOutput, EpochChangeOutput, EventInclusionOutput, InclusionOutput,
+        TransactionExecutionOutput, TransactionRangeOutput, ACCUMULATOR_CONSISTENCY_OUTPUT_SIZE,
+        EPOCH_CHANGE_OUTPUT_SIZE, EVENT_INCLUSION_OUTPUT_FIXED_SIZE, INCLUSION_OUTPUT_SIZE,
+        TRANSACTION_EXECUTION_OUTPUT_FIXED_SIZE, TRANSACTION_RANGE_OUTPUT_SIZE,
+    };
+    use crate::types::transaction::{AbortInfo, AbortLocation, ExecutionStatus};
+
+    #[test]
+    fn test_bytes_conversion_inclusion_output() {
+        let output = InclusionOutput {
+            epoch: 3,
+            version: 1_000,
+            timestamp_usecs: 1_700_000_000_000_000,
+            current_block_id: [1; 32],
+            transaction_accumulator_hash: [2; 32],
+            validator_verifier_hash: [3; 32],
+            reconstructed_root_hash: [4; 32],
+            key: [5; 32],
+            leaf_value_hash: [6; 32],
+        };
+
+        let bytes = output.to_bytes();
+        assert_eq!(bytes.len(), INCLUSION_OUTPUT_SIZE);
+        assert_eq!(bytes[0], 1);
+        assert_eq!(&bytes[9..17], &1_000u64.to_le_bytes());
+        assert_eq!(&bytes[185..], &[6; 32]);
+        assert_eq!(InclusionOutput::from_bytes(&bytes).unwrap(), output);
+
+        // Unknown version
+        let mut wrong_version = bytes.clone();
+        wrong_version[0] = 2;
+        assert!(InclusionOutput::from_bytes(&wrong_version).is_err());
+
+        // Wrong length
+        assert!(InclusionOutput::from_bytes(&bytes[..INCLUSION_OUTPUT_SIZE - 1]).is_err());
+        assert!(InclusionOutput::from_bytes(&[bytes.as_slice(), &[0]].concat()).is_err());
+    }
+
+    #[test]
+    fn test_bytes_conversion_epoch_change_output() {
+        let output = EpochChangeOutput {
+            start_epoch: 1,
+            end_epoch: 5,
+            version: 1_000,
+            timestamp_usecs: 1_700_000_000_000_000,
+            block_id: [1; 32],
+            transaction_accumulator_hash: [2; 32],
+            prev_epoch_validator_verifier_hash: [3; 32],
+            validator_verifier_hash: [4; 32],
+            new_waypoint: [5; 32],
+        };
+
+        let bytes = output.to_bytes();
+        assert_eq!(bytes.len(), EPOCH_CHANGE_OUTPUT_SIZE);
+        assert_eq!(bytes[0], 1);
+        assert_eq!(&bytes[9..17], &5u64.to_le_bytes());
+        assert_eq!(&bytes[161..], &[5; 32]);
+        assert_eq!(EpochChangeOutput::from_bytes(&bytes).unwrap(), output);
+
+        // Unknown version
+        let mut wrong_version = bytes.clone();
+        wrong_version[0] = 0;
+        assert!(EpochChangeOutput::from_bytes(&wrong_version).is_err());
+
+        // Wrong length
+        assert!(EpochChangeOutput::from_bytes(&bytes[1..]).is_err());
+    }
+
+    #[test]
+    fn test_bytes_conversion_transaction_execution_output() {
+        let output = TransactionExecutionOutput {
+            validator_verifier_hash: [1; 32],
+            current_block_id: [2; 32],
+            transaction_accumulator_hash: [3; 32],
+            version: 1_000,
+            transaction_hash: [4; 32],
+            status: ExecutionStatus::MoveAbort {
+                location: AbortLocation::Script,
+                code: 7,
+                info: Some(AbortInfo {
+                    reason_name: "EINSUFFICIENT_BALANCE".to_string(),
+                    description: "Not enough coins".to_string(),
+                }),
+            },
+            gas_used: 12,
+        };
+
+        let bytes = output.to_bytes();
+        assert_eq!(bytes[0], 1);
+        assert_eq!(&bytes[97..105], &1_000u64.to_le_bytes());
+        assert_eq!(
+            &bytes[TRANSACTION_EXECUTION_OUTPUT_FIXED_SIZE..],
+            output.status.to_bytes().as_slice()
+        );
+        assert_eq!(
+            TransactionExecutionOutput::from_bytes(&bytes).unwrap(),
+            output
+        );
+
+        // Unknown version
+        let mut wrong_version = bytes.clone();
+        wrong_version[0] = 2;
+        assert!(TransactionExecutionOutput::from_bytes(&wrong_version).is_err());
+
+        // Truncated or trailing status
+        let fixed_fields = &bytes[..TRANSACTION_EXECUTION_OUTPUT_FIXED_SIZE];
+        assert!(TransactionExecutionOutput::from_bytes(fixed_fields).is_err());
+        assert!(TransactionExecutionOutput::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        let trailing = [bytes.as_slice(), &[0]].concat();
+        assert!(TransactionExecutionOutput::from_bytes(&trailing).is_err());
+    }
+
+    #[test]
+    fn test_bytes_conversion_event_inclusion_output() {
+        let output = EventInclusionOutput {
+            validator_verifier_hash: [1; 32],
+            current_block_id: [2; 32],
+            transaction_accumulator_hash: [3; 32],
+            version: 1_000,
+            event_index: 2,
+            event: ContractEvent::V2(ContractEventV2::new(TypeTag::U64, vec![5; 8])),
+        };
+
+        let bytes = output.to_bytes();
+        assert_eq!(bytes[0], 1);
+        assert_eq!(&bytes[105..113], &2u64.to_le_bytes());
+        assert_eq!(
+            &bytes[EVENT_INCLUSION_OUTPUT_FIXED_SIZE..],
+            output.event.to_bytes().as_slice()
+        );
+        assert_eq!(EventInclusionOutput::from_bytes(&bytes).unwrap(), output);
+
+        // Unknown version
+        let mut wrong_version = bytes.clone();
+        wrong_version[0] = 0;
+        assert!(EventInclusionOutput::from_bytes(&wrong_version).is_err());
+
+        // Truncated event
+        assert!(EventInclusionOutput::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        let fixed_fields = &bytes[..EVENT_INCLUSION_OUTPUT_FIXED_SIZE];
+        assert!(EventInclusionOutput::from_bytes(fixed_fields).is_err());
+    }
+
+    #[test]
+    fn test_bytes_conversion_transaction_range_output() {
+        let output = TransactionRangeOutput {
+            validator_verifier_hash: [1; 32],
+            current_block_id: [2; 32],
+            transaction_accumulator_hash: [3; 32],
+            first_version: 10,
+            last_version: 20,
+            transaction_infos_digest: [4; 32],
+        };
+
+        let bytes = output.to_bytes();
+        assert_eq!(bytes.len(), TRANSACTION_RANGE_OUTPUT_SIZE);
+        assert_eq!(bytes[0], 1);
+        assert_eq!(&bytes[105..113], &20u64.to_le_bytes());
+        assert_eq!(&bytes[113..], &[4; 32]);
+        assert_eq!(TransactionRangeOutput::from_bytes(&bytes).unwrap(), output);
+
+        // Wrong length
+        assert!(TransactionRangeOutput::from_bytes(&bytes[1..]).is_err());
+    }
+
+    #[test]
+    fn test_bytes_conversion_accumulator_consistency_output() {
+        let output = AccumulatorConsistencyOutput {
+            prev_validator_verifier_hash: [1; 32],
+            prev_version: 10,
+            prev_transaction_accumulator_hash: [2; 32],
+            validator_verifier_hash: [3; 32],
+            version: 20,
+            transaction_accumulator_hash: [4; 32],
+        };
+
+        let bytes = output.to_bytes();
+        assert_eq!(bytes.len(), ACCUMULATOR_CONSISTENCY_OUTPUT_SIZE);
+        assert_eq!(bytes[0], 1);
+        assert_eq!(&bytes[33..41], &10u64.to_le_bytes());
+        assert_eq!(&bytes[113..], &[4; 32]);
+        assert_eq!(
+            AccumulatorConsistencyOutput::from_bytes(&bytes).unwrap(),
+            output
+        );
+
+        // Unknown version
+        let mut wrong_version = bytes.clone();
+        wrong_version[0] = 2;
+        assert!(AccumulatorConsistencyOutput::from_bytes(&wrong_version).is_err());
+    }
+}
@@ -1,7 +1,8 @@
 use crate::crypto::hash::{hash_data, prefixed_sha3, CryptoHash, HashValue, HASH_LENGTH};
 use crate::serde_error;
 use crate::types::error::TypesError;
-use crate::types::utils::{ENUM_VARIANT_LEN, U64_SIZE};
+use crate::types::utils::{put_string, read_slice, read_string, read_u16, read_u64, read_u8};
+use crate::types::{AccountAddress, ACCOUNT_ADDRESS_SIZE};
 use bytes::{Buf, BufMut, BytesMut};
 use serde::{Deserialize, Serialize};
 
@@ -27,6 +28,50 @@ impl TransactionInfo {
         }
     }
 
+    /// Returns the hash of the transaction the `TransactionInfo` is about.
+    ///
+    /// # Returns
+    ///
+    /// The hash of the transaction.
+    pub const fn transaction_hash(&self) -> HashValue {
+        match self {
+            TransactionInfo::V0(info) => info.transaction_hash,
+        }
+    }
+
+    /// Returns the root hash of the event accumulator of the transaction.
+    ///
+    /// # Returns
+    ///
+    /// The root hash of the events emitted by the transaction.
+    pub const fn event_root_hash(&self) -> HashValue {
+        match self {
+            TransactionInfo::V0(info) => info.event_root_hash,
+        }
+    }
+
+    /// Returns the gas used by the transaction.
+    ///
+    /// # Returns
+    ///
+    /// The gas used by the transaction.
+    pub const fn gas_used(&self) -> u64 {
+        match self {
+            TransactionInfo::V0(info) => info.gas_used,
+        }
+    }
+
+    /// Returns the execution status of the `TransactionInfo`.
+    ///
+    /// # Returns
+    ///
+    /// The execution status of the `TransactionInfo`.
+    pub const fn status(&self) -> &ExecutionStatus {
+        match self {
+            TransactionInfo::V0(info) => &info.status,
+        }
+    }
+
     /// Converts the `TransactionInfo` to a byte vector.
     ///
     /// # Returns
@@ -54,15 +99,12 @@ impl TransactionInfo {
     /// A `Result` which is `Ok` if the `TransactionInfo`
     /// could be successfully created, and `Err` otherwise.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, TypesError> {
-        let mut buf = BytesMut::from(bytes);
-        let tx_info = match buf.get_u8() {
+        let mut buf = bytes;
+        let tx_info = match read_u8(&mut buf, "TransactionInfo")? {
+            // `TransactionInfoV0` has a variable length, and is the last field
             0 => {
-                let tx_info_v0 = TransactionInfoV0::from_bytes(
-                    buf.chunk().get(..TRANSACTION_INFO_V0_SIZE).ok_or_else(|| {
-                        serde_error!("TransactionInfo", "Not enough data for TransactionInfoV0")
-                    })?,
-                )?;
-                buf.advance(TRANSACTION_INFO_V0_SIZE);
+                let tx_info_v0 = TransactionInfoV0::from_bytes(buf.chunk())?;
+                buf.advance(buf.remaining());
                 TransactionInfo::V0(tx_info_v0)
             }
             _ => return Err(serde_error!("TransactionInfo", "Invalid variant")),
@@ -88,10 +130,6 @@ impl CryptoHash for TransactionInfo {
     }
 }
 
-/// Length in bytes of the serialized `TransactionInfoV0`.
-pub const TRANSACTION_INFO_V0_SIZE: usize =
-    U64_SIZE + 4 * HASH_LENGTH + 2 * ENUM_VARIANT_LEN + EXECUTION_STATUS_SIZE;
-
 /// `TransactionInfoV0`  contains Information related to how
 /// a transaction affected the state of the Aptos blockchain.
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -164,38 +202,37 @@ impl TransactionInfoV0 {
     /// A `Result` which is `Ok` if the `TransactionInfoV0`
     /// could be successfully created, and `Err` otherwise.
     pub(crate) fn from_bytes(bytes: &[u8]) -> Result<Self, TypesError> {
-        let mut buf = BytesMut::from(bytes);
+        let mut buf = bytes;
 
-        let gas_used = buf.get_u64_le();
+        let gas_used = read_u64(&mut buf, "TransactionInfoV0")?;
 
-        let status =
-            ExecutionStatus::from_bytes(buf.chunk().get(..EXECUTION_STATUS_SIZE).ok_or_else(
-                || serde_error!("TransactionInfo0", "Not enough data for ExecutionStatus"),
-            )?)?;
-        buf.advance(EXECUTION_STATUS_SIZE);
+        let status_size = ExecutionStatus::estimate_size_from_bytes(buf.chunk())
+            .map_err(|e| serde_error!("TransactionInfo0", e))?;
+        let status = ExecutionStatus::from_bytes(&buf.chunk()[..status_size])?;
+        buf.advance(status_size);
 
         let transaction_hash =
             HashValue::from_slice(buf.chunk().get(..HASH_LENGTH).ok_or_else(|| {
                 serde_error!("TransactionInfo0", "Not enough data for transaction hash")
             })?)
-                .unwrap();
+            .unwrap();
         buf.advance(HASH_LENGTH);
 
         let event_root_hash =
             HashValue::from_slice(buf.chunk().get(..HASH_LENGTH).ok_or_else(|| {
                 serde_error!("TransactionInfo0", "Not enough data for event root hash")
             })?)
-                .unwrap();
+            .unwrap();
         buf.advance(HASH_LENGTH);
 
         let state_change_hash =
             HashValue::from_slice(buf.chunk().get(..HASH_LENGTH).ok_or_else(|| {
                 serde_error!("TransactionInfo0", "Not enough data for statechange hash")
             })?)
-                .unwrap();
+            .unwrap();
         buf.advance(HASH_LENGTH);
 
-        let state_checkpoint_hash = if buf.get_u8() == 1 {
+        let state_checkpoint_hash = if read_u8(&mut buf, "TransactionInfoV0")? == 1 {
             Some(
                 HashValue::from_slice(buf.chunk().get(..HASH_LENGTH).ok_or_else(|| {
                     serde_error!(
@@ -203,14 +240,16 @@ impl TransactionInfoV0 {
                         "Not enough data for state checkpoint hash"
                     )
                 })?)
-                    .unwrap(),
+                .unwrap(),
             )
         } else {
             None
         };
-        buf.advance(HASH_LENGTH);
+        if state_checkpoint_hash.is_some() {
+            buf.advance(HASH_LENGTH);
+        }
 
-        let state_cemetery_hash = if buf.get_u8() == 1 {
+        let state_cemetery_hash = if read_u8(&mut buf, "TransactionInfoV0")? == 1 {
             let hash_value =
                 HashValue::from_slice(buf.chunk().get(..HASH_LENGTH).ok_or_else(|| {
                     serde_error!(
@@ -218,7 +257,7 @@ impl TransactionInfoV0 {
                         "Not enough data for state cemetery hash"
                     )
                 })?)
-                    .unwrap();
+                .unwrap();
             buf.advance(HASH_LENGTH);
             Some(hash_value)
         } else {
@@ -244,17 +283,26 @@ impl TransactionInfoV0 {
     }
 }
 
-/// Length in bytes of the serialized `ExecutionStatus`. We only
-/// expect to receive successful transactions included  in the block to
-/// prove account inclusion.
-pub const EXECUTION_STATUS_SIZE: usize = ENUM_VARIANT_LEN;
-
 /// The status of VM execution, which contains more detailed failure info.
-/// We only expect to handle successful transactions in the light client,
-/// as we use it for state checkpoint.
-#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// It mirrors the Aptos `ExecutionStatus`, so that the `TransactionInfo`
+/// of failed transactions can be deserialized and hashed.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ExecutionStatus {
+    #[default]
     Success,
+    OutOfGas,
+    MoveAbort {
+        location: AbortLocation,
+        code: u64,
+        info: Option<AbortInfo>,
+    },
+    ExecutionFailure {
+        location: AbortLocation,
+        function: u16,
+        code_offset: u16,
+    },
+    /// Any other failure, carrying the code of the VM status if any.
+    MiscellaneousError(Option<u64>),
 }
 
 impl ExecutionStatus {
@@ -269,9 +317,50 @@ impl ExecutionStatus {
             ExecutionStatus::Success => {
                 bytes.put_u8(0);
             }
+            ExecutionStatus::OutOfGas => {
+                bytes.put_u8(1);
+            }
+            ExecutionStatus::MoveAbort {
+                location,
+                code,
+                info,
+            } => {
+                bytes.put_u8(2);
+                bytes.put_slice(&location.to_bytes());
+                bytes.put_u64_le(*code);
+                match info {
+                    Some(info) => {
+                        bytes.put_u8(1);
+                        put_string(&mut bytes, &info.reason_name);
+                        put_string(&mut bytes, &info.description);
+                    }
+                    None => bytes.put_u8(0),
+                }
+            }
+            ExecutionStatus::ExecutionFailure {
+                location,
+                function,
+                code_offset,
+            } => {
+                bytes.put_u8(3);
+                bytes.put_slice(&location.to_bytes());
+                bytes.put_u16_le(*function);
+                bytes.put_u16_le(*code_offset);
+            }
+            ExecutionStatus::MiscellaneousError(code) => {
+                bytes.put_u8(4);
+                match code {
+                    Some(code) => {
+                        bytes.put_u8(1);
+                        bytes.put_u64_le(*code);
+                    }
+                    None => bytes.put_u8(0),
+                }
+            }
         }
         bytes.to_vec()
     }
+
     /// Creates a `ExecutionStatus` from a byte slice.
     ///
     /// # Arguments
@@ -282,19 +371,10 @@ impl ExecutionStatus {
     ///
     /// A `Result` which is `Ok` if the `ExecutionStatus`
     /// could be successfully created, and `Err` otherwise.
-    pub(crate) fn from_bytes(bytes: &[u8]) -> Result<Self, TypesError> {
-        let mut buf = BytesMut::from(bytes);
-        let execution_status = match buf.get_u8() {
-            0 => ExecutionStatus::Success,
-            _ => {
-                return Err(serde_error!(
-                    "ExecutionStatus",
-                    "Invalid variant, expected only success"
-                ))
-            }
-        };
+    pub(crate) fn from_bytes(mut bytes: &[u8]) -> Result<Self, TypesError> {
+        let execution_status = Self::read(&mut bytes)?;
 
-        if buf.remaining() != 0 {
+        if bytes.remaining() != 0 {
             return Err(serde_error!(
                 "ExecutionStatus",
                 "Unexpected data after completing deserialization"
@@ -303,25 +383,221 @@ impl ExecutionStatus {
 
         Ok(execution_status)
     }
+
+    /// Estimate the size in bytes for `ExecutionStatus` from the given bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes: &[u8]` - A byte slice from which to estimate the size.
+    ///
+    /// # Returns
+    ///
+    /// The estimated size in bytes for the structure.
+    ///
+    /// # Note
+    ///
+    /// The `ExecutionStatus` bytes should start from offset 0 of the slice.
+    pub(crate) fn estimate_size_from_bytes(bytes: &[u8]) -> Result<usize, TypesError> {
+        let mut remaining = bytes;
+        Self::read(&mut remaining)?;
+
+        Ok(bytes.len() - remaining.len())
+    }
+
+    /// Reads an `ExecutionStatus` from the start of `bytes`, advancing it.
+    fn read(bytes: &mut &[u8]) -> Result<Self, TypesError> {
+        let execution_status = match read_u8(bytes, "ExecutionStatus")? {
+            0 => ExecutionStatus::Success,
+            1 => ExecutionStatus::OutOfGas,
+            2 => {
+                let location = AbortLocation::read(bytes)?;
+                let code = read_u64(bytes, "ExecutionStatus")?;
+                let info = match read_u8(bytes, "ExecutionStatus")? {
+                    0 => None,
+                    1 => Some(AbortInfo {
+                        reason_name: read_string(bytes, "AbortInfo")?,
+                        description: read_string(bytes, "AbortInfo")?,
+                    }),
+                    _ => return Err(serde_error!("ExecutionStatus", "Invalid option for info")),
+                };
+
+                ExecutionStatus::MoveAbort {
+                    location,
+                    code,
+                    info,
+                }
+            }
+            3 => {
+                let location = AbortLocation::read(bytes)?;
+                let function = read_u16(bytes, "ExecutionStatus")?;
+                let code_offset = read_u16(bytes, "ExecutionStatus")?;
+
+                ExecutionStatus::ExecutionFailure {
+                    location,
+                    function,
+                    code_offset,
+                }
+            }
+            4 => ExecutionStatus::MiscellaneousError(match read_u8(bytes, "ExecutionStatus")? {
+                0 => None,
+                1 => Some(read_u64(bytes, "ExecutionStatus")?),
+                _ => return Err(serde_error!("ExecutionStatus", "Invalid option for code")),
+            }),
+            _ => return Err(serde_error!("ExecutionStatus", "Invalid variant")),
+        };
+
+        Ok(execution_status)
+    }
+}
+
+/// Location in which a Move abort or execution failure happened.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AbortLocation {
+    Module(ModuleId),
+    Script,
+}
+
+impl AbortLocation {
+    /// Converts the `AbortLocation` to a byte vector.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<u8>` representing the `AbortLocation`.
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = BytesMut::new();
+        match self {
+            AbortLocation::Module(module_id) => {
+                bytes.put_u8(0);
+                bytes.put_slice(&module_id.address.to_bytes());
+                put_string(&mut bytes, &module_id.name);
+            }
+            AbortLocation::Script => {
+                bytes.put_u8(1);
+            }
+        }
+        bytes.to_vec()
+    }
+
+    /// Reads an `AbortLocation` from the start of `bytes`, advancing it.
+    fn read(bytes: &mut &[u8]) -> Result<Self, TypesError> {
+        match read_u8(bytes, "AbortLocation")? {
+            0 => Ok(AbortLocation::Module(ModuleId {
+                address: AccountAddress::from_bytes(read_slice(
+                    bytes,
+                    ACCOUNT_ADDRESS_SIZE,
+                    "ModuleId",
+                )?)?,
+                name: read_string(bytes, "ModuleId")?,
+            })),
+            1 => Ok(AbortLocation::Script),
+            _ => Err(serde_error!("AbortLocation", "Invalid variant")),
+        }
+    }
+}
+
+/// Identifier of a Move module, by the address of its account and its
+/// name.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ModuleId {
+    pub address: AccountAddress,
+    pub name: String,
+}
+
+/// Description of a Move abort code, as found in the error map of the
+/// aborting module.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AbortInfo {
+    pub reason_name: String,
+    pub description: String,
 }
 
 #[cfg(test)]
 mod test {
     #[test]
     fn test_bytes_conversion_execution_status() {
-        use crate::types::transaction::ExecutionStatus;
-
-        // Test ExecutionStatus::Success
-        let execution_status = ExecutionStatus::Success;
-        let execution_status_ser_bcs = bcs::to_bytes(&execution_status).unwrap();
-        let execution_status_from_bcs =
-            ExecutionStatus::from_bytes(&execution_status_ser_bcs).unwrap();
-
-        assert_eq!(execution_status, execution_status_from_bcs);
+        use crate::types::transaction::{AbortInfo, AbortLocation, ExecutionStatus, ModuleId};
+        use crate::types::AccountAddress;
+
+        let location = AbortLocation::Module(ModuleId {
+            address: AccountAddress::new([1; 32]),
+            name: "coin".to_string(),
+        });
+        for execution_status in [
+            ExecutionStatus::Success,
+            ExecutionStatus::OutOfGas,
+            ExecutionStatus::MoveAbort {
+                location: location.clone(),
+                code: 65542,
+                info: Some(AbortInfo {
+                    reason_name: "EINSUFFICIENT_BALANCE".to_string(),
+                    description: "Not enough coins to complete transaction".to_string(),
+                }),
+            },
+            ExecutionStatus::MoveAbort {
+                location: AbortLocation::Script,
+                code: 1,
+                info: None,
+            },
+            ExecutionStatus::ExecutionFailure {
+                location,
+                function: 3,
+                code_offset: 512,
+            },
+            ExecutionStatus::MiscellaneousError(Some(4016)),
+            ExecutionStatus::MiscellaneousError(None),
+        ] {
+            let execution_status_ser_bcs = bcs::to_bytes(&execution_status).unwrap();
+            let execution_status_from_bcs =
+                ExecutionStatus::from_bytes(&execution_status_ser_bcs).unwrap();
+
+            assert_eq!(execution_status, execution_status_from_bcs);
+
+            let execution_status_to_bytes = execution_status_from_bcs.to_bytes();
+
+            assert_eq!(execution_status_ser_bcs, execution_status_to_bytes);
+            assert_eq!(
+                ExecutionStatus::estimate_size_from_bytes(
+                    &[execution_status_to_bytes.as_slice(), &[0; 8]].concat()
+                )
+                .unwrap(),
+                execution_status_to_bytes.len()
+            );
+            // Truncated data is rejected rather than panicking
+            assert!(ExecutionStatus::from_bytes(
+                &execution_status_to_bytes[..execution_status_to_bytes.len() - 1]
+            )
+            .is_err());
+        }
+        assert!(ExecutionStatus::from_bytes(&[5]).is_err());
+    }
 
-        let execution_status_to_bytes = execution_status_from_bcs.to_bytes();
+    #[test]
+    fn test_bytes_conversion_failed_transaction_info() {
+        use crate::crypto::hash::HashValue;
+        use crate::types::transaction::{
+            AbortLocation, ExecutionStatus, TransactionInfo, TransactionInfoV0,
+        };
 
-        assert_eq!(execution_status_ser_bcs, execution_status_to_bytes);
+        let transaction_info = TransactionInfo::V0(TransactionInfoV0 {
+            gas_used: 10,
+            status: ExecutionStatus::MoveAbort {
+                location: AbortLocation::Script,
+                code: 7,
+                info: None,
+            },
+            transaction_hash: HashValue::new([1; 32]),
+            event_root_hash: HashValue::new([2; 32]),
+            state_change_hash: HashValue::new([3; 32]),
+            state_checkpoint_hash: None,
+            state_cemetery_hash: None,
+        });
+        let bytes = bcs::to_bytes(&transaction_info).unwrap();
+
+        assert_eq!(transaction_info.to_bytes(), bytes);
+        assert_eq!(
+            TransactionInfo::from_bytes(&bytes).unwrap(),
+            transaction_info
+        );
     }
 
     #[cfg(feature = "aptos")]
@@ -345,6 +621,43 @@ mod test {
         assert_eq!(aptos_transaction_bytes, lc_transaction_bytes);
     }
 
+    #[cfg(feature = "aptos")]
+    #[test]
+    fn test_bytes_conversion_aptos_execution_status() {
+        use crate::types::transaction::ExecutionStatus;
+        use aptos_sdk::move_types::account_address::AccountAddress;
+        use aptos_sdk::move_types::identifier::Identifier;
+        use aptos_sdk::move_types::language_storage::ModuleId;
+        use aptos_sdk::move_types::vm_status::{AbortLocation, StatusCode};
+        use aptos_types::transaction::ExecutionStatus as AptosExecutionStatus;
+
+        let location = AbortLocation::Module(ModuleId::new(
+            AccountAddress::ONE,
+            Identifier::new("coin").unwrap(),
+        ));
+        for aptos_execution_status in [
+            AptosExecutionStatus::OutOfGas,
+            AptosExecutionStatus::MoveAbort {
+                location: location.clone(),
+                code: 65542,
+                info: None,
+            },
+            AptosExecutionStatus::ExecutionFailure {
+                location,
+                function: 3,
+                code_offset: 512,
+            },
+            AptosExecutionStatus::MiscellaneousError(Some(
+                StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR,
+            )),
+        ] {
+            let aptos_bytes = bcs::to_bytes(&aptos_execution_status).unwrap();
+            let execution_status = ExecutionStatus::from_bytes(&aptos_bytes).unwrap();
+
+            assert_eq!(execution_status.to_bytes(), aptos_bytes);
+        }
+    }
+
     #[cfg(feature = "aptos")]
     #[test]
     fn test_hash_transaction_info() {
@@ -368,4 +681,4 @@ mod test {
             AptosCryptoHash::hash(aptos_transaction).as_ref()
         );
     }
-}
\ No newline at end of file
+}
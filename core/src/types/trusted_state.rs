@@ -60,14 +60,15 @@ impl TrustedState {
     /// The waypoint of the `TrustedState`.
     pub fn waypoint(&self) -> Waypoint {
         match self {
-            Self::EpochWaypoint(_waypoint) => {
-                unimplemented!("This LC doesn't support epoch waypoints")
-            }
+            Self::EpochWaypoint(waypoint) => *waypoint,
             Self::EpochState { waypoint, .. } => *waypoint,
         }
     }
+
     /// Checks if epoch change verification is required. This
-    /// is the case if the current epoch is behind the target epoch.
+    /// is the case if the current epoch is behind the target epoch,
+    /// or if we only trust an epoch waypoint, in which case we always
+    /// need to verify the epoch change it commits to.
     ///
     /// # Arguments
     ///
@@ -78,9 +79,7 @@ impl TrustedState {
     /// A boolean indicating whether epoch change verification is required.
     fn epoch_change_verification_required(&self, epoch: u64) -> bool {
         match self {
-            Self::EpochWaypoint(_waypoint) => {
-                unimplemented!("This LC doesn't support epoch waypoints")
-            }
+            Self::EpochWaypoint(_) => true,
             Self::EpochState { epoch_state, .. } => {
                 epoch_state.epoch_change_verification_required(epoch)
             }
@@ -88,7 +87,8 @@ impl TrustedState {
     }
 
     /// Checks if a ledger info is stale. This is the case if the ledger info's
-    /// epoch is behind the current trusted epoch.
+    /// epoch is behind the current trusted epoch or, when starting from an
+    /// epoch waypoint, if its version is behind the waypoint version.
     ///
     /// # Arguments
     ///
@@ -99,14 +99,14 @@ impl TrustedState {
     /// A boolean indicating whether the ledger info is stale.
     fn is_ledger_info_stale(&self, ledger_info: &LedgerInfo) -> bool {
         match self {
-            Self::EpochWaypoint(_waypoint) => {
-                unimplemented!("This LC doesn't support epoch waypoints")
-            }
+            Self::EpochWaypoint(waypoint) => ledger_info.version() < waypoint.version(),
             Self::EpochState { epoch_state, .. } => epoch_state.is_ledger_info_stale(ledger_info),
         }
     }
+
     /// Verifies a ledger info with signatures against  the current trusted state.
-    /// This will verify the signatures and the epoch number.
+    /// This will verify the signatures and the epoch number. If the trusted state
+    /// is an epoch waypoint, the ledger info is verified against the waypoint instead.
     ///
     /// # Arguments
     ///
@@ -119,19 +119,20 @@ impl TrustedState {
     /// signatures is valid, and `Err` otherwise.
     fn verify(&self, ledger_info: &LedgerInfoWithSignatures) -> anyhow::Result<()> {
         match self {
-            Self::EpochWaypoint(_waypoint) => {
-                unimplemented!("This LC doesn't support epoch waypoints")
-            }
+            Self::EpochWaypoint(waypoint) => waypoint.verify(ledger_info.ledger_info()),
             Self::EpochState { epoch_state, .. } => epoch_state.verify(ledger_info),
         }
     }
 
     /// The main LC method that verifies and ratchets the
     /// trusted state. Expects to receive an `EpochChangeProof`
-    /// containing one `LedgerInfoWithSignatures` that
-    /// represents an epoch transition from
-    /// trusted_state.epoch -> trusted_state.epoch +1, and
-    /// verifies it.
+    /// containing one `LedgerInfoWithSignatures` per epoch
+    /// transition, starting from trusted_state.epoch ->
+    /// trusted_state.epoch + 1, and verifies the whole chain.
+    ///
+    /// If the trusted state is an epoch waypoint, the first
+    /// `LedgerInfoWithSignatures` of the proof has to be the
+    /// epoch change ledger info committed to by the waypoint.
     ///
     /// # Arguments
     ///
@@ -228,7 +229,10 @@ impl TrustedState {
     /// A `Result` which is `Ok` if the `TrustedState` could
     /// be successfully created, and `Err` otherwise.
     pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, TypesError> {
-        let epoch_state_size = bytes.len() - WAYPOINT_SIZE - 1;
+        let epoch_state_size = bytes
+            .len()
+            .checked_sub(WAYPOINT_SIZE + 1)
+            .ok_or_else(|| serde_error!("TrustedState", "Not enough data for Waypoint"))?;
 
         let trusted_state = match bytes.get_u8() {
             0 => {
@@ -236,6 +240,7 @@ impl TrustedState {
                     Waypoint::from_bytes(bytes.chunk().get(..WAYPOINT_SIZE).ok_or_else(|| {
                         serde_error!("TrustedState", "Not enough data for Waypoint")
                     })?)?;
+                bytes.advance(WAYPOINT_SIZE);
                 TrustedState::EpochWaypoint(waypoint)
             }
             1 => {
@@ -384,6 +389,47 @@ impl EpochChangeProof {
         Ok(self.ledger_info_with_sigs.last().unwrap())
     }
 
+    /// Returns the epochs a [`TrustedState`] is ratcheted between when
+    /// verifying the [`EpochChangeProof`], which can span several epoch
+    /// changes.
+    ///
+    /// The start epoch is the epoch of the first non-stale
+    /// [`LedgerInfoWithSignatures`] in the proof, and the end epoch is the
+    /// epoch of the validator set carried by the last one. This method does
+    /// not verify the proof, [`EpochChangeProof::verify`] should be called
+    /// beforehand.
+    ///
+    /// # Arguments
+    ///
+    /// * `verifier` - The [`TrustedState`] the [`EpochChangeProof`] is verified against.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the start and end epochs of the ratcheting.
+    pub fn epoch_range(&self, verifier: &TrustedState) -> anyhow::Result<(u64, u64)> {
+        let start_epoch = self
+            .ledger_info_with_sigs
+            .iter()
+            .find(|&ledger_info_with_sigs| {
+                !verifier.is_ledger_info_stale(ledger_info_with_sigs.ledger_info())
+            })
+            .ok_or_else(|| {
+                format_err!("The EpochChangeProof doesn't carry a non-stale LedgerInfo")
+            })?
+            .ledger_info()
+            .epoch();
+        let end_epoch = self
+            .ledger_info_with_sigs
+            .last()
+            .and_then(|ledger_info_with_sigs| {
+                ledger_info_with_sigs.ledger_info().next_epoch_state()
+            })
+            .ok_or_else(|| format_err!("LedgerInfo doesn't carry a ValidatorSet"))?
+            .epoch;
+
+        Ok((start_epoch, end_epoch))
+    }
+
     /// Converts the `EpochChangeProof` to a byte vector.
     ///
     /// # Returns
@@ -423,9 +469,12 @@ impl EpochChangeProof {
         bytes.advance(bytes_read);
 
         // Total length for LedgerInfoWithSignatures
-        let total_len = bytes.remaining() - 1;
+        let total_len = bytes
+            .remaining()
+            .checked_sub(1)
+            .ok_or_else(|| serde_error!("EpochChangeProof", "Not enough data for more"))?;
 
-        if total_len % len as usize != 0 {
+        if len != 0 && total_len % len as usize != 0 {
             return Err(serde_error!(
                 "EpochChangeProof",
                 "Invalid data length for ledger_info_with_sigs"
@@ -512,6 +561,26 @@ mod test {
             assess_equality(&bytes);
         }
     }
+    #[test]
+    fn test_bytes_conversion_trusted_state_waypoint() {
+        use crate::aptos_test_utils::wrapper::AptosWrapper;
+
+        let mut aptos_wrapper = AptosWrapper::new(2, 130, 130).unwrap();
+
+        aptos_wrapper.generate_traffic().unwrap();
+        aptos_wrapper.commit_new_epoch().unwrap();
+
+        let waypoint = aptos_types::waypoint::Waypoint::new_epoch_boundary(
+            aptos_wrapper.get_latest_li().unwrap().ledger_info(),
+        )
+        .unwrap();
+        let trusted_state = aptos_types::trusted_state::TrustedState::from_epoch_waypoint(waypoint);
+
+        let bytes = bcs::to_bytes(&trusted_state).unwrap();
+
+        assess_equality(&bytes);
+    }
+
     proptest! {
         #![proptest_config(ProptestConfig::with_cases(10))]
         #[test]
@@ -563,4 +632,4 @@ mod test {
             aptos_trusted_state_hash.to_vec()
         );
     }
-}
\ No newline at end of file
+}
@@ -1,4 +1,7 @@
+use crate::serde_error;
+use crate::types::error::TypesError;
 use anyhow::{anyhow, Result};
+use bytes::{Buf, BufMut, BytesMut};
 
 /// Size in bytes for an enum variant representation.
 pub const ENUM_VARIANT_LEN: usize = 1;
@@ -82,6 +85,75 @@ pub fn write_leb128(mut value: u64) -> Vec<u8> {
     result
 }
 
+/// Writes a byte vector with its LEB128 encoded length, as BCS does.
+pub(crate) fn put_bytes(bytes: &mut BytesMut, value: &[u8]) {
+    bytes.put_slice(&write_leb128(value.len() as u64));
+    bytes.put_slice(value);
+}
+
+/// Writes a string with its LEB128 encoded length, as BCS does.
+pub(crate) fn put_string(bytes: &mut BytesMut, string: &str) {
+    put_bytes(bytes, string.as_bytes());
+}
+
+/// Reads `len` bytes from the start of `bytes`, advancing it.
+pub(crate) fn read_slice<'a>(
+    bytes: &mut &'a [u8],
+    len: usize,
+    structure: &str,
+) -> Result<&'a [u8], TypesError> {
+    if bytes.len() < len {
+        return Err(serde_error!(structure, "Not enough data"));
+    }
+    let (slice, remaining) = bytes.split_at(len);
+    *bytes = remaining;
+
+    Ok(slice)
+}
+
+pub(crate) fn read_u8(bytes: &mut &[u8], structure: &str) -> Result<u8, TypesError> {
+    Ok(read_slice(bytes, 1, structure)?[0])
+}
+
+pub(crate) fn read_u16(bytes: &mut &[u8], structure: &str) -> Result<u16, TypesError> {
+    let mut slice = read_slice(bytes, 2, structure)?;
+
+    Ok(slice.get_u16_le())
+}
+
+pub(crate) fn read_u64(bytes: &mut &[u8], structure: &str) -> Result<u64, TypesError> {
+    let mut slice = read_slice(bytes, U64_SIZE, structure)?;
+
+    Ok(slice.get_u64_le())
+}
+
+/// Reads a LEB128 encoded length from the start of `bytes`, advancing it.
+pub(crate) fn read_len(bytes: &mut &[u8], structure: &str) -> Result<usize, TypesError> {
+    let (len, bytes_read) = read_leb128(bytes).map_err(|e| serde_error!(structure, e))?;
+    bytes.advance(bytes_read);
+
+    Ok(len as usize)
+}
+
+/// Reads a byte vector written with its LEB128 encoded length, advancing
+/// `bytes`.
+pub(crate) fn read_bytes<'a>(
+    bytes: &mut &'a [u8],
+    structure: &str,
+) -> Result<&'a [u8], TypesError> {
+    let len = read_len(bytes, structure)?;
+
+    read_slice(bytes, len, structure)
+}
+
+/// Reads a string written with its LEB128 encoded length, advancing
+/// `bytes`.
+pub(crate) fn read_string(bytes: &mut &[u8], structure: &str) -> Result<String, TypesError> {
+    let slice = read_bytes(bytes, structure)?;
+
+    String::from_utf8(slice.to_vec()).map_err(|e| serde_error!(structure, e))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -130,4 +202,4 @@ mod tests {
         assert_eq!(num_bytes, expected_num_bytes);
         assert_eq!(value, expected);
     }
-}
\ No newline at end of file
+}
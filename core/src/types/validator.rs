@@ -3,7 +3,9 @@ use crate::crypto::sig::{AggregateSignature, BitVec, PublicKey, PUB_KEY_LEN};
 use crate::serde_error;
 use crate::types::error::{TypesError, VerifyError};
 use crate::types::ledger_info::LedgerInfo;
-use crate::types::utils::{read_leb128, write_leb128, LEB128_PUBKEY_LEN, VOTING_POWER_OFFSET_INCR};
+use crate::types::utils::{
+    read_leb128, read_u64, write_leb128, LEB128_PUBKEY_LEN, VOTING_POWER_OFFSET_INCR,
+};
 use crate::types::{AccountAddress, ACCOUNT_ADDRESS_SIZE};
 use anyhow::Result;
 use bytes::{Buf, BufMut, BytesMut};
@@ -76,7 +78,7 @@ impl ValidatorConsensusInfo {
                 )
             })?,
         )
-            .map_err(|e| serde_error!("ValidatorConsensusInfo", e))?;
+        .map_err(|e| serde_error!("ValidatorConsensusInfo", e))?;
         bytes.advance(ACCOUNT_ADDRESS_SIZE); // Advance the buffer by the size of AccountAddress
 
         let (slice_len, bytes_read) = read_leb128(bytes).map_err(|e| {
@@ -91,9 +93,9 @@ impl ValidatorConsensusInfo {
             PublicKey::from_bytes(bytes.chunk().get(..slice_len as usize).ok_or_else(|| {
                 serde_error!("ValidatorConsensusInfo", "Not enough data for PublicKey")
             })?)
-                .map_err(|e| serde_error!("ValidatorConsensusInfo", e))?;
+            .map_err(|e| serde_error!("ValidatorConsensusInfo", e))?;
         bytes.advance(slice_len as usize); // Advance the buffer by the size of PublicKey
-        let voting_power = bytes.get_u64_le();
+        let voting_power = read_u64(&mut bytes, "ValidatorConsensusInfo")?;
 
         if bytes.remaining() != 0 {
             return Err(serde_error!(
@@ -428,7 +430,10 @@ impl ValidatorVerifier {
     pub(crate) fn estimate_size_from_bytes(bytes: &[u8]) -> Result<usize, TypesError> {
         let (slice_len, bytes_read) =
             read_leb128(bytes).map_err(|e| serde_error!("ValidatorVerifier", e))?;
-        Ok(slice_len as usize * VALIDATOR_CONSENSUS_INFO_SIZE + bytes_read)
+        (slice_len as usize)
+            .checked_mul(VALIDATOR_CONSENSUS_INFO_SIZE)
+            .and_then(|size| size.checked_add(bytes_read))
+            .ok_or_else(|| serde_error!("ValidatorVerifier", "Too many validator_infos"))
     }
 }
 
@@ -524,4 +529,4 @@ mod test {
             assert_eq!(bytes, validator_to_bytes);
         }
     }
-}
\ No newline at end of file
+}
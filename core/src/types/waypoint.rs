@@ -3,8 +3,9 @@ use crate::serde_error;
 use crate::types::epoch_state::EpochState;
 use crate::types::error::TypesError;
 use crate::types::ledger_info::LedgerInfo;
-use crate::types::utils::U64_SIZE;
+use crate::types::utils::{read_u64, U64_SIZE};
 use crate::types::Version;
+use anyhow::ensure;
 use bytes::{Buf, BufMut, BytesMut};
 use getset::CopyGetters;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -18,12 +19,27 @@ pub struct Waypoint {
     #[getset(get_copy = "pub")]
     version: Version,
     /// The hash of the chosen fields of LedgerInfo.
+    #[getset(get_copy = "pub")]
     value: HashValue,
 }
 
 /// `Waypoint` is a structure representing a waypoint,
 /// which is a value that clients can use to bootstrap securely to a ledger.
 impl Waypoint {
+    /// Creates a new `Waypoint` from its version and value.
+    ///
+    /// # Arguments
+    ///
+    /// * `version: Version` - The version of the reconfiguration transaction.
+    /// * `value: HashValue` - The hash of the chosen fields of the `LedgerInfo`.
+    ///
+    /// # Returns
+    ///
+    /// A new `Waypoint`.
+    pub const fn new(version: Version, value: HashValue) -> Self {
+        Self { version, value }
+    }
+
     /// Generate a new waypoint given any LedgerInfo.
     ///
     /// # Arguments
@@ -54,6 +70,60 @@ impl Waypoint {
         }
     }
 
+    /// Generate a new waypoint given an epoch change LedgerInfo.
+    ///
+    /// Contrary to [`Waypoint::new_any`], the value is always computed
+    /// from the `LedgerInfo`, regardless of the `waypoint` feature, as
+    /// the resulting `Waypoint` is meant to be used as a root of trust.
+    ///
+    /// # Arguments
+    ///
+    /// * `ledger_info: &LedgerInfo` - The epoch change ledger info.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which is `Ok` with the new `Waypoint` if the
+    /// ledger info ends an epoch, and `Err` otherwise.
+    pub fn new_epoch_boundary(ledger_info: &LedgerInfo) -> anyhow::Result<Self> {
+        ensure!(
+            ledger_info.next_epoch_state().is_some(),
+            "No validator set in the provided LedgerInfo"
+        );
+        let converter = Ledger2WaypointConverter::new(ledger_info);
+        Ok(Self {
+            version: ledger_info.version(),
+            value: converter.hash(),
+        })
+    }
+
+    /// Verifies that the given `LedgerInfo` is the one committed
+    /// to by the `Waypoint`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ledger_info: &LedgerInfo` - The ledger info to verify.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which is `Ok` if both the version and the hash of
+    /// the ledger info match the `Waypoint`, and `Err` otherwise.
+    pub fn verify(&self, ledger_info: &LedgerInfo) -> anyhow::Result<()> {
+        ensure!(
+            ledger_info.version() == self.version,
+            "Waypoint version mismatch: waypoint version = {}, given version = {}",
+            self.version,
+            ledger_info.version()
+        );
+        let converter = Ledger2WaypointConverter::new(ledger_info);
+        ensure!(
+            converter.hash() == self.value,
+            "Waypoint value mismatch: waypoint value = {:x}, given value = {:x}",
+            self.value,
+            converter.hash()
+        );
+        Ok(())
+    }
+
     /// Creates a `Waypoint` from a byte slice.
     ///
     /// # Arguments
@@ -65,7 +135,7 @@ impl Waypoint {
     /// A `Result` which is `Ok` if the `Waypoint` could
     /// be successfully created, and `Err` otherwise.
     pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, TypesError> {
-        let version = bytes.get_u64_le();
+        let version = read_u64(&mut bytes, "Waypoint")?;
 
         let value = HashValue::from_slice(
             bytes
@@ -73,7 +143,7 @@ impl Waypoint {
                 .get(..HASH_LENGTH)
                 .ok_or_else(|| serde_error!("Waypoint", "Not enough data for value"))?,
         )
-            .map_err(|e| serde_error!("Waypoint", e))?;
+        .map_err(|e| serde_error!("Waypoint", e))?;
 
         bytes.advance(HASH_LENGTH);
 
@@ -146,7 +216,6 @@ impl Ledger2WaypointConverter {
     /// # Returns
     ///
     /// A new `Ledger2WaypointConverter`.
-    #[allow(dead_code)]
     pub(crate) fn new(ledger_info: &LedgerInfo) -> Self {
         Self {
             epoch: ledger_info.epoch(),
@@ -213,11 +282,69 @@ mod test {
         assert_eq!(expected, actual.hash());
     }
 
+    #[test]
+    fn test_verify_epoch_boundary() {
+        use crate::types::block_info::BlockInfo;
+
+        let ledger_info = |timestamp_usecs: u64, next_epoch_state: Option<EpochState>| {
+            LedgerInfo::new(
+                BlockInfo::new(
+                    1,
+                    0,
+                    HashValue::new([1; HASH_LENGTH]),
+                    HashValue::new([2; HASH_LENGTH]),
+                    42,
+                    timestamp_usecs,
+                    next_epoch_state,
+                ),
+                HashValue::default(),
+            )
+        };
+
+        let epoch_change_li = ledger_info(10, Some(EpochState::default()));
+        let waypoint = Waypoint::new_epoch_boundary(&epoch_change_li).unwrap();
+
+        assert_eq!(waypoint.version(), 42);
+        waypoint.verify(&epoch_change_li).unwrap();
+
+        // Any change in the committed fields should be detected
+        assert!(waypoint
+            .verify(&ledger_info(11, Some(EpochState::default())))
+            .is_err());
+
+        // Only epoch change ledger infos can be used as an epoch boundary
+        assert!(Waypoint::new_epoch_boundary(&ledger_info(10, None)).is_err());
+    }
+
     #[cfg(feature = "aptos")]
     mod aptos {
         use proptest::prelude::ProptestConfig;
         use proptest::proptest;
 
+        #[test]
+        fn test_waypoint_epoch_boundary() {
+            use super::*;
+            use crate::aptos_test_utils::wrapper::AptosWrapper;
+
+            let mut aptos_wrapper = AptosWrapper::new(2, 130, 130).unwrap();
+
+            aptos_wrapper.generate_traffic().unwrap();
+            aptos_wrapper.commit_new_epoch().unwrap();
+
+            let aptos_li = aptos_wrapper.get_latest_li().unwrap().ledger_info().clone();
+            let aptos_waypoint =
+                aptos_types::waypoint::Waypoint::new_epoch_boundary(&aptos_li).unwrap();
+
+            let intern_li = LedgerInfo::from_bytes(&bcs::to_bytes(&aptos_li).unwrap()).unwrap();
+            let intern_waypoint = Waypoint::new_epoch_boundary(&intern_li).unwrap();
+
+            assert_eq!(
+                bcs::to_bytes(&aptos_waypoint).unwrap(),
+                intern_waypoint.to_bytes()
+            );
+            intern_waypoint.verify(&intern_li).unwrap();
+        }
+
         proptest! {
             #![proptest_config(ProptestConfig::with_cases(10))]
             #[test]
@@ -244,4 +371,4 @@ mod test {
             }
         }
     }
-}
\ No newline at end of file
+}
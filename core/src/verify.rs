@@ -0,0 +1,371 @@
+//! Checks run by the programs on their inputs.
+//!
+//! Each function runs the whole check sequence of a program and returns the
+//! output the program commits to. The programs `expect` on their result,
+//! while the host runs them natively to verify or preflight proofs, so that
+//! both always run the same checks.
+
+use crate::crypto::hash::{CryptoHash, HashValue};
+use crate::merkle::consistency_proof::{
+    AccumulatorConsistencyProof, TransactionAccumulatorSummary,
+};
+use crate::merkle::event_proof::EventAccumulatorProof;
+use crate::merkle::sparse_proof::SparseMerkleProof;
+use crate::merkle::transaction_proof::{
+    transaction_infos_digest, TransactionAccumulatorProof, TransactionAccumulatorRangeProof,
+};
+use crate::types::assets::{
+    AccumulatorConsistencyProofAssets, EventProofAssets, SparseMerkleProofAssets,
+    TransactionProofAssets, TransactionRangeProofAssets, ValidatorVerifierAssets,
+};
+use crate::types::event::ContractEvent;
+use crate::types::ledger_info::LedgerInfoWithSignatures;
+use crate::types::output::{
+    AccumulatorConsistencyOutput, EpochChangeOutput, EventInclusionOutput, InclusionOutput,
+    TransactionExecutionOutput, TransactionRangeOutput,
+};
+use crate::types::transaction::TransactionInfo;
+use crate::types::trusted_state::{EpochChangeProof, TrustedState, TrustedStateChange};
+use crate::types::validator::ValidatorVerifier;
+use crate::types::waypoint::Waypoint;
+use anyhow::{anyhow, bail, ensure, Result};
+
+/// Runs the checks of the inclusion program.
+///
+/// # Arguments
+///
+/// * `sparse_merkle_proof_assets` - The proof of the leaf in the state checkpoint of the transaction.
+/// * `transaction_proof_assets` - The proof of the transaction in the signed ledger info.
+/// * `validator_verifier_assets` - The validator verifier that signed the ledger info.
+///
+/// # Returns
+///
+/// The `InclusionOutput` the inclusion program commits to.
+pub fn verify_inclusion(
+    sparse_merkle_proof_assets: &SparseMerkleProofAssets,
+    transaction_proof_assets: &TransactionProofAssets,
+    validator_verifier_assets: &ValidatorVerifierAssets,
+) -> Result<InclusionOutput> {
+    let validator_verifier =
+        ValidatorVerifier::from_bytes(validator_verifier_assets.validator_verifier())?;
+
+    // Verify transaction inclusion in the LedgerInfoWithSignatures
+    let transaction = TransactionInfo::from_bytes(transaction_proof_assets.transaction())?;
+    let transaction_proof =
+        TransactionAccumulatorProof::from_bytes(transaction_proof_assets.transaction_proof())?;
+    let latest_li = LedgerInfoWithSignatures::from_bytes(transaction_proof_assets.latest_li())?;
+    let expected_root_hash = latest_li.ledger_info().transaction_accumulator_hash();
+
+    transaction_proof.verify(
+        expected_root_hash,
+        transaction.hash(),
+        *transaction_proof_assets.transaction_index(),
+    )?;
+    latest_li.verify_signatures(&validator_verifier)?;
+
+    // Verify the leaf inclusion in the state checkpoint
+    let sparse_merkle_proof =
+        SparseMerkleProof::from_bytes(sparse_merkle_proof_assets.sparse_merkle_proof())?;
+    let sparse_expected_root_hash = transaction
+        .state_checkpoint()
+        .ok_or_else(|| anyhow!("TransactionInfo doesn't carry a state checkpoint"))?;
+    let reconstructed_root_hash = sparse_merkle_proof.verify_by_hash(
+        sparse_expected_root_hash,
+        HashValue::from_slice(sparse_merkle_proof_assets.leaf_key())?,
+        HashValue::from_slice(sparse_merkle_proof_assets.leaf_hash())?,
+    )?;
+
+    let latest_li = latest_li.ledger_info();
+
+    Ok(InclusionOutput {
+        epoch: latest_li.epoch(),
+        version: latest_li.version(),
+        timestamp_usecs: latest_li.timestamp_usecs(),
+        current_block_id: *latest_li.block_id().as_ref(),
+        transaction_accumulator_hash: *expected_root_hash.as_ref(),
+        validator_verifier_hash: *validator_verifier.hash().as_ref(),
+        reconstructed_root_hash: *reconstructed_root_hash.as_ref(),
+        key: *sparse_merkle_proof_assets.leaf_key(),
+        leaf_value_hash: *sparse_merkle_proof_assets.leaf_hash(),
+    })
+}
+
+/// Runs the checks of the transaction execution program.
+///
+/// # Arguments
+///
+/// * `transaction_proof_assets` - The proof of the transaction in the signed ledger info,
+///   whose `transaction_index` is the version of the transaction.
+/// * `validator_verifier_assets` - The validator verifier that signed the ledger info.
+///
+/// # Returns
+///
+/// The `TransactionExecutionOutput` the transaction execution program commits to.
+pub fn verify_transaction_execution(
+    transaction_proof_assets: &TransactionProofAssets,
+    validator_verifier_assets: &ValidatorVerifierAssets,
+) -> Result<TransactionExecutionOutput> {
+    let validator_verifier =
+        ValidatorVerifier::from_bytes(validator_verifier_assets.validator_verifier())?;
+
+    // Verify that the transaction was committed at `version` in the
+    // LedgerInfoWithSignatures
+    let transaction = TransactionInfo::from_bytes(transaction_proof_assets.transaction())?;
+    let transaction_proof =
+        TransactionAccumulatorProof::from_bytes(transaction_proof_assets.transaction_proof())?;
+    let latest_li = LedgerInfoWithSignatures::from_bytes(transaction_proof_assets.latest_li())?;
+    let expected_root_hash = latest_li.ledger_info().transaction_accumulator_hash();
+    let version = *transaction_proof_assets.transaction_index();
+
+    transaction_proof.verify(expected_root_hash, transaction.hash(), version)?;
+    latest_li.verify_signatures(&validator_verifier)?;
+
+    Ok(TransactionExecutionOutput {
+        validator_verifier_hash: *validator_verifier.hash().as_ref(),
+        current_block_id: *latest_li.ledger_info().block_id().as_ref(),
+        transaction_accumulator_hash: *expected_root_hash.as_ref(),
+        version,
+        transaction_hash: *transaction.transaction_hash().as_ref(),
+        status: transaction.status().clone(),
+        gas_used: transaction.gas_used(),
+    })
+}
+
+/// Runs the checks of the transaction range program.
+///
+/// # Arguments
+///
+/// * `transaction_range_proof_assets` - The range proof of the transactions in the signed
+///   ledger info.
+/// * `validator_verifier_assets` - The validator verifier that signed the ledger info.
+///
+/// # Returns
+///
+/// The `TransactionRangeOutput` the transaction range program commits to.
+pub fn verify_transaction_range(
+    transaction_range_proof_assets: &TransactionRangeProofAssets,
+    validator_verifier_assets: &ValidatorVerifierAssets,
+) -> Result<TransactionRangeOutput> {
+    let validator_verifier =
+        ValidatorVerifier::from_bytes(validator_verifier_assets.validator_verifier())?;
+
+    let transaction_hashes = transaction_range_proof_assets
+        .transactions()
+        .iter()
+        .map(|transaction| Ok(TransactionInfo::from_bytes(transaction)?.hash()))
+        .collect::<Result<Vec<HashValue>>>()?;
+
+    // Verify that the transactions were committed from `first_version` in
+    // the LedgerInfoWithSignatures
+    let range_proof =
+        TransactionAccumulatorRangeProof::from_bytes(transaction_range_proof_assets.range_proof())?;
+    let latest_li =
+        LedgerInfoWithSignatures::from_bytes(transaction_range_proof_assets.latest_li())?;
+    let expected_root_hash = latest_li.ledger_info().transaction_accumulator_hash();
+    let first_version = *transaction_range_proof_assets.first_version();
+
+    range_proof.verify(expected_root_hash, first_version, &transaction_hashes)?;
+    latest_li.verify_signatures(&validator_verifier)?;
+
+    Ok(TransactionRangeOutput {
+        validator_verifier_hash: *validator_verifier.hash().as_ref(),
+        current_block_id: *latest_li.ledger_info().block_id().as_ref(),
+        transaction_accumulator_hash: *expected_root_hash.as_ref(),
+        first_version,
+        last_version: first_version + transaction_hashes.len() as u64 - 1,
+        transaction_infos_digest: *transaction_infos_digest(&transaction_hashes).as_ref(),
+    })
+}
+
+/// Runs the checks of the event inclusion program.
+///
+/// # Arguments
+///
+/// * `event_proof_assets` - The proof of the event in the event accumulator of the transaction.
+/// * `transaction_proof_assets` - The proof of the transaction in the signed ledger info,
+///   whose `transaction_index` is the version of the transaction.
+/// * `validator_verifier_assets` - The validator verifier that signed the ledger info.
+///
+/// # Returns
+///
+/// The `EventInclusionOutput` the event inclusion program commits to.
+pub fn verify_event_inclusion(
+    event_proof_assets: &EventProofAssets,
+    transaction_proof_assets: &TransactionProofAssets,
+    validator_verifier_assets: &ValidatorVerifierAssets,
+) -> Result<EventInclusionOutput> {
+    let validator_verifier =
+        ValidatorVerifier::from_bytes(validator_verifier_assets.validator_verifier())?;
+
+    // Verify that the event was emitted by the transaction
+    let event = ContractEvent::from_bytes(event_proof_assets.event())?;
+    let event_proof = EventAccumulatorProof::from_bytes(event_proof_assets.event_proof())?;
+    let transaction = TransactionInfo::from_bytes(transaction_proof_assets.transaction())?;
+    let event_index = *event_proof_assets.event_index();
+
+    event_proof.verify(transaction.event_root_hash(), event.hash(), event_index)?;
+
+    // Verify that the transaction was committed at `version` in the
+    // LedgerInfoWithSignatures
+    let transaction_proof =
+        TransactionAccumulatorProof::from_bytes(transaction_proof_assets.transaction_proof())?;
+    let latest_li = LedgerInfoWithSignatures::from_bytes(transaction_proof_assets.latest_li())?;
+    let expected_root_hash = latest_li.ledger_info().transaction_accumulator_hash();
+    let version = *transaction_proof_assets.transaction_index();
+
+    transaction_proof.verify(expected_root_hash, transaction.hash(), version)?;
+    latest_li.verify_signatures(&validator_verifier)?;
+
+    Ok(EventInclusionOutput {
+        validator_verifier_hash: *validator_verifier.hash().as_ref(),
+        current_block_id: *latest_li.ledger_info().block_id().as_ref(),
+        transaction_accumulator_hash: *expected_root_hash.as_ref(),
+        version,
+        event_index,
+        event,
+    })
+}
+
+/// Runs the checks of the accumulator consistency program.
+///
+/// # Arguments
+///
+/// * `accumulator_consistency_proof_assets` - The summary of the accumulator at the previous
+///   ledger info, both signed ledger infos and the consistency proof between them.
+/// * `prev_validator_verifier_assets` - The validator verifier that signed the previous
+///   ledger info.
+/// * `validator_verifier_assets` - The validator verifier that signed the latest ledger info.
+///
+/// # Returns
+///
+/// The `AccumulatorConsistencyOutput` the accumulator consistency program commits to.
+pub fn verify_accumulator_consistency(
+    accumulator_consistency_proof_assets: &AccumulatorConsistencyProofAssets,
+    prev_validator_verifier_assets: &ValidatorVerifierAssets,
+    validator_verifier_assets: &ValidatorVerifierAssets,
+) -> Result<AccumulatorConsistencyOutput> {
+    let prev_validator_verifier =
+        ValidatorVerifier::from_bytes(prev_validator_verifier_assets.validator_verifier())?;
+    let validator_verifier =
+        ValidatorVerifier::from_bytes(validator_verifier_assets.validator_verifier())?;
+
+    // Verify that the accumulator summary is the one of the previous
+    // LedgerInfoWithSignatures
+    let accumulator_summary = TransactionAccumulatorSummary::from_bytes(
+        accumulator_consistency_proof_assets.accumulator_summary(),
+    )?;
+    let prev_li =
+        LedgerInfoWithSignatures::from_bytes(accumulator_consistency_proof_assets.prev_li())?;
+    let prev_root_hash = prev_li.ledger_info().transaction_accumulator_hash();
+
+    ensure!(
+        accumulator_summary.root_hash() == prev_root_hash,
+        "Accumulator summary does not match the previous LedgerInfo root hash"
+    );
+    ensure!(
+        accumulator_summary.version() == prev_li.ledger_info().version(),
+        "Accumulator summary does not match the previous LedgerInfo version"
+    );
+    prev_li.verify_signatures(&prev_validator_verifier)?;
+
+    // Verify that extending the accumulator with the consistency proof
+    // yields the accumulator of the latest LedgerInfoWithSignatures
+    let consistency_proof = AccumulatorConsistencyProof::from_bytes(
+        accumulator_consistency_proof_assets.consistency_proof(),
+    )?;
+    let latest_li =
+        LedgerInfoWithSignatures::from_bytes(accumulator_consistency_proof_assets.latest_li())?;
+
+    accumulator_summary.try_extend_with_proof(&consistency_proof, latest_li.ledger_info())?;
+    latest_li.verify_signatures(&validator_verifier)?;
+
+    Ok(AccumulatorConsistencyOutput {
+        prev_validator_verifier_hash: *prev_validator_verifier.hash().as_ref(),
+        prev_version: prev_li.ledger_info().version(),
+        prev_transaction_accumulator_hash: *prev_root_hash.as_ref(),
+        validator_verifier_hash: *validator_verifier.hash().as_ref(),
+        version: latest_li.ledger_info().version(),
+        transaction_accumulator_hash: *latest_li
+            .ledger_info()
+            .transaction_accumulator_hash()
+            .as_ref(),
+    })
+}
+
+/// Runs the checks of the epoch change program.
+///
+/// # Arguments
+///
+/// * `trusted_state` - The serialized current `TrustedState`.
+/// * `epoch_change_proof` - The serialized `EpochChangeProof` to ratchet it with.
+///
+/// # Returns
+///
+/// The `EpochChangeOutput` the epoch change program commits to.
+pub fn verify_epoch_change(
+    trusted_state: &[u8],
+    epoch_change_proof: &[u8],
+) -> Result<EpochChangeOutput> {
+    let trusted_state = TrustedState::from_bytes(trusted_state)?;
+    let epoch_change_proof = EpochChangeProof::from_bytes(epoch_change_proof)?;
+
+    let (output, _) = ratchet_trusted_state(&trusted_state, &epoch_change_proof)?;
+
+    Ok(output)
+}
+
+/// Runs the checks of the epoch change program on deserialized inputs,
+/// also returning the ratcheted trusted state.
+///
+/// # Arguments
+///
+/// * `trusted_state` - The current `TrustedState`.
+/// * `epoch_change_proof` - The `EpochChangeProof` to ratchet it with.
+///
+/// # Returns
+///
+/// The `EpochChangeOutput` the epoch change program commits to, and the new
+/// `TrustedState`.
+pub fn ratchet_trusted_state(
+    trusted_state: &TrustedState,
+    epoch_change_proof: &EpochChangeProof,
+) -> Result<(EpochChangeOutput, TrustedState)> {
+    let (new_state, latest_epoch_change_li) =
+        match trusted_state.verify_and_ratchet_inner(epoch_change_proof)? {
+            TrustedStateChange::Epoch {
+                new_state,
+                latest_epoch_change_li,
+            } => (new_state, latest_epoch_change_li.ledger_info()),
+            _ => bail!("Expected epoch change"),
+        };
+    let validator_verifier_hash = latest_epoch_change_li
+        .next_epoch_state()
+        .ok_or_else(|| anyhow!("LedgerInfo doesn't carry a ValidatorSet"))?
+        .verifier()
+        .hash();
+    let new_waypoint = Waypoint::new_epoch_boundary(latest_epoch_change_li)?;
+    // The proof can span several epoch changes, commit to both ends of it.
+    let (start_epoch, end_epoch) = epoch_change_proof.epoch_range(trusted_state)?;
+    // When bootstrapping from a waypoint there is no previously trusted
+    // validator set, so we commit to the waypoint value instead.
+    let prev_epoch_validator_verifier_hash = match trusted_state {
+        TrustedState::EpochState { epoch_state, .. } => epoch_state.verifier().hash(),
+        TrustedState::EpochWaypoint(waypoint) => waypoint.value(),
+    };
+
+    let output = EpochChangeOutput {
+        start_epoch,
+        end_epoch,
+        version: latest_epoch_change_li.version(),
+        timestamp_usecs: latest_epoch_change_li.timestamp_usecs(),
+        block_id: *latest_epoch_change_li.block_id().as_ref(),
+        transaction_accumulator_hash: *latest_epoch_change_li
+            .transaction_accumulator_hash()
+            .as_ref(),
+        prev_epoch_validator_verifier_hash: *prev_epoch_validator_verifier_hash.as_ref(),
+        validator_verifier_hash: *validator_verifier_hash.as_ref(),
+        new_waypoint: *new_waypoint.value().as_ref(),
+    };
+
+    Ok((output, new_state))
+}
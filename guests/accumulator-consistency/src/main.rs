@@ -0,0 +1,27 @@
+use risc0_zkvm::guest::env;
+use aptos_lc_core::types::assets::{AccumulatorConsistencyProofAssets, ValidatorVerifierAssets};
+use aptos_lc_core::verify::verify_accumulator_consistency;
+
+fn main() {
+    let accumulator_summary_bytes: Vec<u8> = env::read();
+    let prev_ledger_info_bytes: Vec<u8> = env::read();
+    let consistency_proof: Vec<u8> = env::read();
+    let ledger_info_bytes: Vec<u8> = env::read();
+
+    let prev_verified_validator_verifier: Vec<u8> = env::read();
+    let verified_validator_verifier: Vec<u8> = env::read();
+
+    let output = verify_accumulator_consistency(
+        &AccumulatorConsistencyProofAssets::new(
+            accumulator_summary_bytes,
+            prev_ledger_info_bytes,
+            consistency_proof,
+            ledger_info_bytes,
+        ),
+        &ValidatorVerifierAssets::new(prev_verified_validator_verifier),
+        &ValidatorVerifierAssets::new(verified_validator_verifier),
+    )
+    .expect("verify_accumulator_consistency: could not verify accumulator consistency");
+
+    env::commit_slice(&output.to_bytes());
+}
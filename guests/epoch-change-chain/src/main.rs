@@ -0,0 +1,85 @@
+use risc0_zkvm::guest::env;
+use risc0_zkvm::serde::to_vec;
+
+use aptos_lc_core::crypto::hash::CryptoHash;
+use aptos_lc_core::types::output::EpochChangeChainOutput;
+use aptos_lc_core::types::trusted_state::{EpochChangeProof, TrustedState, TrustedStateChange};
+
+fn main() {
+    let image_id: [u32; 8] = env::read();
+    let prev_output: Option<EpochChangeChainOutput> = env::read();
+    let trusted_state_bytes: Vec<u8> = env::read();
+    let epoch_change_proof: Vec<u8> = env::read();
+    let trusted_state = TrustedState::from_bytes(&trusted_state_bytes)
+        .expect("TrustedState::from_bytes: could not create trusted state");
+    let epoch_change_proof = EpochChangeProof::from_bytes(&epoch_change_proof)
+        .expect("EpochChangeProof::from_bytes: could not create epoch change proof");
+
+    // When bootstrapping from a waypoint there is no previously trusted
+    // validator set, so we commit to the waypoint value instead.
+    let prev_epoch_validator_verifier_hash = match &trusted_state {
+        TrustedState::EpochState { epoch_state, .. } => epoch_state.verifier().hash(),
+        TrustedState::EpochWaypoint(waypoint) => waypoint.value(),
+    };
+
+    let trusted_state_change = trusted_state
+        .verify_and_ratchet_inner(&epoch_change_proof)
+        .expect("TrustedState::verify_and_ratchet_inner: could not ratchet");
+    let validator_verifier_hash = match trusted_state_change {
+        TrustedStateChange::Epoch {
+            latest_epoch_change_li,
+            ..
+        } => latest_epoch_change_li
+            .ledger_info()
+            .next_epoch_state()
+            .expect("Expected epoch state")
+            .verifier()
+            .hash(),
+        _ => panic!("Expected epoch change"),
+    };
+    let (start_epoch, end_epoch) = epoch_change_proof
+        .epoch_range(&trusted_state)
+        .expect("EpochChangeProof::epoch_range: could not get epoch range");
+
+    // Either start a new chain from the current trusted state, or extend
+    // the chain attested to by a previous receipt of this program.
+    let (genesis_validator_verifier_hash, start_epoch) = match prev_output {
+        None => (*prev_epoch_validator_verifier_hash.as_ref(), start_epoch),
+        Some(prev_output) => {
+            assert_eq!(
+                prev_output.image_id, image_id,
+                "image_id: previous receipt was not generated by this program"
+            );
+            env::verify(
+                image_id,
+                &to_vec(&prev_output).expect("to_vec: could not serialize previous output"),
+            )
+            .expect("env::verify: could not verify previous receipt");
+            assert_eq!(
+                prev_output.validator_verifier_hash,
+                *prev_epoch_validator_verifier_hash.as_ref(),
+                "validator_verifier_hash: trusted state does not match the end of the chain"
+            );
+            assert_eq!(
+                trusted_state.epoch(),
+                Some(prev_output.end_epoch),
+                "epoch: trusted state does not match the end of the chain"
+            );
+
+            (
+                prev_output.genesis_validator_verifier_hash,
+                prev_output.start_epoch,
+            )
+        }
+    };
+
+    let output = EpochChangeChainOutput {
+        image_id,
+        start_epoch,
+        end_epoch,
+        genesis_validator_verifier_hash,
+        validator_verifier_hash: *validator_verifier_hash.as_ref(),
+    };
+
+    env::commit(&output);
+}
@@ -0,0 +1,32 @@
+use risc0_zkvm::guest::env;
+use aptos_lc_core::types::assets::{
+    EventProofAssets, TransactionProofAssets, ValidatorVerifierAssets,
+};
+use aptos_lc_core::verify::verify_event_inclusion;
+
+fn main() {
+    let event_bytes: Vec<u8> = env::read();
+    let event_index: u64 = env::read();
+    let event_proof: Vec<u8> = env::read();
+
+    let transaction_bytes: Vec<u8> = env::read();
+    let version: u64 = env::read();
+    let transaction_proof: Vec<u8> = env::read();
+    let ledger_info_bytes: Vec<u8> = env::read();
+
+    let verified_validator_verifier: Vec<u8> = env::read();
+
+    let output = verify_event_inclusion(
+        &EventProofAssets::new(event_bytes, event_index, event_proof),
+        &TransactionProofAssets::new(
+            transaction_bytes,
+            version,
+            transaction_proof,
+            ledger_info_bytes,
+        ),
+        &ValidatorVerifierAssets::new(verified_validator_verifier),
+    )
+    .expect("verify_event_inclusion: could not verify event inclusion");
+
+    env::commit_slice(&output.to_bytes());
+}
@@ -0,0 +1,25 @@
+use risc0_zkvm::guest::env;
+use aptos_lc_core::types::assets::{TransactionProofAssets, ValidatorVerifierAssets};
+use aptos_lc_core::verify::verify_transaction_execution;
+
+fn main() {
+    let transaction_bytes: Vec<u8> = env::read();
+    let version: u64 = env::read();
+    let transaction_proof: Vec<u8> = env::read();
+    let ledger_info_bytes: Vec<u8> = env::read();
+
+    let verified_validator_verifier: Vec<u8> = env::read();
+
+    let output = verify_transaction_execution(
+        &TransactionProofAssets::new(
+            transaction_bytes,
+            version,
+            transaction_proof,
+            ledger_info_bytes,
+        ),
+        &ValidatorVerifierAssets::new(verified_validator_verifier),
+    )
+    .expect("verify_transaction_execution: could not verify transaction execution");
+
+    env::commit_slice(&output.to_bytes());
+}
@@ -0,0 +1,25 @@
+use risc0_zkvm::guest::env;
+use aptos_lc_core::types::assets::{TransactionRangeProofAssets, ValidatorVerifierAssets};
+use aptos_lc_core::verify::verify_transaction_range;
+
+fn main() {
+    let transactions_bytes: Vec<Vec<u8>> = env::read();
+    let first_version: u64 = env::read();
+    let range_proof: Vec<u8> = env::read();
+    let ledger_info_bytes: Vec<u8> = env::read();
+
+    let verified_validator_verifier: Vec<u8> = env::read();
+
+    let output = verify_transaction_range(
+        &TransactionRangeProofAssets::new(
+            transactions_bytes,
+            first_version,
+            range_proof,
+            ledger_info_bytes,
+        ),
+        &ValidatorVerifierAssets::new(verified_validator_verifier),
+    )
+    .expect("verify_transaction_range: could not verify transaction range");
+
+    env::commit_slice(&output.to_bytes());
+}
@@ -9,7 +9,6 @@ use serde::Serialize;
 const NBR_VALIDATORS: usize = 130;
 const AVERAGE_SIGNERS_NBR: usize = 95;
 
-
 struct ProvingAssets {
     trusted_state: Vec<u8>,
     validator_verifier_hash: Vec<u8>,
@@ -50,7 +49,6 @@ struct Timings {
     verifying_time: u128,
 }
 
-
 fn main() {
     // Setup the logger.
     tracing_subscriber::fmt()
@@ -59,13 +57,17 @@ fn main() {
 
     let proving_assets = ProvingAssets::new();
     let prover = default_prover();
-    let (receipt, output) = prove_epoch_change(&*prover, &proving_assets.trusted_state, &proving_assets.epoch_change_proof).unwrap();
+    let (receipt, output) = prove_epoch_change(
+        &*prover,
+        &proving_assets.trusted_state,
+        &proving_assets.epoch_change_proof,
+        false,
+    )
+    .unwrap();
     assert_eq!(
         output.prev_epoch_validator_verifier_hash,
         proving_assets.validator_verifier_hash.as_slice()
     );
 
-    receipt
-        .verify(EPOCH_CHANGE_ID)
-        .unwrap();
+    receipt.verify(EPOCH_CHANGE_ID).unwrap();
 }
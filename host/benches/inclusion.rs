@@ -8,7 +8,9 @@ use aptos_lc_core::crypto::hash::CryptoHash;
 use aptos_lc_core::types::ledger_info::LedgerInfoWithSignatures;
 use aptos_lc_core::types::trusted_state::TrustedState;
 use aptos_lc_core::types::validator::ValidatorVerifier;
-use host::inclusion::{prove_inclusion, SparseMerkleProofAssets, TransactionProofAssets, ValidatorVerifierAssets};
+use host::inclusion::{
+    prove_inclusion, SparseMerkleProofAssets, TransactionProofAssets, ValidatorVerifierAssets,
+};
 const NBR_LEAVES: [usize; 5] = [32, 128, 2048, 8192, 32768];
 const NBR_VALIDATORS: usize = 130;
 const AVERAGE_SIGNERS_NBR: usize = 95;
@@ -87,8 +89,14 @@ fn main() {
         let proving_assets = ProvingAssets::from_nbr_leaves(nbr_leaves);
         let start_proving = Instant::now();
         let prover = default_prover();
-        let (receipt, output) = prove_inclusion(&*prover, &proving_assets.sparse_merkle_proof_assets, &proving_assets.transaction_proof_assets,
-                                                &proving_assets.validator_verifier_assets).unwrap();
+        let (receipt, output) = prove_inclusion(
+            &*prover,
+            &proving_assets.sparse_merkle_proof_assets,
+            &proving_assets.transaction_proof_assets,
+            &proving_assets.validator_verifier_assets,
+            false,
+        )
+        .unwrap();
         let proving_time = start_proving.elapsed();
 
         let start_verifying = Instant::now();
@@ -104,9 +112,9 @@ fn main() {
                     .validator_verifier_assets
                     .validator_verifier()
             )
-                .unwrap()
-                .hash()
-                .as_ref()
+            .unwrap()
+            .hash()
+            .as_ref()
         );
 
         // Verify the consistency of the final merkle root hash computed
@@ -1,5 +1,6 @@
 use serde::Serialize;
 
+use host::error::ClientError;
 use std::env;
 use std::fs::File;
 use std::io::Read;
@@ -8,20 +9,17 @@ use std::time::{Duration, Instant};
 use tokio::net::TcpStream;
 use tokio::runtime::Runtime;
 use tokio::time::sleep;
-use host::error::ClientError;
 
 use anyhow::anyhow;
 use bcs::from_bytes;
 use host::aptos::{AccountInclusionProofResponse, EpochChangeProofResponse};
 use host::types::{EpochChangeData, InclusionData, ProvingMode, Request};
 
-
 const ACCOUNT_INCLUSION_DATA_PATH: &str = "./benches/assets/account_inclusion_data.bcs";
 const EPOCH_CHANGE_DATA_PATH: &str = "./benches/assets/epoch_change_data.bcs";
 const DEFAULT_RUST_LOG: &str = "warn";
 const DEFAULT_RUSTFLAGS: &str = "--cfg tokio_unstable -C opt-level=3";
 
-
 #[derive(Debug, Clone, Serialize)]
 struct BenchResults {
     e2e_proving_time: u128,
@@ -36,7 +34,6 @@ struct ProofData {
     request_response_proof_size: usize,
 }
 
-
 fn main() -> Result<(), anyhow::Error> {
     let final_snark: bool = env::var("SNARK").unwrap_or_else(|_| "0".into()) == "1";
     let run_parallel: bool = env::var("RUN_PARALLEL").unwrap_or_else(|_| "0".into()) == "1";
@@ -52,7 +49,7 @@ fn main() -> Result<(), anyhow::Error> {
         final_snark,
         rust_log.clone(),
         rustflags.clone(),
-        cuda.clone()
+        cuda.clone(),
     ))?;
 
     // Start primary server
@@ -60,7 +57,7 @@ fn main() -> Result<(), anyhow::Error> {
         final_snark,
         rust_log.clone(),
         rustflags.clone(),
-        cuda
+        cuda,
     ))?;
 
     // Join the benchmark tasks and block until they are done
@@ -91,14 +88,7 @@ fn main() -> Result<(), anyhow::Error> {
         epoch_change_proof.proving_time
     };
 
-    let (
-        final_snark,
-        rustflags,
-    ) = get_parameters(
-        final_snark,
-        rust_log,
-        rustflags,
-    );
+    let (final_snark, rustflags) = get_parameters(final_snark, rust_log, rustflags);
 
     let bench_results = BenchResults {
         e2e_proving_time,
@@ -116,7 +106,6 @@ fn main() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
-
 async fn start_primary_server(
     final_snark: bool,
     rust_log: Option<String>,
@@ -128,14 +117,7 @@ async fn start_primary_server(
     let secondary_addr =
         env::var("SECONDARY_ADDR").map_err(|_| anyhow::anyhow!("SECONDARY_ADDR not set"))?;
 
-    let (
-        rust_log,
-        rustflags,
-    ) = get_parameters(
-        final_snark,
-        rust_log,
-        rustflags,
-    );
+    let (rust_log, rustflags) = get_parameters(final_snark, rust_log, rustflags);
 
     let mut args = vec![
         "run",
@@ -163,7 +145,6 @@ async fn start_primary_server(
         .spawn()
         .map_err(|e| anyhow!(e))?;
 
-
     let mut attempts = 0;
 
     loop {
@@ -184,7 +165,6 @@ async fn start_primary_server(
     }
 }
 
-
 async fn start_secondary_server(
     final_snark: bool,
     rust_log: Option<String>,
@@ -194,14 +174,7 @@ async fn start_secondary_server(
     let secondary_addr =
         env::var("SECONDARY_ADDR").map_err(|_| anyhow::anyhow!("SECONDARY_ADDR not set"))?;
 
-    let (
-        rust_log,
-        rustflags,
-    ) = get_parameters(
-        final_snark,
-        rust_log,
-        rustflags,
-    );
+    let (rust_log, rustflags) = get_parameters(final_snark, rust_log, rustflags);
 
     let mut args = vec![
         "run",
@@ -227,7 +200,6 @@ async fn start_secondary_server(
         .spawn()
         .map_err(|e| anyhow!(e))?;
 
-
     let mut attempts = 0;
 
     loop {
@@ -276,7 +248,7 @@ async fn bench_proving_inclusion(final_snark: bool) -> Result<ProofData, anyhow:
         proving_type,
         inclusion_data,
     ))))
-        .map_err(|e| anyhow!(e))?;
+    .map_err(|e| anyhow!(e))?;
 
     // Start measuring proving time
     let start = Instant::now();
@@ -331,7 +303,7 @@ async fn bench_proving_epoch_change(final_snark: bool) -> Result<ProofData, anyh
         proving_type,
         epoch_change_data,
     ))))
-        .map_err(|e| anyhow!(e))?;
+    .map_err(|e| anyhow!(e))?;
 
     // Start measuring proving time
     let start = Instant::now();
@@ -363,12 +335,8 @@ fn get_parameters(
     rust_log: Option<String>,
     rustflags: Option<String>,
 ) -> (String, String) {
-
     let rust_log = rust_log.unwrap_or(DEFAULT_RUST_LOG.to_string());
     let rustflags = rustflags.unwrap_or(DEFAULT_RUSTFLAGS.to_string());
 
-    (
-        rust_log,
-        rustflags,
-    )
+    (rust_log, rustflags)
 }
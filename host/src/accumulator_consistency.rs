@@ -0,0 +1,78 @@
+use crate::error::LightClientError;
+use crate::inclusion::ValidatorVerifierAssets;
+use aptos_guests::ACCUMULATOR_CONSISTENCY_ELF;
+use aptos_lc_core::types::output::AccumulatorConsistencyOutput;
+use risc0_zkvm::{ExecutorEnv, Prover, Receipt};
+
+pub use aptos_lc_core::types::assets::AccumulatorConsistencyProofAssets;
+
+/// Generates the inputs of the accumulator consistency program.
+pub fn generate_stdin<'a>(
+    accumulator_consistency_proof_assets: &'a AccumulatorConsistencyProofAssets,
+    prev_validator_verifier_assets: &'a ValidatorVerifierAssets,
+    validator_verifier_assets: &'a ValidatorVerifierAssets,
+) -> ExecutorEnv<'a> {
+    ExecutorEnv::builder()
+        .write(accumulator_consistency_proof_assets.accumulator_summary())
+        .unwrap()
+        .write(accumulator_consistency_proof_assets.prev_li())
+        .unwrap()
+        .write(accumulator_consistency_proof_assets.consistency_proof())
+        .unwrap()
+        .write(accumulator_consistency_proof_assets.latest_li())
+        .unwrap()
+        .write(prev_validator_verifier_assets.validator_verifier())
+        .unwrap()
+        .write(validator_verifier_assets.validator_verifier())
+        .unwrap()
+        .build()
+        .unwrap()
+}
+
+/// Proves that the transaction accumulator of a signed ledger info extends
+/// the one of a previously signed ledger info.
+///
+/// # Arguments
+///
+/// * `client` - The prover.
+/// * `accumulator_consistency_proof_assets` - The summary of the transaction
+///   accumulator at the previous ledger info, both signed ledger infos and
+///   the consistency proof between them.
+/// * `prev_validator_verifier_assets` - The validator verifier that signed
+///   the previous ledger info.
+/// * `validator_verifier_assets` - The validator verifier that signed the
+///   latest ledger info.
+///
+/// # Returns
+///
+/// The receipt and the `AccumulatorConsistencyOutput` it commits to.
+pub fn prove_accumulator_consistency(
+    client: &dyn Prover,
+    accumulator_consistency_proof_assets: &AccumulatorConsistencyProofAssets,
+    prev_validator_verifier_assets: &ValidatorVerifierAssets,
+    validator_verifier_assets: &ValidatorVerifierAssets,
+) -> Result<(Receipt, AccumulatorConsistencyOutput), LightClientError> {
+    let env = generate_stdin(
+        accumulator_consistency_proof_assets,
+        prev_validator_verifier_assets,
+        validator_verifier_assets,
+    );
+
+    let proof = client
+        .prove(env, ACCUMULATOR_CONSISTENCY_ELF)
+        .map_err(|err| LightClientError::ProvingError {
+            program: "prove-accumulator-consistency".to_string(),
+            source: err.into(),
+        })?;
+
+    // Read output, committed with its stable encoding.
+    let output =
+        AccumulatorConsistencyOutput::from_bytes(&proof.receipt.journal.bytes).map_err(|err| {
+            LightClientError::DecodeError {
+                program: "prove-accumulator-consistency".to_string(),
+                source: err.into(),
+            }
+        })?;
+
+    Ok((proof.receipt, output))
+}
@@ -1,4 +1,6 @@
-use serde::{Deserialize, Serialize};
+use crate::error::ClientError;
+use crate::inclusion::{SparseMerkleProofAssets, TransactionProofAssets, ValidatorVerifierAssets};
+use crate::types::{EpochChangeData, InclusionData};
 use aptos_lc_core::crypto::hash::HashValue;
 use aptos_lc_core::merkle::sparse_proof::SparseMerkleProof;
 use aptos_lc_core::merkle::transaction_proof::TransactionAccumulatorProof;
@@ -6,11 +8,13 @@ use aptos_lc_core::types::ledger_info::LedgerInfoWithSignatures;
 use aptos_lc_core::types::transaction::TransactionInfo;
 use aptos_lc_core::types::trusted_state::{EpochChangeProof, TrustedState};
 use aptos_lc_core::types::validator::ValidatorVerifier;
-use crate::inclusion::{SparseMerkleProofAssets, TransactionProofAssets, ValidatorVerifierAssets};
-use crate::types::{EpochChangeData, InclusionData};
+use aptos_lc_core::types::AccountAddress;
+use reqwest::header::ACCEPT;
+use serde::{Deserialize, Serialize};
 
 /// The role of the Aptos node the client connects to. Can be Validator or Full Node.
 #[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum NodeRole {
     Validator,
     FullNode,
@@ -101,4 +105,321 @@ impl From<AccountInclusionProofResponse> for InclusionData {
             ),
         }
     }
-}
\ No newline at end of file
+}
+/// Asynchronous client for the Aptos node endpoints serving the payloads
+/// needed to generate proofs.
+#[derive(Debug, Clone)]
+pub struct AptosClient {
+    client: reqwest::Client,
+    node_url: String,
+}
+
+impl AptosClient {
+    /// Creates a new `AptosClient`.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_url` - The base URL of the Aptos node, e.g. `http://127.0.0.1:8080`.
+    ///
+    /// # Returns
+    ///
+    /// The `AptosClient`.
+    pub fn new(node_url: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            node_url: node_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Sends a GET request to the given endpoint of the node and returns the
+    /// body of the response.
+    async fn get(&self, endpoint: &str, accept: &str) -> Result<Vec<u8>, ClientError> {
+        let url = format!("{}{}", self.node_url, endpoint);
+
+        let response = self
+            .client
+            .get(&url)
+            .header(ACCEPT, accept)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|err| ClientError::Request {
+                endpoint: url.clone(),
+                source: err.into(),
+            })?;
+
+        let bytes = response.bytes().await.map_err(|err| ClientError::Request {
+            endpoint: url,
+            source: err.into(),
+        })?;
+
+        Ok(bytes.to_vec())
+    }
+
+    /// Fetches the latest ledger information from `/v1/`.
+    ///
+    /// # Returns
+    ///
+    /// The `LedgerInfoResponse` of the node.
+    pub async fn ledger_info(&self) -> Result<LedgerInfoResponse, ClientError> {
+        let endpoint = "/v1/";
+        let bytes = self.get(endpoint, "application/json").await?;
+
+        serde_json::from_slice(&bytes).map_err(|err| ClientError::ResponsePayload {
+            endpoint: endpoint.to_string(),
+            source: err.into(),
+        })
+    }
+
+    /// Fetches the proof of the epoch changes starting at `epoch` from
+    /// `/v1/epoch/proof`.
+    ///
+    /// # Arguments
+    ///
+    /// * `epoch` - The epoch to start the proof from.
+    ///
+    /// # Returns
+    ///
+    /// The `EpochChangeProofResponse` of the node.
+    pub async fn epoch_change_proof(
+        &self,
+        epoch: u64,
+    ) -> Result<EpochChangeProofResponse, ClientError> {
+        let endpoint = format!("/v1/epoch/proof?epoch_number={epoch}");
+        let bytes = self.get(&endpoint, "application/x-bcs").await?;
+
+        bcs::from_bytes(&bytes).map_err(|err| ClientError::ResponsePayload {
+            endpoint,
+            source: err.into(),
+        })
+    }
+
+    /// Fetches the proof of the epoch changes starting at `epoch` and
+    /// converts it into an `EpochChangeData`.
+    ///
+    /// # Arguments
+    ///
+    /// * `epoch` - The epoch to start the proof from.
+    ///
+    /// # Returns
+    ///
+    /// The `EpochChangeData` to prove the epoch changes with.
+    pub async fn epoch_change_data(&self, epoch: u64) -> Result<EpochChangeData, ClientError> {
+        Ok(self.epoch_change_proof(epoch).await?.into())
+    }
+
+    /// Fetches the proof of inclusion of an account from
+    /// `/v1/accounts/:address/proof`.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The address of the account.
+    ///
+    /// # Returns
+    ///
+    /// The `AccountInclusionProofResponse` of the node.
+    pub async fn account_inclusion_proof(
+        &self,
+        address: &AccountAddress,
+    ) -> Result<AccountInclusionProofResponse, ClientError> {
+        let endpoint = format!("/v1/accounts/0x{}/proof", hex::encode(address.to_bytes()));
+        let bytes = self.get(&endpoint, "application/x-bcs").await?;
+
+        bcs::from_bytes(&bytes).map_err(|err| ClientError::ResponsePayload {
+            endpoint,
+            source: err.into(),
+        })
+    }
+
+    /// Fetches the proof of inclusion of an account and converts it into an
+    /// `InclusionData`.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The address of the account.
+    ///
+    /// # Returns
+    ///
+    /// The `InclusionData` to prove the inclusion of the account with.
+    pub async fn inclusion_data(
+        &self,
+        address: &AccountAddress,
+    ) -> Result<InclusionData, ClientError> {
+        Ok(self.account_inclusion_proof(address).await?.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::aptos::{AccountInclusionProofResponse, AptosClient, EpochChangeProofResponse};
+    use crate::error::ClientError;
+    use aptos_lc_core::aptos_test_utils::wrapper::AptosWrapper;
+    use aptos_lc_core::crypto::hash::HashValue;
+    use aptos_lc_core::merkle::sparse_proof::SparseMerkleProof;
+    use aptos_lc_core::merkle::transaction_proof::TransactionAccumulatorProof;
+    use aptos_lc_core::types::ledger_info::LedgerInfoWithSignatures;
+    use aptos_lc_core::types::transaction::TransactionInfo;
+    use aptos_lc_core::types::trusted_state::{EpochChangeProof, TrustedState};
+    use aptos_lc_core::types::validator::ValidatorVerifier;
+    use aptos_lc_core::types::AccountAddress;
+    use axum::extract::{Path, Query, State};
+    use axum::http::header::CONTENT_TYPE;
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+    use axum::routing::get;
+    use axum::Router;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use tokio::net::TcpListener;
+
+    /// Payload of the `/v1/` endpoint, as answered by an Aptos node.
+    const LEDGER_INFO: &str = r#"{"chain_id":4,"epoch":"1","ledger_version":"10","oldest_ledger_version":"0","ledger_timestamp":"1718028413354936","node_role":"validator","oldest_block_height":"0","block_height":"1","git_hash":"6d4b2ac0fd1b4fd8a3d4f3d8e7c5f3b1a2d0e9c4"}"#;
+
+    struct MockNode {
+        epoch_change_proof: Vec<u8>,
+        account_address: String,
+        account_inclusion_proof: Vec<u8>,
+    }
+
+    fn mock_node() -> MockNode {
+        let mut aptos_wrapper = AptosWrapper::new(4, 3, 3).unwrap();
+        let trusted_state: TrustedState =
+            bcs::from_bytes(&bcs::to_bytes(aptos_wrapper.trusted_state()).unwrap()).unwrap();
+        let validator_verifier = match &trusted_state {
+            TrustedState::EpochState { epoch_state, .. } => epoch_state.verifier().clone(),
+            _ => panic!("Expected epoch state"),
+        };
+
+        aptos_wrapper.generate_traffic().unwrap();
+
+        let proof_assets = aptos_wrapper.get_latest_proof_account(1).unwrap();
+        let account_inclusion_proof = AccountInclusionProofResponse {
+            state_proof: SparseMerkleProof::from_bytes(
+                &bcs::to_bytes(proof_assets.state_proof()).unwrap(),
+            )
+            .unwrap(),
+            element_key: HashValue::from_slice(proof_assets.key().to_vec()).unwrap(),
+            element_hash: HashValue::from_slice(proof_assets.state_value_hash().unwrap().to_vec())
+                .unwrap(),
+            transaction_proof: TransactionAccumulatorProof::from_bytes(
+                &bcs::to_bytes(proof_assets.transaction_proof()).unwrap(),
+            )
+            .unwrap(),
+            transaction: TransactionInfo::from_bytes(
+                &bcs::to_bytes(proof_assets.transaction()).unwrap(),
+            )
+            .unwrap(),
+            transaction_index: *proof_assets.transaction_version(),
+            ledger_info_v0: LedgerInfoWithSignatures::from_bytes(
+                &aptos_wrapper.get_latest_li_bytes().unwrap(),
+            )
+            .unwrap(),
+            validator_verifier: ValidatorVerifier::from_bytes(&validator_verifier.to_bytes())
+                .unwrap(),
+        };
+        let account_address = format!(
+            "0x{}",
+            hex::encode(aptos_wrapper.accounts()[1].address().to_vec())
+        );
+
+        let state_proof = aptos_wrapper
+            .new_state_proof(trusted_state.version())
+            .unwrap();
+        let epoch_change_proof = EpochChangeProofResponse {
+            epoch_change_proof: EpochChangeProof::from_bytes(
+                &bcs::to_bytes(state_proof.epoch_changes()).unwrap(),
+            )
+            .unwrap(),
+            trusted_state,
+        };
+
+        MockNode {
+            epoch_change_proof: bcs::to_bytes(&epoch_change_proof).unwrap(),
+            account_address,
+            account_inclusion_proof: bcs::to_bytes(&account_inclusion_proof).unwrap(),
+        }
+    }
+
+    async fn mock_ledger_info() -> impl IntoResponse {
+        ([(CONTENT_TYPE, "application/json")], LEDGER_INFO)
+    }
+
+    async fn mock_epoch_change_proof(
+        State(mock_node): State<Arc<MockNode>>,
+        Query(query): Query<HashMap<String, String>>,
+    ) -> Result<Vec<u8>, StatusCode> {
+        match query.get("epoch_number").map(String::as_str) {
+            Some("1") => Ok(mock_node.epoch_change_proof.clone()),
+            _ => Err(StatusCode::NOT_FOUND),
+        }
+    }
+
+    async fn mock_account_inclusion_proof(
+        State(mock_node): State<Arc<MockNode>>,
+        Path(address): Path<String>,
+    ) -> Result<Vec<u8>, StatusCode> {
+        if address == mock_node.account_address {
+            Ok(mock_node.account_inclusion_proof.clone())
+        } else if address == format!("0x{}", hex::encode([1; 32])) {
+            // Malformed payload
+            Ok(vec![1, 2, 3])
+        } else {
+            Err(StatusCode::NOT_FOUND)
+        }
+    }
+
+    /// Spawns a local HTTP server mocking the endpoints of an Aptos node and
+    /// returns its URL.
+    async fn spawn_mock_node(mock_node: MockNode) -> String {
+        let app = Router::new()
+            .route("/v1/", get(mock_ledger_info))
+            .route("/v1/epoch/proof", get(mock_epoch_change_proof))
+            .route(
+                "/v1/accounts/:address/proof",
+                get(mock_account_inclusion_proof),
+            )
+            .with_state(Arc::new(mock_node));
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        format!("http://{addr}")
+    }
+
+    #[tokio::test]
+    async fn test_aptos_client() {
+        let mock_node = mock_node();
+        let account_address = AccountAddress::from_bytes(
+            &hex::decode(mock_node.account_address.trim_start_matches("0x")).unwrap(),
+        )
+        .unwrap();
+        let client = AptosClient::new(spawn_mock_node(mock_node).await);
+
+        let ledger_info = client.ledger_info().await.unwrap();
+        assert_eq!(ledger_info.epoch(), "1");
+
+        let epoch_change_data = client.epoch_change_data(1).await.unwrap();
+        assert!(TrustedState::from_bytes(&epoch_change_data.trusted_state).is_ok());
+        assert!(EpochChangeProof::from_bytes(&epoch_change_data.epoch_change_proof).is_ok());
+
+        let inclusion_data = client.inclusion_data(&account_address).await.unwrap();
+        assert!(crate::native::verify_inclusion(&inclusion_data).is_ok());
+
+        // Errors of the node are surfaced as request errors
+        assert!(matches!(
+            client.epoch_change_data(2).await,
+            Err(ClientError::Request { .. })
+        ));
+        assert!(matches!(
+            client.inclusion_data(&AccountAddress::new([2; 32])).await,
+            Err(ClientError::Request { .. })
+        ));
+
+        // Malformed payloads are surfaced as payload errors
+        assert!(matches!(
+            client.inclusion_data(&AccountAddress::new([1; 32])).await,
+            Err(ClientError::ResponsePayload { .. })
+        ));
+    }
+}
@@ -0,0 +1,492 @@
+//! Content-addressed cache of the receipts generated by the proof server.
+//!
+//! Receipts are keyed on the hash of the image ID of the program, the
+//! `ProvingMode` and the inputs of the proof, and stored in a directory, one
+//! `<key>.receipt` file per receipt. The cache is bounded in size, evicting
+//! the oldest receipts first, and its entries expire after a time to live.
+//! Concurrent requests for the same key are coalesced, so that the proof is
+//! only generated once.
+
+use crate::error::ApiError;
+use aptos_guests::{EPOCH_CHANGE_ID, INCLUSION_ID};
+use aptos_lc_core::crypto::hash::{hash_data, HASH_LENGTH};
+use host::error::ErrorResponse;
+use host::types::Request;
+use risc0_zkvm::Receipt;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::future::Future;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tokio::sync::OnceCell;
+use tokio::task::spawn_blocking;
+use tracing::warn;
+
+const RECEIPT_EXTENSION: &str = "receipt";
+
+/// Domain separation tag of the cache keys.
+const CACHE_KEY_TAG: &[u8] = b"PROOF_SERVER_RECEIPT_CACHE";
+
+/// Name of the response header telling whether a receipt came from the
+/// cache.
+pub const CACHE_STATUS_HEADER: &str = "x-cache";
+
+/// Key of a receipt in the cache.
+pub type CacheKey = [u8; HASH_LENGTH];
+
+/// How a receipt was obtained from the cache.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CacheStatus {
+    /// The receipt was already cached.
+    Hit,
+    /// The receipt was generated for a concurrent identical request.
+    Coalesced,
+    /// The receipt was generated for this request.
+    Miss,
+}
+
+impl CacheStatus {
+    /// Returns the value of the [`CACHE_STATUS_HEADER`] header.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            CacheStatus::Hit => "hit",
+            CacheStatus::Coalesced => "coalesced",
+            CacheStatus::Miss => "miss",
+        }
+    }
+}
+
+struct Entry {
+    size: u64,
+    created: SystemTime,
+}
+
+/// The result of a proof shared by the coalesced requests.
+type InFlight = Arc<OnceCell<Result<Receipt, ErrorResponse>>>;
+
+/// A size and time bounded cache of receipts, stored on disk.
+pub struct ReceiptCache {
+    dir: PathBuf,
+    max_size: u64,
+    ttl: Duration,
+    /// Only held to update the entries, the receipt files are accessed
+    /// once it is released.
+    entries: Mutex<HashMap<CacheKey, Entry>>,
+    in_flight: Mutex<HashMap<CacheKey, InFlight>>,
+}
+
+impl ReceiptCache {
+    /// Creates a new `ReceiptCache`, loading the receipts previously
+    /// stored in `dir`.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory the receipts are stored in.
+    /// * `max_size` - The maximum total size of the stored receipts, in bytes. 0 disables storing receipts.
+    /// * `ttl` - The time after which a stored receipt expires.
+    ///
+    /// # Returns
+    ///
+    /// The `ReceiptCache`.
+    pub fn new(dir: impl AsRef<Path>, max_size: u64, ttl: Duration) -> std::io::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+
+        let mut entries = HashMap::new();
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(RECEIPT_EXTENSION) {
+                continue;
+            }
+            let Some(key) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| hex::decode(stem).ok())
+                .and_then(|key| CacheKey::try_from(key).ok())
+            else {
+                continue;
+            };
+            let metadata = entry.metadata()?;
+            entries.insert(
+                key,
+                Entry {
+                    size: metadata.len(),
+                    created: metadata.modified()?,
+                },
+            );
+        }
+
+        let cache = Self {
+            dir,
+            max_size,
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+            in_flight: Mutex::new(HashMap::new()),
+        };
+        for key in cache.evict(&mut entries) {
+            let _ = fs::remove_file(cache.receipt_path(&key));
+        }
+        *cache.entries.lock().unwrap() = entries;
+
+        Ok(cache)
+    }
+
+    /// Returns the cached receipt of a key, or generates it with `prove`.
+    /// While a receipt is being generated, the requests for the same key
+    /// wait for it instead of generating it again.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the receipt, see [`cache_key`].
+    /// * `prove` - The future generating the receipt.
+    ///
+    /// # Returns
+    ///
+    /// The `Receipt` and how it was obtained.
+    pub async fn get_or_prove(
+        &self,
+        key: CacheKey,
+        prove: impl Future<Output = Result<Receipt, ApiError>>,
+    ) -> Result<(Receipt, CacheStatus), ApiError> {
+        if let Some(receipt) = self.get(&key).await {
+            return Ok((receipt, CacheStatus::Hit));
+        }
+
+        let cell = self
+            .in_flight
+            .lock()
+            .unwrap()
+            .entry(key)
+            .or_default()
+            .clone();
+        let mut status = CacheStatus::Coalesced;
+        let status_ref = &mut status;
+        let res = cell
+            .get_or_init(|| async move {
+                // The receipt may have been stored since it was looked up
+                if let Some(receipt) = self.get(&key).await {
+                    *status_ref = CacheStatus::Hit;
+                    return Ok(receipt);
+                }
+
+                *status_ref = CacheStatus::Miss;
+                let receipt = prove.await.map_err(ApiError::into_body)?;
+                self.insert(&key, &receipt).await;
+
+                Ok(receipt)
+            })
+            .await
+            .clone();
+
+        {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            if in_flight
+                .get(&key)
+                .is_some_and(|current| Arc::ptr_eq(current, &cell))
+            {
+                in_flight.remove(&key);
+            }
+        }
+
+        res.map(|receipt| (receipt, status))
+            .map_err(ApiError::from_response)
+    }
+
+    /// Returns the stored receipt of a key, if it has not expired.
+    async fn get(&self, key: &CacheKey) -> Option<Receipt> {
+        let expired = {
+            let mut entries = self.entries.lock().unwrap();
+            let expired = self.is_expired(entries.get(key)?);
+            if expired {
+                entries.remove(key);
+            }
+            expired
+        };
+        if expired {
+            self.remove_files(vec![*key]).await;
+            return None;
+        }
+
+        let path = self.receipt_path(key);
+        let read_path = path.clone();
+        let res = spawn_blocking(move || -> anyhow::Result<Receipt> {
+            Ok(bcs::from_bytes(&fs::read(read_path)?)?)
+        })
+        .await
+        .map_err(anyhow::Error::from)
+        .and_then(|res| res);
+
+        match res {
+            Ok(receipt) => Some(receipt),
+            Err(err) => {
+                warn!("Failed to read cached receipt {}: {err}", path.display());
+                self.entries.lock().unwrap().remove(key);
+                self.remove_files(vec![*key]).await;
+                None
+            }
+        }
+    }
+
+    /// Stores a receipt, evicting the oldest receipts if the cache grows
+    /// over its maximum size. Failures are only logged, as the receipt can
+    /// still be served.
+    async fn insert(&self, key: &CacheKey, receipt: &Receipt) {
+        if let Err(err) = self.try_insert(key, receipt).await {
+            warn!("Failed to cache receipt {}: {err}", hex::encode(key));
+        }
+    }
+
+    async fn try_insert(&self, key: &CacheKey, receipt: &Receipt) -> anyhow::Result<()> {
+        let bytes = bcs::to_bytes(receipt)?;
+        let size = bytes.len() as u64;
+        if size > self.max_size {
+            return Ok(());
+        }
+
+        let dir = self.dir.clone();
+        let path = self.receipt_path(key);
+        spawn_blocking(move || write_receipt(&dir, &path, &bytes)).await??;
+
+        let evicted = {
+            let mut entries = self.entries.lock().unwrap();
+            entries.insert(
+                *key,
+                Entry {
+                    size,
+                    created: SystemTime::now(),
+                },
+            );
+            self.evict(&mut entries)
+        };
+        self.remove_files(evicted).await;
+
+        Ok(())
+    }
+
+    /// Removes the expired entries, then the oldest ones until the cache
+    /// fits in its maximum size.
+    ///
+    /// # Returns
+    ///
+    /// The keys of the removed entries, whose files are left to delete.
+    fn evict(&self, entries: &mut HashMap<CacheKey, Entry>) -> Vec<CacheKey> {
+        let mut evicted = entries
+            .iter()
+            .filter(|(_, entry)| self.is_expired(entry))
+            .map(|(key, _)| *key)
+            .collect::<Vec<_>>();
+        for key in &evicted {
+            entries.remove(key);
+        }
+
+        let mut size = entries.values().map(|entry| entry.size).sum::<u64>();
+        if size <= self.max_size {
+            return evicted;
+        }
+        let mut by_age = entries
+            .iter()
+            .map(|(key, entry)| (entry.created, *key))
+            .collect::<Vec<_>>();
+        by_age.sort();
+        for (_, key) in by_age {
+            if size <= self.max_size {
+                break;
+            }
+            size -= entries[&key].size;
+            entries.remove(&key);
+            evicted.push(key);
+        }
+
+        evicted
+    }
+
+    async fn remove_files(&self, keys: Vec<CacheKey>) {
+        for key in keys {
+            let _ = tokio::fs::remove_file(self.receipt_path(&key)).await;
+        }
+    }
+
+    fn is_expired(&self, entry: &Entry) -> bool {
+        entry
+            .created
+            .elapsed()
+            .is_ok_and(|elapsed| elapsed > self.ttl)
+    }
+
+    fn receipt_path(&self, key: &CacheKey) -> PathBuf {
+        self.dir
+            .join(format!("{}.{RECEIPT_EXTENSION}", hex::encode(key)))
+    }
+}
+
+/// Writes a receipt to a temporary file which is synced then renamed, so
+/// that a crash never leaves a partial receipt behind.
+fn write_receipt(dir: &Path, path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let tmp_path = path.with_extension("tmp");
+    let mut tmp_file = File::create(&tmp_path)?;
+    tmp_file.write_all(bytes)?;
+    tmp_file.sync_all()?;
+    fs::rename(&tmp_path, path)?;
+
+    // Persist the rename itself
+    File::open(dir).and_then(|dir| dir.sync_all())
+}
+
+/// Returns the cache key of a proving request: the hash of the image ID of
+/// its program, its `ProvingMode` and the BCS encoding of its inputs.
+///
+/// # Arguments
+///
+/// * `request` - The proving request.
+///
+/// # Returns
+///
+/// The `CacheKey`, or `None` if the request is not a proving request.
+pub fn cache_key(request: &Request) -> Option<CacheKey> {
+    let (image_id, proving_mode, inputs) = match request {
+        Request::ProveInclusion(boxed) => (INCLUSION_ID, boxed.0, bcs::to_bytes(&boxed.1).ok()?),
+        Request::ProveEpochChange(boxed) => {
+            (EPOCH_CHANGE_ID, boxed.0, bcs::to_bytes(&boxed.1).ok()?)
+        }
+        _ => return None,
+    };
+    let image_id = image_id
+        .iter()
+        .flat_map(|word| word.to_le_bytes())
+        .collect::<Vec<_>>();
+
+    Some(hash_data(
+        CACHE_KEY_TAG,
+        vec![
+            image_id.as_slice(),
+            &[proving_mode.to_bytes()][..],
+            inputs.as_slice(),
+        ],
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{cache_key, CacheStatus, ReceiptCache};
+    use crate::error::ApiError;
+    use aptos_guests::EPOCH_CHANGE_ID;
+    use host::error::ErrorCode;
+    use host::types::{EpochChangeData, EpochChangeExpectations, ProvingMode, Request};
+    use risc0_zkvm::{FakeReceipt, InnerReceipt, Receipt, ReceiptClaim};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    fn fake_receipt(journal: Vec<u8>) -> Receipt {
+        Receipt::new(
+            InnerReceipt::Fake(FakeReceipt::new(ReceiptClaim::ok(
+                EPOCH_CHANGE_ID,
+                journal.clone(),
+            ))),
+            journal,
+        )
+    }
+
+    fn request(proving_mode: ProvingMode, trusted_state: Vec<u8>) -> Request {
+        Request::ProveEpochChange(Box::new((
+            proving_mode,
+            EpochChangeData {
+                trusted_state,
+                epoch_change_proof: vec![],
+            },
+        )))
+    }
+
+    #[test]
+    fn test_cache_key() {
+        let key = cache_key(&request(ProvingMode::STARK, vec![1])).unwrap();
+        assert_eq!(cache_key(&request(ProvingMode::STARK, vec![1])), Some(key));
+        assert_ne!(cache_key(&request(ProvingMode::SNARK, vec![1])), Some(key));
+        assert_ne!(cache_key(&request(ProvingMode::STARK, vec![2])), Some(key));
+        assert_eq!(
+            cache_key(&Request::VerifyEpochChange(Box::new((
+                fake_receipt(vec![]),
+                EpochChangeExpectations::default()
+            )))),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_receipt_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ReceiptCache::new(dir.path(), 1 << 20, Duration::from_secs(60)).unwrap();
+        let key = cache_key(&request(ProvingMode::STARK, vec![1])).unwrap();
+        let proofs = AtomicUsize::new(0);
+        let prove = |journal: Vec<u8>| {
+            let proofs = &proofs;
+            async move {
+                proofs.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                Ok(fake_receipt(journal))
+            }
+        };
+
+        // Concurrent identical requests are proven once
+        let (first, second) = tokio::join!(
+            cache.get_or_prove(key, prove(vec![1])),
+            cache.get_or_prove(key, prove(vec![1])),
+        );
+        let mut statuses = [first.unwrap().1, second.unwrap().1];
+        statuses.sort_by_key(|status| status.as_str());
+        assert_eq!(statuses, [CacheStatus::Coalesced, CacheStatus::Miss]);
+        assert_eq!(proofs.load(Ordering::SeqCst), 1);
+
+        let (receipt, status) = cache.get_or_prove(key, prove(vec![1])).await.unwrap();
+        assert_eq!(status, CacheStatus::Hit);
+        assert_eq!(receipt.journal.bytes, vec![1]);
+        assert_eq!(proofs.load(Ordering::SeqCst), 1);
+
+        // Failures are not cached
+        let other_key = cache_key(&request(ProvingMode::STARK, vec![2])).unwrap();
+        let err = cache
+            .get_or_prove(other_key, async {
+                Err(ApiError::new(ErrorCode::ProverFailed, "Prover crashed"))
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err.into_body().code, ErrorCode::ProverFailed);
+        let (_, status) = cache.get_or_prove(other_key, prove(vec![2])).await.unwrap();
+        assert_eq!(status, CacheStatus::Miss);
+        drop(cache);
+
+        // Receipts survive a restart
+        let cache = ReceiptCache::new(dir.path(), 1 << 20, Duration::from_secs(60)).unwrap();
+        let (_, status) = cache.get_or_prove(key, prove(vec![1])).await.unwrap();
+        assert_eq!(status, CacheStatus::Hit);
+    }
+
+    #[tokio::test]
+    async fn test_receipt_cache_bounds() {
+        let dir = tempfile::tempdir().unwrap();
+        let size = bcs::to_bytes(&fake_receipt(vec![1])).unwrap().len() as u64;
+        let keys = [vec![1], vec![2]]
+            .map(|trusted_state| cache_key(&request(ProvingMode::STARK, trusted_state)).unwrap());
+
+        // Only one receipt fits, so the oldest one is evicted
+        let cache = ReceiptCache::new(dir.path(), size, Duration::from_secs(60)).unwrap();
+        for (key, journal) in keys.iter().zip([vec![1], vec![2]]) {
+            cache
+                .get_or_prove(*key, async move { Ok(fake_receipt(journal)) })
+                .await
+                .unwrap();
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(cache.get(&keys[0]).await.is_none());
+        assert!(cache.get(&keys[1]).await.is_some());
+
+        // Receipts expire after the time to live
+        let cache = ReceiptCache::new(dir.path(), size, Duration::from_millis(500)).unwrap();
+        assert!(cache.get(&keys[1]).await.is_some());
+        tokio::time::sleep(Duration::from_millis(600)).await;
+        assert!(cache.get(&keys[1]).await.is_none());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+}
@@ -0,0 +1,245 @@
+//! Offloading of proofs to a cluster of proof servers.
+//!
+//! In split mode the server does not prove requests itself but forwards
+//! them to one of its backends, which are proof servers running in single
+//! mode. Backends are picked by load: the ones reporting a free worker on
+//! `/ready` are preferred, and among them the ones with the fewest requests
+//! forwarded by this server. Backends are health-checked in the background
+//! and failed requests are retried on another backend.
+
+use crate::error::ApiError;
+use host::error::{ErrorCode, ErrorResponse};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Timeout of the `/health` and `/ready` requests sent to the backends.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A backend proof server.
+struct Backend {
+    addr: String,
+    healthy: AtomicBool,
+    in_flight: AtomicUsize,
+}
+
+/// Decrements the number of in-flight requests of a backend when dropped,
+/// so that it stays accurate if the forwarding future is cancelled.
+struct InFlight<'a>(&'a AtomicUsize);
+
+impl<'a> InFlight<'a> {
+    fn new(counter: &'a AtomicUsize) -> Self {
+        counter.fetch_add(1, Ordering::SeqCst);
+        Self(counter)
+    }
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// A cluster of backend proof servers.
+pub struct Cluster {
+    client: reqwest::Client,
+    backends: Vec<Backend>,
+    retries: usize,
+    /// Held while a backend is picked and its in-flight count increased,
+    /// so that concurrent requests see each other.
+    selection: Mutex<()>,
+}
+
+impl Cluster {
+    /// Creates a new `Cluster`. All backends are considered healthy until
+    /// they are checked.
+    ///
+    /// # Arguments
+    ///
+    /// * `addrs` - The addresses of the backends. E.g. 127.0.0.1:4321
+    /// * `retries` - The number of times a failed request is retried.
+    ///
+    /// # Returns
+    ///
+    /// The `Cluster`.
+    pub fn new(addrs: Vec<String>, retries: usize) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            backends: addrs
+                .into_iter()
+                .map(|addr| Backend {
+                    addr,
+                    healthy: AtomicBool::new(true),
+                    in_flight: AtomicUsize::new(0),
+                })
+                .collect(),
+            retries,
+            selection: Mutex::new(()),
+        }
+    }
+
+    /// Spawns a task checking the health of the backends every `interval`.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - The interval between two health checks.
+    pub fn spawn_health_checks(self: &Arc<Self>, interval: Duration) {
+        let cluster = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(interval);
+            loop {
+                interval.tick().await;
+                cluster.check_health().await;
+            }
+        });
+    }
+
+    /// Returns the number of backends that passed their last health check.
+    pub fn healthy_backends(&self) -> usize {
+        self.backends
+            .iter()
+            .filter(|backend| backend.healthy.load(Ordering::SeqCst))
+            .count()
+    }
+
+    /// Forwards a request to a backend, retrying on another backend if it
+    /// fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the endpoint of the backend. E.g. /epoch/proof
+    /// * `body` - The body of the request.
+    ///
+    /// # Returns
+    ///
+    /// The body of the response of the backend.
+    pub async fn forward(&self, path: &str, body: Vec<u8>) -> Result<Vec<u8>, ApiError> {
+        let mut tried = vec![];
+        let mut last_error = None;
+
+        for _ in 0..=self.retries {
+            if tried.len() == self.backends.len() {
+                tried.clear();
+            }
+            let Some((index, in_flight)) = self.select(&tried).await else {
+                break;
+            };
+            tried.push(index);
+
+            let backend = &self.backends[index];
+            info!("Forwarding request to {}{}", backend.addr, path);
+            let res = {
+                let _in_flight = in_flight;
+                self.client
+                    .post(format!("http://{}{}", backend.addr, path))
+                    .body(body.clone())
+                    .send()
+                    .await
+            };
+
+            match res {
+                Ok(response) if response.status().is_success() => match response.bytes().await {
+                    Ok(bytes) => return Ok(bytes.to_vec()),
+                    Err(err) => warn!("Failed to receive response from {}: {err}", backend.addr),
+                },
+                // The request itself is invalid, retrying would not help
+                Ok(response) if response.status().is_client_error() => {
+                    return Err(backend_error(response).await);
+                }
+                Ok(response) => {
+                    warn!("Backend {} answered {}", backend.addr, response.status());
+                    last_error = Some(backend_error(response).await);
+                }
+                Err(err) => {
+                    warn!("Failed to send request to {}: {err}", backend.addr);
+                    backend.healthy.store(false, Ordering::SeqCst);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            ApiError::new(
+                ErrorCode::BackendUnavailable,
+                "No backend could handle the request",
+            )
+        }))
+    }
+
+    /// Selects the backend to forward a request to, skipping the backends
+    /// in `excluded`. The request is counted in flight on the backend as
+    /// soon as it is selected.
+    async fn select(&self, excluded: &[usize]) -> Option<(usize, InFlight<'_>)> {
+        let available = |index: &usize| !excluded.contains(index);
+        let mut candidates = (0..self.backends.len())
+            .filter(available)
+            .filter(|index| self.backends[*index].healthy.load(Ordering::SeqCst))
+            .collect::<Vec<_>>();
+        // The health checks may be outdated, so unhealthy backends are
+        // still better than failing right away
+        if candidates.is_empty() {
+            candidates = (0..self.backends.len()).filter(available).collect();
+        }
+
+        let mut busy = vec![];
+        while let Some((index, in_flight)) = self.reserve(&candidates, &busy) {
+            if self.check(index, "/ready").await {
+                return Some((index, in_flight));
+            }
+            busy.push(index);
+        }
+
+        // All backends are busy, queue on the least loaded one
+        self.reserve(&candidates, &[])
+    }
+
+    /// Counts a request in flight on the least loaded backend among
+    /// `candidates`, skipping the ones in `excluded`.
+    fn reserve(&self, candidates: &[usize], excluded: &[usize]) -> Option<(usize, InFlight<'_>)> {
+        let _selection = self.selection.lock().unwrap();
+        let index = candidates
+            .iter()
+            .copied()
+            .filter(|index| !excluded.contains(index))
+            .min_by_key(|index| self.backends[*index].in_flight.load(Ordering::SeqCst))?;
+
+        Some((index, InFlight::new(&self.backends[index].in_flight)))
+    }
+
+    async fn check_health(&self) {
+        for (index, backend) in self.backends.iter().enumerate() {
+            let healthy = self.check(index, "/health").await;
+            if backend.healthy.swap(healthy, Ordering::SeqCst) != healthy {
+                if healthy {
+                    info!("Backend {} is healthy", backend.addr);
+                } else {
+                    warn!("Backend {} is unhealthy", backend.addr);
+                }
+            }
+        }
+    }
+
+    /// Returns whether a GET request to `path` on a backend succeeds.
+    async fn check(&self, index: usize, path: &str) -> bool {
+        self.client
+            .get(format!("http://{}{}", self.backends[index].addr, path))
+            .timeout(CHECK_TIMEOUT)
+            .send()
+            .await
+            .is_ok_and(|response| response.status().is_success())
+    }
+}
+
+/// Reads the error body of a response of a backend.
+async fn backend_error(response: reqwest::Response) -> ApiError {
+    let status = response.status();
+    response
+        .bytes()
+        .await
+        .ok()
+        .and_then(|bytes| bcs::from_bytes::<ErrorResponse>(&bytes).ok())
+        .map_or_else(
+            || ApiError::new(ErrorCode::Internal, format!("Backend answered {status}")),
+            ApiError::from_response,
+        )
+}
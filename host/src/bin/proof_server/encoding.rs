@@ -0,0 +1,208 @@
+//! Content negotiation between the BCS and JSON encodings of the API.
+//!
+//! Requests are decoded according to their `Content-Type` header and
+//! responses are encoded according to the `Accept` header, falling back to
+//! the encoding of the request. BCS is used when neither header asks for
+//! JSON, see [`host::json`] for the JSON representations.
+
+use axum::body::Body;
+use axum::http::header::{ACCEPT, CONTENT_TYPE};
+use axum::http::{HeaderMap, Response, StatusCode};
+use host::json::{JsonReceipt, JsonRequest, BCS_CONTENT_TYPE, JSON_CONTENT_TYPE};
+use host::types::Request;
+use risc0_zkvm::Receipt;
+use serde::Serialize;
+
+/// Encoding of a request or response body.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Encoding {
+    Bcs,
+    Json,
+}
+
+impl Encoding {
+    fn from_header(
+        headers: &HeaderMap,
+        name: impl axum::http::header::AsHeaderName,
+    ) -> Option<Self> {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| {
+                if value.contains(JSON_CONTENT_TYPE) {
+                    Encoding::Json
+                } else {
+                    Encoding::Bcs
+                }
+            })
+    }
+
+    /// Returns the encoding of the body of a request.
+    ///
+    /// # Arguments
+    ///
+    /// * `headers` - The headers of the request.
+    ///
+    /// # Returns
+    ///
+    /// The `Encoding` of the body.
+    pub fn of_request(headers: &HeaderMap) -> Self {
+        Self::from_header(headers, CONTENT_TYPE).unwrap_or(Encoding::Bcs)
+    }
+
+    /// Returns the encoding expected by the client for the response.
+    ///
+    /// # Arguments
+    ///
+    /// * `headers` - The headers of the request.
+    ///
+    /// # Returns
+    ///
+    /// The `Encoding` of the response.
+    pub fn of_response(headers: &HeaderMap) -> Self {
+        match Self::from_header(headers, ACCEPT) {
+            Some(Encoding::Json) => Encoding::Json,
+            _ => Self::of_request(headers),
+        }
+    }
+
+    /// Returns the content type of the encoding.
+    pub const fn content_type(self) -> &'static str {
+        match self {
+            Encoding::Bcs => BCS_CONTENT_TYPE,
+            Encoding::Json => JSON_CONTENT_TYPE,
+        }
+    }
+
+    /// Decodes a request.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The body of the request.
+    ///
+    /// # Returns
+    ///
+    /// The decoded `Request`.
+    pub fn decode_request(self, bytes: &[u8]) -> anyhow::Result<Request> {
+        match self {
+            Encoding::Bcs => Ok(bcs::from_bytes(bytes)?),
+            Encoding::Json => Request::try_from(serde_json::from_slice::<JsonRequest>(bytes)?),
+        }
+    }
+
+    /// Encodes a value whose JSON representation is its serde one.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to encode.
+    ///
+    /// # Returns
+    ///
+    /// The encoded value.
+    pub fn encode<T: Serialize>(self, value: &T) -> anyhow::Result<Vec<u8>> {
+        match self {
+            Encoding::Bcs => Ok(bcs::to_bytes(value)?),
+            Encoding::Json => Ok(serde_json::to_vec(value)?),
+        }
+    }
+
+    /// Encodes a value, as its `J` representation for JSON.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to encode.
+    ///
+    /// # Returns
+    ///
+    /// The encoded value.
+    pub fn encode_as<T, J>(self, value: &T) -> anyhow::Result<Vec<u8>>
+    where
+        T: Serialize,
+        J: Serialize + for<'a> From<&'a T>,
+    {
+        match self {
+            Encoding::Bcs => Ok(bcs::to_bytes(value)?),
+            Encoding::Json => Ok(serde_json::to_vec(&J::from(value))?),
+        }
+    }
+
+    /// Encodes a receipt, as a [`JsonReceipt`] for JSON.
+    ///
+    /// # Arguments
+    ///
+    /// * `receipt` - The receipt to encode.
+    ///
+    /// # Returns
+    ///
+    /// The encoded receipt.
+    pub fn encode_receipt(self, receipt: &Receipt) -> anyhow::Result<Vec<u8>> {
+        match self {
+            Encoding::Bcs => Ok(bcs::to_bytes(receipt)?),
+            Encoding::Json => Ok(serde_json::to_vec(&JsonReceipt::try_from(receipt)?)?),
+        }
+    }
+
+    /// Builds a successful response carrying an encoded body.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The encoded body.
+    ///
+    /// # Returns
+    ///
+    /// The response.
+    pub fn response(self, body: Vec<u8>) -> Result<Response<Body>, axum::http::Error> {
+        Response::builder()
+            .status(StatusCode::OK)
+            .header(CONTENT_TYPE, self.content_type())
+            .body(Body::from(body))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Encoding;
+    use axum::http::header::{ACCEPT, CONTENT_TYPE};
+    use axum::http::HeaderMap;
+    use host::json::JsonRequest;
+    use host::types::{EpochChangeData, ProvingMode, Request};
+
+    #[test]
+    fn test_encoding_negotiation() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(Encoding::of_request(&headers), Encoding::Bcs);
+        assert_eq!(Encoding::of_response(&headers), Encoding::Bcs);
+
+        headers.insert(ACCEPT, "application/json".parse().unwrap());
+        assert_eq!(Encoding::of_request(&headers), Encoding::Bcs);
+        assert_eq!(Encoding::of_response(&headers), Encoding::Json);
+
+        headers.remove(ACCEPT);
+        headers.insert(
+            CONTENT_TYPE,
+            "application/json; charset=utf-8".parse().unwrap(),
+        );
+        assert_eq!(Encoding::of_request(&headers), Encoding::Json);
+        assert_eq!(Encoding::of_response(&headers), Encoding::Json);
+    }
+
+    #[test]
+    fn test_decode_request() {
+        let request = Request::ProveEpochChange(Box::new((
+            ProvingMode::STARK,
+            EpochChangeData {
+                trusted_state: vec![1, 2, 3],
+                epoch_change_proof: vec![4, 5],
+            },
+        )));
+        let bcs_bytes = bcs::to_bytes(&request).unwrap();
+        let json_bytes = serde_json::to_vec(&JsonRequest::try_from(&request).unwrap()).unwrap();
+
+        // Both encodings decode to the same request
+        for (encoding, bytes) in [(Encoding::Bcs, &bcs_bytes), (Encoding::Json, &json_bytes)] {
+            let decoded = encoding.decode_request(bytes).unwrap();
+            assert_eq!(bcs::to_bytes(&decoded).unwrap(), bcs_bytes);
+        }
+        assert!(Encoding::Json.decode_request(&bcs_bytes).is_err());
+    }
+}
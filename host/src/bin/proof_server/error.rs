@@ -0,0 +1,174 @@
+//! Error responses of the proof server.
+//!
+//! Every failure is answered with an [`ErrorResponse`] body, encoded like
+//! the successful responses of the endpoint, carrying an [`ErrorCode`] that
+//! clients can match on.
+
+use crate::encoding::Encoding;
+use axum::body::Body;
+use axum::http::header::CONTENT_TYPE;
+use axum::http::{Response, StatusCode};
+use axum::response::IntoResponse;
+use host::error::{ErrorCode, ErrorResponse, JobError};
+use tracing::error;
+
+/// An error answered to a client.
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    body: ErrorResponse,
+    encoding: Encoding,
+}
+
+impl ApiError {
+    /// Creates a new `ApiError`, with the HTTP status matching `code`.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The code of the error.
+    /// * `message` - The human-readable description of the error.
+    ///
+    /// # Returns
+    ///
+    /// The `ApiError`.
+    pub fn new(code: ErrorCode, message: impl ToString) -> Self {
+        Self::from_response(ErrorResponse::new(code, message))
+    }
+
+    /// Creates an `ApiError` from an error, see [`ErrorCode::of_error`].
+    ///
+    /// # Arguments
+    ///
+    /// * `err` - The error.
+    ///
+    /// # Returns
+    ///
+    /// The `ApiError`.
+    pub fn from_error(err: &(dyn std::error::Error + 'static)) -> Self {
+        Self::from_response(ErrorResponse::from_error(err))
+    }
+
+    /// Creates an `ApiError` from the error body of a response, e.g. one
+    /// of a backend.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The error body.
+    ///
+    /// # Returns
+    ///
+    /// The `ApiError`.
+    pub fn from_response(body: ErrorResponse) -> Self {
+        Self {
+            status: status_of(body.code),
+            body,
+            encoding: Encoding::Bcs,
+        }
+    }
+
+    /// Returns the error body.
+    pub fn into_body(self) -> ErrorResponse {
+        self.body
+    }
+
+    /// Sets the encoding of the error body.
+    pub const fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+}
+
+impl From<JobError> for ApiError {
+    fn from(err: JobError) -> Self {
+        let code = match &err {
+            JobError::QueueFull => ErrorCode::QueueFull,
+            JobError::NotFound { .. } => ErrorCode::JobNotFound,
+            JobError::InvalidStatus { .. } => ErrorCode::InvalidJobStatus,
+            JobError::InvalidRequest { .. } => ErrorCode::InvalidRequestType,
+            JobError::InvalidCancelToken { .. } => ErrorCode::InvalidCancelToken,
+            JobError::Io { .. } | JobError::Serialization { .. } => ErrorCode::Internal,
+        };
+
+        ApiError::new(code, err)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> axum::response::Response {
+        error!("{:?}: {}", self.body.code, self.body.message);
+
+        let mut response = self
+            .encoding
+            .encode(&self.body)
+            .ok()
+            .and_then(|bytes| {
+                Response::builder()
+                    .status(self.status)
+                    .header(CONTENT_TYPE, self.encoding.content_type())
+                    .body(Body::from(bytes))
+                    .ok()
+            })
+            .map_or_else(|| self.status.into_response(), IntoResponse::into_response);
+        // Exposes the code to the metrics middleware
+        response.extensions_mut().insert(self.body.code);
+
+        response
+    }
+}
+
+/// Returns the HTTP status of the responses carrying an error.
+fn status_of(code: ErrorCode) -> StatusCode {
+    match code {
+        ErrorCode::MalformedRequest | ErrorCode::InvalidRequestType => StatusCode::BAD_REQUEST,
+        ErrorCode::MalformedProof
+        | ErrorCode::QuorumNotMet
+        | ErrorCode::InvalidSignature
+        | ErrorCode::ProofVerificationFailed
+        | ErrorCode::InvalidReceipt
+        | ErrorCode::UnsupportedReceipt => StatusCode::UNPROCESSABLE_ENTITY,
+        ErrorCode::JobNotFound => StatusCode::NOT_FOUND,
+        ErrorCode::InvalidJobStatus => StatusCode::CONFLICT,
+        ErrorCode::InvalidCancelToken => StatusCode::FORBIDDEN,
+        ErrorCode::QueueFull | ErrorCode::BackendUnavailable => StatusCode::SERVICE_UNAVAILABLE,
+        ErrorCode::ProverFailed | ErrorCode::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ApiError;
+    use crate::encoding::Encoding;
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+    use host::error::{ErrorCode, ErrorResponse, JobError};
+
+    #[tokio::test]
+    async fn test_error_response() {
+        let response = ApiError::from(JobError::NotFound { id: 3 })
+            .with_encoding(Encoding::Json)
+            .into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            response.extensions().get::<ErrorCode>(),
+            Some(&ErrorCode::JobNotFound)
+        );
+
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: ErrorResponse = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body.code, ErrorCode::JobNotFound);
+        assert!(String::from_utf8(bytes.to_vec())
+            .unwrap()
+            .contains(r#""code":"job_not_found""#));
+
+        let response =
+            ApiError::new(ErrorCode::QuorumNotMet, "Too little voting power").into_response();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: ErrorResponse = bcs::from_bytes(&bytes).unwrap();
+        assert_eq!(body.code, ErrorCode::QuorumNotMet);
+    }
+}
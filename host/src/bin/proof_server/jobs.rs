@@ -0,0 +1,683 @@
+//! Asynchronous proving jobs of the proof server.
+//!
+//! Proving requests submitted to the [`JobQueue`] are buffered in a bounded
+//! queue and proven like the requests of the proving endpoints, through the
+//! receipt cache and, in split mode, the backends. The receipts of finished
+//! jobs are written to a directory, one `<id>.receipt` file per job, so that
+//! they can still be fetched after the server restarts. Finished jobs are
+//! evicted, along with their receipt, once their retention time is over.
+
+use crate::cache::CacheStatus;
+use crate::error::ApiError;
+use host::error::JobError;
+use host::types::{JobId, JobStatus, JobSubmission, Request};
+use risc0_zkvm::Receipt;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::future::Future;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tokio::sync::{mpsc, Notify};
+use tracing::{error, info};
+
+/// Header carrying the cancel token of a job, see [`JobSubmission`].
+pub const CANCEL_TOKEN_HEADER: &str = "x-cancel-token";
+
+const RECEIPT_EXTENSION: &str = "receipt";
+
+/// Interval between two evictions of the expired jobs.
+const EVICTION_INTERVAL: Duration = Duration::from_secs(60);
+
+type JobReceiver = Arc<tokio::sync::Mutex<mpsc::Receiver<(JobId, Request)>>>;
+
+/// Future resolving to the receipt of a job, see [`JobProveFn`].
+pub type JobProveFuture =
+    Pin<Box<dyn Future<Output = Result<(Receipt, CacheStatus), ApiError>> + Send>>;
+
+/// Function generating the receipt of the request of a job, and telling
+/// how it was obtained from the receipt cache.
+pub type JobProveFn = Arc<dyn Fn(Request) -> JobProveFuture + Send + Sync>;
+
+/// A job known to the queue.
+struct Job {
+    status: JobStatus,
+    /// Hash of the cancel token of the job, `None` for the jobs loaded
+    /// from the receipts directory which can not be cancelled anymore.
+    cancel_token_hash: Option<[u8; 32]>,
+    /// Notified when the job is cancelled, to stop waiting for its receipt.
+    cancelled: Arc<Notify>,
+    /// When the job reached a final status.
+    finished_at: Option<SystemTime>,
+}
+
+/// A queue of proving jobs processed by a fixed number of workers.
+pub struct JobQueue {
+    jobs: Mutex<HashMap<JobId, Job>>,
+    /// How the receipts of the jobs done since the server started were
+    /// obtained from the receipt cache.
+    cache_statuses: Mutex<HashMap<JobId, CacheStatus>>,
+    next_id: AtomicU64,
+    sender: mpsc::Sender<(JobId, Request)>,
+    receipts_dir: PathBuf,
+    retention: Duration,
+    prove: JobProveFn,
+}
+
+impl JobQueue {
+    /// Creates a new `JobQueue` and spawns its job workers. The receipts
+    /// previously persisted in `receipts_dir` are loaded as done jobs.
+    ///
+    /// # Arguments
+    ///
+    /// * `receipts_dir` - The directory the receipts of finished jobs are persisted in.
+    /// * `queue_size` - The maximum number of jobs waiting for a worker.
+    /// * `workers` - The number of jobs proven concurrently.
+    /// * `retention` - The time finished jobs are kept for.
+    /// * `prove` - The function generating the receipts of the jobs.
+    ///
+    /// # Returns
+    ///
+    /// The `JobQueue`.
+    pub fn new(
+        receipts_dir: impl AsRef<Path>,
+        queue_size: usize,
+        workers: usize,
+        retention: Duration,
+        prove: JobProveFn,
+    ) -> Result<Arc<Self>, JobError> {
+        let receipts_dir = receipts_dir.as_ref().to_path_buf();
+        fs::create_dir_all(&receipts_dir).map_err(|err| io_error(&receipts_dir, err))?;
+
+        let jobs = load_jobs(&receipts_dir)?;
+        let next_id = jobs.keys().max().map_or(0, |id| id + 1);
+        info!(
+            "Loaded {} finished jobs from {}",
+            jobs.len(),
+            receipts_dir.display()
+        );
+
+        let (sender, receiver) = mpsc::channel(queue_size.max(1));
+        let queue = Arc::new(Self {
+            jobs: Mutex::new(jobs),
+            cache_statuses: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(next_id),
+            sender,
+            receipts_dir,
+            retention,
+            prove,
+        });
+
+        let receiver = Arc::new(tokio::sync::Mutex::new(receiver));
+        for _ in 0..workers.max(1) {
+            tokio::spawn(queue.clone().run_worker(receiver.clone()));
+        }
+        tokio::spawn(queue.clone().run_eviction());
+
+        Ok(queue)
+    }
+
+    /// Submits a proving request.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request to prove, either `Request::ProveInclusion` or `Request::ProveEpochChange`.
+    ///
+    /// # Returns
+    ///
+    /// The identifier of the job and the token required to cancel it.
+    pub fn submit(&self, request: Request) -> Result<JobSubmission, JobError> {
+        if !matches!(
+            request,
+            Request::ProveInclusion(_) | Request::ProveEpochChange(_)
+        ) {
+            return Err(JobError::InvalidRequest {
+                request: (&request).to_string(),
+            });
+        }
+
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let cancel_token = hex::encode(rand::random::<[u8; 16]>());
+        // Register the job first so that a worker picking it up right away
+        // finds it queued.
+        self.jobs.lock().unwrap().insert(
+            id,
+            Job {
+                status: JobStatus::Queued,
+                cancel_token_hash: Some(Sha256::digest(cancel_token.as_bytes()).into()),
+                cancelled: Arc::new(Notify::new()),
+                finished_at: None,
+            },
+        );
+        if self.sender.try_send((id, request)).is_err() {
+            self.jobs.lock().unwrap().remove(&id);
+            return Err(JobError::QueueFull);
+        }
+
+        info!("Job {id} queued");
+        Ok(JobSubmission { id, cancel_token })
+    }
+
+    /// Returns the status of a job.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The identifier of the job.
+    ///
+    /// # Returns
+    ///
+    /// The `JobStatus` of the job.
+    pub fn status(&self, id: JobId) -> Result<JobStatus, JobError> {
+        self.jobs
+            .lock()
+            .unwrap()
+            .get(&id)
+            .map(|job| job.status.clone())
+            .ok_or(JobError::NotFound { id })
+    }
+
+    /// Returns the number of jobs waiting for a worker.
+    pub fn queued(&self) -> usize {
+        self.jobs
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|job| job.status == JobStatus::Queued)
+            .count()
+    }
+
+    /// Returns the receipt of a done job.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The identifier of the job.
+    ///
+    /// # Returns
+    ///
+    /// The `Receipt` of the job.
+    pub fn receipt(&self, id: JobId) -> Result<Receipt, JobError> {
+        match self.status(id)? {
+            JobStatus::Done => (),
+            status => return Err(JobError::InvalidStatus { id, status }),
+        }
+
+        let path = self.receipt_path(id);
+        let bytes = fs::read(&path).map_err(|err| io_error(&path, err))?;
+
+        bcs::from_bytes(&bytes).map_err(|err| JobError::Serialization { source: err.into() })
+    }
+
+    /// Returns how the receipt of a done job was obtained from the receipt
+    /// cache, if it was proven since the server started.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The identifier of the job.
+    pub fn cache_status(&self, id: JobId) -> Option<CacheStatus> {
+        self.cache_statuses.lock().unwrap().get(&id).copied()
+    }
+
+    /// Cancels a queued or running job. A running job stops waiting for
+    /// its receipt right away, and the receipt is discarded.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The identifier of the job.
+    /// * `cancel_token` - The cancel token returned on the submission of the job.
+    pub fn cancel(&self, id: JobId, cancel_token: &str) -> Result<(), JobError> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let job = jobs.get_mut(&id).ok_or(JobError::NotFound { id })?;
+        // Compare hashes so that the comparison does not leak the token
+        let cancel_token_hash: [u8; 32] = Sha256::digest(cancel_token.as_bytes()).into();
+        if job.cancel_token_hash != Some(cancel_token_hash) {
+            return Err(JobError::InvalidCancelToken { id });
+        }
+        if job.status.is_finished() {
+            return Err(JobError::InvalidStatus {
+                id,
+                status: job.status.clone(),
+            });
+        }
+        job.status = JobStatus::Cancelled;
+        job.finished_at = Some(SystemTime::now());
+        // Stores a permit if the worker is not waiting for the receipt yet
+        job.cancelled.notify_one();
+
+        info!("Job {id} cancelled");
+        Ok(())
+    }
+
+    async fn run_worker(self: Arc<Self>, receiver: JobReceiver) {
+        loop {
+            let Some((id, request)) = receiver.lock().await.recv().await else {
+                return;
+            };
+
+            // Skip the jobs cancelled while queued
+            let Some(cancelled) = self.start(id) else {
+                continue;
+            };
+
+            info!("Start proving job {id}");
+            let res = tokio::select! {
+                res = (self.prove)(request) => res,
+                () = cancelled.notified() => {
+                    info!("Stopped proving cancelled job {id}");
+                    continue;
+                }
+            };
+            let res = match res {
+                Ok((receipt, cache_status)) => self
+                    .persist_receipt(id, &receipt)
+                    .map(|()| cache_status)
+                    .map_err(|err| err.to_string()),
+                Err(err) => Err(err.into_body().message),
+            };
+
+            let status = match res {
+                Ok(cache_status) => {
+                    self.cache_statuses.lock().unwrap().insert(id, cache_status);
+                    JobStatus::Done
+                }
+                Err(message) => {
+                    error!("Job {id} failed: {message}");
+                    JobStatus::Failed(message)
+                }
+            };
+            if !self.finish(id, status) {
+                // The job was cancelled after its receipt was generated
+                self.cache_statuses.lock().unwrap().remove(&id);
+                let _ = fs::remove_file(self.receipt_path(id));
+                continue;
+            }
+
+            info!("Job {id} finished");
+        }
+    }
+
+    async fn run_eviction(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(EVICTION_INTERVAL);
+        loop {
+            interval.tick().await;
+            self.evict_expired();
+        }
+    }
+
+    /// Removes the jobs finished for longer than the retention time, along
+    /// with their receipt.
+    fn evict_expired(&self) {
+        let expired: Vec<JobId> = {
+            let mut jobs = self.jobs.lock().unwrap();
+            let expired = jobs
+                .iter()
+                .filter(|(_, job)| {
+                    job.finished_at.is_some_and(|finished_at| {
+                        finished_at
+                            .elapsed()
+                            .is_ok_and(|elapsed| elapsed >= self.retention)
+                    })
+                })
+                .map(|(id, _)| *id)
+                .collect();
+            for id in &expired {
+                jobs.remove(id);
+            }
+            expired
+        };
+        if expired.is_empty() {
+            return;
+        }
+
+        let mut cache_statuses = self.cache_statuses.lock().unwrap();
+        for id in &expired {
+            cache_statuses.remove(id);
+        }
+        drop(cache_statuses);
+        for id in &expired {
+            let path = self.receipt_path(*id);
+            if let Err(err) = fs::remove_file(&path) {
+                if err.kind() != std::io::ErrorKind::NotFound {
+                    error!("Failed to remove {}: {err}", path.display());
+                }
+            }
+        }
+
+        info!("Evicted {} expired jobs", expired.len());
+    }
+
+    /// Sets the status of a queued job to running, returning the
+    /// notification of its cancellation.
+    fn start(&self, id: JobId) -> Option<Arc<Notify>> {
+        match self.jobs.lock().unwrap().get_mut(&id) {
+            Some(job) if job.status == JobStatus::Queued => {
+                job.status = JobStatus::Running;
+                Some(job.cancelled.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Sets the final status of a running job, unless it was cancelled.
+    fn finish(&self, id: JobId, status: JobStatus) -> bool {
+        match self.jobs.lock().unwrap().get_mut(&id) {
+            Some(job) if job.status == JobStatus::Running => {
+                job.status = status;
+                job.finished_at = Some(SystemTime::now());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn receipt_path(&self, id: JobId) -> PathBuf {
+        self.receipts_dir.join(format!("{id}.{RECEIPT_EXTENSION}"))
+    }
+
+    /// Writes the receipt to a temporary file which is synced then
+    /// renamed, so that a crash never leaves a partial receipt behind.
+    fn persist_receipt(&self, id: JobId, receipt: &Receipt) -> anyhow::Result<()> {
+        let bytes = bcs::to_bytes(receipt)?;
+
+        let path = self.receipt_path(id);
+        let tmp_path = path.with_extension("tmp");
+        let mut tmp_file = File::create(&tmp_path)?;
+        tmp_file.write_all(&bytes)?;
+        tmp_file.sync_all()?;
+        fs::rename(&tmp_path, &path)?;
+
+        // Persist the rename itself
+        File::open(&self.receipts_dir).and_then(|dir| dir.sync_all())?;
+
+        Ok(())
+    }
+}
+
+fn io_error(path: &Path, source: std::io::Error) -> JobError {
+    JobError::Io {
+        path: path.display().to_string(),
+        source,
+    }
+}
+
+/// Lists the jobs whose receipt was persisted in `receipts_dir`. They are
+/// considered finished when their receipt was last modified.
+fn load_jobs(receipts_dir: &Path) -> Result<HashMap<JobId, Job>, JobError> {
+    let mut jobs = HashMap::new();
+    for entry in fs::read_dir(receipts_dir).map_err(|err| io_error(receipts_dir, err))? {
+        let entry = entry.map_err(|err| io_error(receipts_dir, err))?;
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some(RECEIPT_EXTENSION) {
+            continue;
+        }
+        if let Some(id) = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.parse::<JobId>().ok())
+        {
+            let finished_at = entry
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .map_err(|err| io_error(&path, err))?;
+            jobs.insert(
+                id,
+                Job {
+                    status: JobStatus::Done,
+                    cancel_token_hash: None,
+                    cancelled: Arc::new(Notify::new()),
+                    finished_at: Some(finished_at),
+                },
+            );
+        }
+    }
+
+    Ok(jobs)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cache::CacheStatus;
+    use crate::error::ApiError;
+    use crate::jobs::{JobProveFn, JobProveFuture, JobQueue, RECEIPT_EXTENSION};
+    use crate::metrics::Metrics;
+    use crate::prover::{GeneratedProof, ProverPool};
+    use aptos_guests::EPOCH_CHANGE_ID;
+    use host::error::JobError;
+    use host::types::{
+        EpochChangeData, EpochChangeExpectations, JobId, JobStatus, ProvingMode, Request,
+    };
+    use risc0_zkvm::{FakeReceipt, InnerReceipt, Receipt, ReceiptClaim};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    const RETENTION: Duration = Duration::from_secs(3600);
+
+    fn fake_receipt(journal: Vec<u8>) -> Receipt {
+        Receipt::new(
+            InnerReceipt::Fake(FakeReceipt::new(ReceiptClaim::ok(
+                EPOCH_CHANGE_ID,
+                journal.clone(),
+            ))),
+            journal,
+        )
+    }
+
+    fn request(trusted_state: Vec<u8>) -> Request {
+        Request::ProveEpochChange(Box::new((
+            ProvingMode::STARK,
+            EpochChangeData {
+                trusted_state,
+                epoch_change_proof: vec![],
+            },
+        )))
+    }
+
+    /// A prover blocking until `released` is set, then committing to the
+    /// trusted state of the request. An empty trusted state makes the job
+    /// fail.
+    fn job_prover(size: usize, released: Arc<AtomicBool>) -> JobProveFn {
+        let prove = move |request: &Request| {
+            while !released.load(Ordering::SeqCst) {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            match request {
+                Request::ProveEpochChange(boxed) if !boxed.1.trusted_state.is_empty() => {
+                    Ok(GeneratedProof {
+                        receipt: fake_receipt(boxed.1.trusted_state.clone()),
+                        total_cycles: 0,
+                    })
+                }
+                _ => Err(anyhow::anyhow!("Invalid trusted state")),
+            }
+        };
+
+        let pool = Arc::new(ProverPool::new(
+            size,
+            Arc::new(prove),
+            Arc::new(Metrics::new()),
+        ));
+        Arc::new(move |request| -> JobProveFuture {
+            let pool = pool.clone();
+            Box::pin(async move {
+                let receipt = pool
+                    .prove(request)
+                    .await
+                    .map_err(|err| ApiError::from_error(&*err))?;
+                Ok((receipt, CacheStatus::Miss))
+            })
+        })
+    }
+
+    async fn wait_for_status(queue: &JobQueue, id: JobId, done: impl Fn(&JobStatus) -> bool) {
+        for _ in 0..500 {
+            if done(&queue.status(id).unwrap()) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("Job {id} did not reach the expected status");
+    }
+
+    #[tokio::test]
+    async fn test_job_queue() {
+        let dir = tempfile::tempdir().unwrap();
+        let released = Arc::new(AtomicBool::new(true));
+
+        let queue =
+            JobQueue::new(dir.path(), 4, 2, RETENTION, job_prover(2, released.clone())).unwrap();
+
+        let submission = queue.submit(request(vec![1, 2, 3])).unwrap();
+        let done = submission.id;
+        let failed = queue.submit(request(vec![])).unwrap().id;
+        wait_for_status(&queue, done, JobStatus::is_finished).await;
+        wait_for_status(&queue, failed, JobStatus::is_finished).await;
+        assert_eq!(queue.status(done).unwrap(), JobStatus::Done);
+        assert_eq!(
+            queue.status(failed).unwrap(),
+            JobStatus::Failed("Invalid trusted state".to_string())
+        );
+
+        assert_eq!(queue.receipt(done).unwrap().journal.bytes, vec![1, 2, 3]);
+        assert_eq!(queue.cache_status(done), Some(CacheStatus::Miss));
+        assert_eq!(queue.cache_status(failed), None);
+        assert!(matches!(
+            queue.receipt(failed),
+            Err(JobError::InvalidStatus { .. })
+        ));
+        assert!(matches!(queue.status(42), Err(JobError::NotFound { .. })));
+
+        // Only proving requests can be submitted
+        assert!(matches!(
+            queue.submit(Request::VerifyEpochChange(Box::new((
+                fake_receipt(vec![]),
+                EpochChangeExpectations::default()
+            )))),
+            Err(JobError::InvalidRequest { .. })
+        ));
+        // Finished jobs can not be cancelled
+        assert!(matches!(
+            queue.cancel(done, &submission.cancel_token),
+            Err(JobError::InvalidStatus { .. })
+        ));
+        drop(queue);
+
+        // Receipts survive a restart, and new jobs get fresh identifiers
+        let queue = JobQueue::new(dir.path(), 4, 1, RETENTION, job_prover(1, released)).unwrap();
+        assert_eq!(queue.status(done).unwrap(), JobStatus::Done);
+        assert_eq!(queue.receipt(done).unwrap().journal.bytes, vec![1, 2, 3]);
+        // How the receipt was obtained is not persisted
+        assert_eq!(queue.cache_status(done), None);
+        assert!(matches!(
+            queue.status(failed),
+            Err(JobError::NotFound { .. })
+        ));
+        assert!(queue.submit(request(vec![4])).unwrap().id > failed);
+    }
+
+    #[tokio::test]
+    async fn test_job_queue_cancel() {
+        let dir = tempfile::tempdir().unwrap();
+        let released = Arc::new(AtomicBool::new(false));
+
+        let queue =
+            JobQueue::new(dir.path(), 1, 1, RETENTION, job_prover(1, released.clone())).unwrap();
+
+        let running = queue.submit(request(vec![1])).unwrap();
+        wait_for_status(&queue, running.id, |status| *status == JobStatus::Running).await;
+        let queued = queue.submit(request(vec![2])).unwrap();
+        // The queue only holds one job
+        assert!(matches!(
+            queue.submit(request(vec![3])),
+            Err(JobError::QueueFull)
+        ));
+        assert_eq!(queue.queued(), 1);
+
+        // Jobs can only be cancelled with their own token
+        assert!(matches!(
+            queue.cancel(running.id, "invalid"),
+            Err(JobError::InvalidCancelToken { .. })
+        ));
+        assert!(matches!(
+            queue.cancel(queued.id, &running.cancel_token),
+            Err(JobError::InvalidCancelToken { .. })
+        ));
+        assert_eq!(queue.status(running.id).unwrap(), JobStatus::Running);
+
+        queue.cancel(queued.id, &queued.cancel_token).unwrap();
+        queue.cancel(running.id, &running.cancel_token).unwrap();
+
+        // The worker stops waiting for the cancelled job right away and
+        // drains the queue, while its proof is still blocked
+        let next = loop {
+            match queue.submit(request(vec![4])) {
+                Ok(submission) => break submission.id,
+                Err(JobError::QueueFull) => tokio::time::sleep(Duration::from_millis(10)).await,
+                Err(err) => panic!("{err}"),
+            }
+        };
+        wait_for_status(&queue, next, |status| *status == JobStatus::Running).await;
+        released.store(true, Ordering::SeqCst);
+        wait_for_status(&queue, next, JobStatus::is_finished).await;
+        assert_eq!(queue.receipt(next).unwrap().journal.bytes, vec![4]);
+
+        // The results of cancelled jobs are discarded
+        assert_eq!(queue.status(running.id).unwrap(), JobStatus::Cancelled);
+        assert_eq!(queue.status(queued.id).unwrap(), JobStatus::Cancelled);
+        assert!(queue.receipt(running.id).is_err());
+        assert_eq!(queue.cache_status(running.id), None);
+        assert!(!dir
+            .path()
+            .join(format!("{}.{RECEIPT_EXTENSION}", running.id))
+            .exists());
+    }
+
+    #[tokio::test]
+    async fn test_job_queue_eviction() {
+        let dir = tempfile::tempdir().unwrap();
+        let released = Arc::new(AtomicBool::new(false));
+
+        // Jobs expire as soon as they are finished
+        let queue = JobQueue::new(
+            dir.path(),
+            4,
+            1,
+            Duration::ZERO,
+            job_prover(1, released.clone()),
+        )
+        .unwrap();
+
+        let done = queue.submit(request(vec![1])).unwrap().id;
+        let cancelled = queue.submit(request(vec![2])).unwrap();
+        wait_for_status(&queue, done, |status| *status == JobStatus::Running).await;
+
+        // Unfinished jobs are never evicted
+        queue.evict_expired();
+        assert_eq!(queue.status(done).unwrap(), JobStatus::Running);
+        assert_eq!(queue.status(cancelled.id).unwrap(), JobStatus::Queued);
+
+        queue.cancel(cancelled.id, &cancelled.cancel_token).unwrap();
+        released.store(true, Ordering::SeqCst);
+        wait_for_status(&queue, done, JobStatus::is_finished).await;
+        let receipt_path = dir.path().join(format!("{done}.{RECEIPT_EXTENSION}"));
+        assert!(receipt_path.exists());
+
+        // Finished jobs are evicted along with their receipt
+        queue.evict_expired();
+        assert!(matches!(queue.status(done), Err(JobError::NotFound { .. })));
+        assert!(matches!(
+            queue.status(cancelled.id),
+            Err(JobError::NotFound { .. })
+        ));
+        assert_eq!(queue.cache_status(done), None);
+        assert!(!receipt_path.exists());
+        drop(queue);
+
+        // Evicted jobs are not loaded back
+        let queue = JobQueue::new(dir.path(), 4, 1, RETENTION, job_prover(1, released)).unwrap();
+        assert!(matches!(queue.status(done), Err(JobError::NotFound { .. })));
+    }
+}
@@ -0,0 +1,696 @@
+use anyhow::Error;
+use axum::body::Body;
+use axum::body::HttpBody;
+use axum::extract::rejection::QueryRejection;
+use axum::extract::{MatchedPath, Path, Query, State};
+use axum::http::header::{CONTENT_LENGTH, CONTENT_TYPE};
+use axum::http::{HeaderMap, HeaderValue, Response, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::IntoResponse;
+use axum::routing::{get, post};
+use axum::Router;
+use clap::{Parser, ValueEnum};
+use host::error::ErrorCode;
+use host::evm::{EvmExport, JournalOutput, VerifierCalldata};
+use host::json::{
+    JsonEpochChangeOutput, JsonEvmExport, JsonInclusionOutput, JsonVerificationResponse,
+};
+use host::types::{JobId, Request, VerificationResponse};
+use risc0_zkvm::Receipt;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tracing::info;
+
+use aptos_guests::{EPOCH_CHANGE_ID, INCLUSION_ID};
+use aptos_lc_core::types::error::TypesError;
+use aptos_lc_core::types::output::{EpochChangeOutput, InclusionOutput};
+
+use crate::cache::{cache_key, CacheStatus, ReceiptCache, CACHE_STATUS_HEADER};
+use crate::cluster::Cluster;
+use crate::encoding::Encoding;
+use crate::error::ApiError;
+use crate::jobs::{JobProveFuture, JobQueue, CANCEL_TOKEN_HEADER};
+use crate::metrics::{Metrics, METRICS_CONTENT_TYPE};
+use crate::prover::{preflight, prove_request, ProverPool};
+
+mod cache;
+mod cluster;
+mod encoding;
+mod error;
+mod jobs;
+mod metrics;
+mod prover;
+
+#[derive(Parser)]
+struct Cli {
+    /// Address of this server. E.g. 127.0.0.1:4321
+    #[arg(short, long)]
+    addr: String,
+
+    /// Comma separated addresses of the backend servers proofs are
+    /// forwarded to in split mode. E.g. 127.0.0.1:4322,127.0.0.1:4323
+    #[arg(short, long, alias = "snd-addr", value_delimiter = ',')]
+    backends: Vec<String>,
+    #[arg(short, long)]
+    mode: Mode,
+
+    /// Number of times a proof request failing on a backend is retried on
+    /// another one.
+    #[arg(long, default_value_t = 2)]
+    retries: usize,
+
+    /// Interval between two health checks of the backends, in seconds.
+    #[arg(long, default_value_t = 10)]
+    health_check_interval: u64,
+
+    /// Number of proofs generated concurrently.
+    #[arg(long, default_value_t = 1)]
+    workers: usize,
+
+    /// Maximum number of proving jobs waiting for a worker.
+    #[arg(long, default_value_t = 16)]
+    queue_size: usize,
+
+    /// Directory in which the receipts of finished jobs are persisted.
+    #[arg(long, default_value = "jobs")]
+    jobs_dir: String,
+
+    /// Time after which a finished job and its receipt are evicted, in
+    /// seconds.
+    #[arg(long, default_value_t = 86400)]
+    job_ttl: u64,
+
+    /// Directory in which the cached receipts are stored.
+    #[arg(long, default_value = "cache")]
+    cache_dir: String,
+
+    /// Maximum size of the cached receipts, in megabytes. 0 disables the
+    /// cache, identical concurrent requests are still proven once.
+    #[arg(long, default_value_t = 1024)]
+    cache_size: u64,
+
+    /// Time after which a cached receipt expires, in seconds.
+    #[arg(long, default_value_t = 86400)]
+    cache_ttl: u64,
+}
+
+#[derive(ValueEnum, Clone, Debug, Eq, PartialEq)]
+enum Mode {
+    Single,
+    Split,
+}
+
+#[derive(Clone)]
+struct ServerState {
+    prover: ReceiptProver,
+    jobs: Arc<JobQueue>,
+    metrics: Arc<Metrics>,
+}
+
+/// Generates the receipts of the proving requests, of both the proving
+/// endpoints and the jobs.
+#[derive(Clone)]
+struct ReceiptProver {
+    cluster: Arc<Cluster>,
+    mode: Mode,
+    pool: Arc<ProverPool>,
+    cache: Arc<ReceiptCache>,
+}
+
+/// Query parameters of the proving endpoints.
+#[derive(Deserialize)]
+struct ProveParams {
+    /// Whether the inputs are checked natively before proving. Defaults
+    /// to `true`.
+    preflight: Option<bool>,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let Cli {
+        addr,
+        backends,
+        mode,
+        retries,
+        health_check_interval,
+        workers,
+        queue_size,
+        jobs_dir,
+        job_ttl,
+        cache_dir,
+        cache_size,
+        cache_ttl,
+    } = Cli::parse();
+
+    if mode == Mode::Split && backends.is_empty() {
+        return Err(Error::msg(
+            "At least one backend address is required in split mode",
+        ));
+    }
+
+    let cluster = Arc::new(Cluster::new(backends, retries));
+    if mode == Mode::Split {
+        cluster.spawn_health_checks(Duration::from_secs(health_check_interval));
+    }
+
+    let metrics = Arc::new(Metrics::new());
+    let pool = Arc::new(ProverPool::new(
+        workers,
+        Arc::new(prove_request),
+        metrics.clone(),
+    ));
+    let prover = ReceiptProver {
+        cluster,
+        mode,
+        pool,
+        cache: Arc::new(ReceiptCache::new(
+            cache_dir,
+            cache_size * 1024 * 1024,
+            Duration::from_secs(cache_ttl),
+        )?),
+    };
+    let job_prover = prover.clone();
+    let jobs = JobQueue::new(
+        jobs_dir,
+        queue_size,
+        workers,
+        Duration::from_secs(job_ttl),
+        Arc::new(move |request| -> JobProveFuture {
+            let prover = job_prover.clone();
+            Box::pin(async move { prover.prove(request).await })
+        }),
+    )?;
+    let state = ServerState {
+        prover,
+        jobs,
+        metrics,
+    };
+
+    let app = Router::new()
+        .route("/health", get(health_check))
+        .route("/ready", get(ready_check))
+        .route("/metrics", get(export_metrics))
+        .route("/inclusion/proof", post(inclusion_proof))
+        .route("/epoch/proof", post(epoch_proof))
+        .route("/epoch/verify", post(epoch_verify))
+        .route("/inclusion/verify", post(inclusion_verify))
+        .route("/evm/calldata", post(evm_calldata))
+        .route("/jobs", post(submit_job))
+        .route("/jobs/:id", get(job_status).delete(cancel_job))
+        .route("/jobs/:id/receipt", get(job_receipt))
+        .route_layer(middleware::from_fn_with_state(state.clone(), track_metrics))
+        .with_state(state);
+
+    info!("Server running on {}", addr);
+
+    let listener = TcpListener::bind(addr).await?;
+
+    axum::serve(listener, app).await?;
+
+    Ok(())
+}
+
+async fn health_check() -> impl IntoResponse {
+    StatusCode::OK
+}
+
+async fn ready_check(State(state): State<ServerState>) -> impl IntoResponse {
+    let prover = &state.prover;
+    let is_ready = match prover.mode {
+        Mode::Single => prover.pool.available() > 0,
+        Mode::Split => prover.cluster.healthy_backends() > 0,
+    };
+    if !is_ready {
+        StatusCode::CONFLICT
+    } else {
+        StatusCode::OK
+    }
+}
+
+async fn export_metrics(State(state): State<ServerState>) -> Result<impl IntoResponse, ApiError> {
+    let pool = &state.prover.pool;
+    let busy_workers = pool.size() - pool.available();
+    let body = state
+        .metrics
+        .encode(state.jobs.queued(), busy_workers)
+        .map_err(|err| ApiError::from_error(&err))?;
+
+    Ok(([(CONTENT_TYPE, METRICS_CONTENT_TYPE)], body))
+}
+
+/// Records the size and status of the requests and responses of the
+/// routes, as well as the code of the errors answered.
+async fn track_metrics(
+    State(state): State<ServerState>,
+    path: MatchedPath,
+    request: axum::extract::Request,
+    next: Next,
+) -> axum::response::Response {
+    let request_size = request
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok()?.parse().ok())
+        .unwrap_or_else(|| request.body().size_hint().lower());
+
+    let response = next.run(request).await;
+
+    state.metrics.record_response(
+        path.as_str(),
+        request_size,
+        response.body().size_hint().lower(),
+        response.status().as_u16(),
+        response.extensions().get::<ErrorCode>().copied(),
+    );
+
+    response
+}
+
+/// Reads and decodes the body of a request.
+///
+/// # Arguments
+///
+/// * `request` - The HTTP request.
+///
+/// # Returns
+///
+/// The decoded `Request` and the `Encoding` of the response.
+async fn read_request(request: axum::extract::Request) -> Result<(Request, Encoding), ApiError> {
+    let request_encoding = Encoding::of_request(request.headers());
+    let response_encoding = Encoding::of_response(request.headers());
+
+    let bytes = axum::body::to_bytes(request.into_body(), usize::MAX)
+        .await
+        .map_err(|err| {
+            ApiError::new(ErrorCode::MalformedRequest, err).with_encoding(response_encoding)
+        })?;
+
+    let request = request_encoding.decode_request(&bytes).map_err(|err| {
+        ApiError::new(
+            ErrorCode::MalformedRequest,
+            format!("Failed to deserialize request object: {err}"),
+        )
+        .with_encoding(response_encoding)
+    })?;
+
+    Ok((request, response_encoding))
+}
+
+/// Checks a proving request natively before it is proven, unless the
+/// client disabled it with `?preflight=false`. The checks verify
+/// signatures, so they run on a blocking thread.
+///
+/// # Returns
+///
+/// The checked `Request`.
+async fn check_request(
+    request: Request,
+    params: Result<Query<ProveParams>, QueryRejection>,
+    encoding: Encoding,
+) -> Result<Request, ApiError> {
+    let Query(params) = params
+        .map_err(|err| ApiError::new(ErrorCode::MalformedRequest, err).with_encoding(encoding))?;
+
+    if !params.preflight.unwrap_or(true) {
+        return Ok(request);
+    }
+
+    tokio::task::spawn_blocking(move || preflight(&request).map(|()| request))
+        .await
+        .map_err(|err| ApiError::from_error(&err).with_encoding(encoding))?
+        .map_err(|err| ApiError::from_error(&err).with_encoding(encoding))
+}
+
+fn invalid_request_type(request: &Request, encoding: Encoding) -> ApiError {
+    ApiError::new(
+        ErrorCode::InvalidRequestType,
+        format!("Invalid request type {request}"),
+    )
+    .with_encoding(encoding)
+}
+
+impl ReceiptProver {
+    /// Returns the receipt of a proving request from the cache, or
+    /// generates it.
+    async fn prove(&self, request: Request) -> Result<(Receipt, CacheStatus), ApiError> {
+        match cache_key(&request) {
+            Some(key) => {
+                self.cache
+                    .get_or_prove(key, self.generate_receipt(request))
+                    .await
+            }
+            None => Ok((self.generate_receipt(request).await?, CacheStatus::Miss)),
+        }
+    }
+
+    /// Generates the receipt of a proving request, either locally or on a
+    /// backend depending on the mode of the server. In split mode, the
+    /// request is forwarded to the proving endpoint of its type, the
+    /// preflight checks being already handled by this server.
+    async fn generate_receipt(&self, request: Request) -> Result<Receipt, ApiError> {
+        match self.mode {
+            Mode::Single => self
+                .pool
+                .prove(request)
+                .await
+                .map_err(|err| ApiError::from_error(&*err)),
+            Mode::Split => {
+                let path = match request {
+                    Request::ProveInclusion(_) => "/inclusion/proof",
+                    Request::ProveEpochChange(_) => "/epoch/proof",
+                    _ => {
+                        return Err(ApiError::new(
+                            ErrorCode::InvalidRequestType,
+                            format!("Invalid request type {}", &request),
+                        ))
+                    }
+                };
+
+                // Backends are always addressed in BCS
+                let bytes = bcs::to_bytes(&request).map_err(|err| ApiError::from_error(&err))?;
+                let res = self
+                    .cluster
+                    .forward(&format!("{path}?preflight=false"), bytes)
+                    .await?;
+
+                bcs::from_bytes(&res).map_err(|err| {
+                    ApiError::new(
+                        ErrorCode::Internal,
+                        format!("Failed to deserialize receipt from backend: {err}"),
+                    )
+                })
+            }
+        }
+    }
+}
+
+/// Encodes a receipt into a response.
+fn receipt_response(encoding: Encoding, receipt: &Receipt) -> Result<Response<Body>, ApiError> {
+    let res = encoding
+        .encode_receipt(receipt)
+        .map_err(|err| ApiError::from_error(&*err).with_encoding(encoding))?;
+
+    response(encoding, res)
+}
+
+/// Encodes a receipt into a response, telling whether it came from the
+/// cache in the [`CACHE_STATUS_HEADER`] header.
+fn cached_receipt_response(
+    encoding: Encoding,
+    receipt: &Receipt,
+    cache_status: CacheStatus,
+) -> Result<Response<Body>, ApiError> {
+    let mut response = receipt_response(encoding, receipt)?;
+    response.headers_mut().insert(
+        CACHE_STATUS_HEADER,
+        HeaderValue::from_static(cache_status.as_str()),
+    );
+
+    Ok(response)
+}
+
+/// Verifies a receipt against the image ID of its program, decodes the
+/// output committed to in its journal and checks it against the
+/// expectations of the request. The outcome is recorded under `program`,
+/// a receipt missing expectations being counted as invalid.
+///
+/// # Arguments
+///
+/// * `metrics` - The metrics of the server.
+/// * `receipt` - The receipt to verify.
+/// * `program` - The name of the program, as a metrics label.
+/// * `image_id` - The image ID of the program.
+/// * `decode` - Decodes the output of the program from the journal.
+/// * `check` - Returns the names of the expectations the output does not meet.
+///
+/// # Returns
+///
+/// The decoded output and the expectations it does not meet.
+fn verify_receipt<O>(
+    metrics: &Metrics,
+    receipt: &Receipt,
+    program: &'static str,
+    image_id: [u32; 8],
+    decode: fn(&[u8]) -> Result<O, TypesError>,
+    check: impl FnOnce(&O) -> Vec<String>,
+) -> Result<VerificationResponse<O>, ApiError> {
+    let res = receipt
+        .verify(image_id)
+        .map_err(|err| ApiError::new(ErrorCode::InvalidReceipt, err))
+        .and_then(|_| {
+            decode(&receipt.journal.bytes).map_err(|err| {
+                ApiError::new(
+                    ErrorCode::InvalidReceipt,
+                    format!("Failed to decode journal: {err}"),
+                )
+            })
+        })
+        .map(|output| VerificationResponse {
+            failed_expectations: check(&output),
+            output,
+        });
+    metrics.record_verification(
+        program,
+        res.as_ref()
+            .is_ok_and(|response| response.failed_expectations.is_empty()),
+    );
+
+    res
+}
+
+/// Encodes the response to a verification request, as a
+/// [`JsonVerificationResponse`] of `J` for JSON.
+fn verification_response<O, J>(
+    encoding: Encoding,
+    verification: &VerificationResponse<O>,
+) -> Result<Response<Body>, ApiError>
+where
+    O: Serialize,
+    J: Serialize + for<'a> From<&'a O>,
+{
+    let res = encoding
+        .encode_as::<_, JsonVerificationResponse<J>>(verification)
+        .map_err(|err| ApiError::from_error(&*err).with_encoding(encoding))?;
+
+    response(encoding, res)
+}
+
+async fn inclusion_proof(
+    State(state): State<ServerState>,
+    params: Result<Query<ProveParams>, QueryRejection>,
+    request: axum::extract::Request,
+) -> Result<impl IntoResponse, ApiError> {
+    let (request, encoding) = read_request(request).await?;
+
+    let Request::ProveInclusion(_) = request else {
+        return Err(invalid_request_type(&request, encoding));
+    };
+    let request = check_request(request, params, encoding).await?;
+
+    info!("Start proving");
+    let (receipt, cache_status) = state
+        .prover
+        .prove(request)
+        .await
+        .map_err(|err| err.with_encoding(encoding))?;
+
+    info!("Proof generated. Serializing");
+    cached_receipt_response(encoding, &receipt, cache_status)
+}
+
+async fn inclusion_verify(
+    State(state): State<ServerState>,
+    request: axum::extract::Request,
+) -> Result<impl IntoResponse, ApiError> {
+    let (request, encoding) = read_request(request).await?;
+
+    let Request::VerifyInclusion(boxed) = request else {
+        return Err(invalid_request_type(&request, encoding));
+    };
+    let (receipt, expectations) = *boxed;
+
+    info!("Start verifying inclusion proof");
+    let res = verify_receipt(
+        &state.metrics,
+        &receipt,
+        "inclusion",
+        INCLUSION_ID,
+        InclusionOutput::from_bytes,
+        |output| expectations.check(output),
+    )
+    .map_err(|err| err.with_encoding(encoding))?;
+
+    info!(
+        "Inclusion verification result: failed expectations {:?}",
+        res.failed_expectations
+    );
+    verification_response::<_, JsonInclusionOutput>(encoding, &res)
+}
+
+async fn epoch_proof(
+    State(state): State<ServerState>,
+    params: Result<Query<ProveParams>, QueryRejection>,
+    request: axum::extract::Request,
+) -> Result<impl IntoResponse, ApiError> {
+    let (request, encoding) = read_request(request).await?;
+
+    let Request::ProveEpochChange(_) = request else {
+        return Err(invalid_request_type(&request, encoding));
+    };
+    let request = check_request(request, params, encoding).await?;
+
+    info!("Start proving epoch change");
+    let (receipt, cache_status) = state
+        .prover
+        .prove(request)
+        .await
+        .map_err(|err| err.with_encoding(encoding))?;
+
+    info!("Epoch change proof generated. Serializing");
+    cached_receipt_response(encoding, &receipt, cache_status)
+}
+
+async fn epoch_verify(
+    State(state): State<ServerState>,
+    request: axum::extract::Request,
+) -> Result<impl IntoResponse, ApiError> {
+    info!("Start verifying epoch change proof");
+
+    let (request, encoding) = read_request(request).await?;
+
+    let Request::VerifyEpochChange(boxed) = request else {
+        return Err(invalid_request_type(&request, encoding));
+    };
+    let (receipt, expectations) = *boxed;
+
+    let res = verify_receipt(
+        &state.metrics,
+        &receipt,
+        "epoch_change",
+        EPOCH_CHANGE_ID,
+        EpochChangeOutput::from_bytes,
+        |output| expectations.check(output),
+    )
+    .map_err(|err| err.with_encoding(encoding))?;
+
+    info!(
+        "Epoch change verification result: failed expectations {:?}",
+        res.failed_expectations
+    );
+    verification_response::<_, JsonEpochChangeOutput>(encoding, &res)
+}
+
+async fn evm_calldata(request: axum::extract::Request) -> Result<impl IntoResponse, ApiError> {
+    let (request, encoding) = read_request(request).await?;
+
+    let Request::ExportEvmCalldata(receipt) = request else {
+        return Err(invalid_request_type(&request, encoding));
+    };
+
+    info!("Start exporting receipt for the EVM verifier");
+    let export = evm_export(&receipt).map_err(|err| err.with_encoding(encoding))?;
+    let res = encoding
+        .encode_as::<_, JsonEvmExport>(&export)
+        .map_err(|err| ApiError::from_error(&*err).with_encoding(encoding))?;
+
+    response(encoding, res)
+}
+
+/// Exports a receipt for the EVM verifier. Its program is found from the
+/// image ID of its claim, and it is verified against it.
+fn evm_export(receipt: &Receipt) -> Result<EvmExport, ApiError> {
+    let output = JournalOutput::of_receipt(receipt).map_err(|err| ApiError::from_error(&err))?;
+    let verifier_calldata = VerifierCalldata::new(receipt, output.image_id())
+        .map_err(|err| ApiError::from_error(&err))?;
+    receipt
+        .verify(output.image_id())
+        .map_err(|err| ApiError::new(ErrorCode::InvalidReceipt, err))?;
+
+    Ok(EvmExport {
+        calldata: verifier_calldata.to_bytes(),
+        verifier_calldata,
+        output: output.abi_encode(),
+    })
+}
+
+async fn submit_job(
+    State(state): State<ServerState>,
+    params: Result<Query<ProveParams>, QueryRejection>,
+    request: axum::extract::Request,
+) -> Result<impl IntoResponse, ApiError> {
+    let (request, encoding) = read_request(request).await?;
+    let request = check_request(request, params, encoding).await?;
+
+    let submission = state
+        .jobs
+        .submit(request)
+        .map_err(|err| ApiError::from(err).with_encoding(encoding))?;
+
+    encoded_response(encoding, &submission)
+}
+
+async fn job_status(
+    State(state): State<ServerState>,
+    Path(id): Path<JobId>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, ApiError> {
+    let encoding = Encoding::of_response(&headers);
+    let status = state
+        .jobs
+        .status(id)
+        .map_err(|err| ApiError::from(err).with_encoding(encoding))?;
+
+    encoded_response(encoding, &status)
+}
+
+async fn job_receipt(
+    State(state): State<ServerState>,
+    Path(id): Path<JobId>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, ApiError> {
+    let encoding = Encoding::of_response(&headers);
+    let receipt = state
+        .jobs
+        .receipt(id)
+        .map_err(|err| ApiError::from(err).with_encoding(encoding))?;
+
+    match state.jobs.cache_status(id) {
+        Some(cache_status) => cached_receipt_response(encoding, &receipt, cache_status),
+        None => receipt_response(encoding, &receipt),
+    }
+}
+
+async fn cancel_job(
+    State(state): State<ServerState>,
+    Path(id): Path<JobId>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, ApiError> {
+    let cancel_token = headers
+        .get(CANCEL_TOKEN_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    state
+        .jobs
+        .cancel(id, cancel_token)
+        .map_err(|err| ApiError::from(err).with_encoding(Encoding::of_response(&headers)))?;
+
+    Ok(StatusCode::OK)
+}
+
+fn encoded_response<T: Serialize>(
+    encoding: Encoding,
+    value: &T,
+) -> Result<Response<Body>, ApiError> {
+    let bytes = encoding
+        .encode(value)
+        .map_err(|err| ApiError::from_error(&*err).with_encoding(encoding))?;
+
+    response(encoding, bytes)
+}
+
+fn response(encoding: Encoding, body: Vec<u8>) -> Result<Response<Body>, ApiError> {
+    encoding
+        .response(body)
+        .map_err(|err| ApiError::from_error(&err).with_encoding(encoding))
+}
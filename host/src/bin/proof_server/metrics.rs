@@ -0,0 +1,305 @@
+//! Prometheus metrics of the proof server, exported on `/metrics`.
+
+use host::error::ErrorCode;
+use host::types::Request;
+use prometheus_client::encoding::text::encode;
+use prometheus_client::encoding::EncodeLabelSet;
+use prometheus_client::metrics::counter::Counter;
+use prometheus_client::metrics::family::Family;
+use prometheus_client::metrics::gauge::Gauge;
+use prometheus_client::metrics::histogram::{exponential_buckets, Histogram};
+use prometheus_client::registry::Registry;
+use std::time::Duration;
+
+/// Content type of the encoded metrics.
+pub const METRICS_CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct ProofLabels {
+    program: &'static str,
+    proving_mode: String,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct PathLabels {
+    path: String,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct ResponseLabels {
+    path: String,
+    status: String,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct VerificationLabels {
+    program: &'static str,
+    outcome: &'static str,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct ErrorLabels {
+    code: &'static str,
+}
+
+type HistogramFamily<L> = Family<L, Histogram, fn() -> Histogram>;
+
+fn duration_histogram() -> Histogram {
+    // From 1 second to about 2 hours
+    Histogram::new(exponential_buckets(1.0, 2.0, 14))
+}
+
+fn cycles_histogram() -> Histogram {
+    // From 64K to about 1G cycles
+    Histogram::new(exponential_buckets(65536.0, 2.0, 15))
+}
+
+fn size_histogram() -> Histogram {
+    // From 256 bytes to 64MB
+    Histogram::new(exponential_buckets(256.0, 4.0, 10))
+}
+
+/// Metrics of the proof server.
+pub struct Metrics {
+    registry: Registry,
+    proving_duration: HistogramFamily<ProofLabels>,
+    proving_cycles: HistogramFamily<ProofLabels>,
+    request_size: HistogramFamily<PathLabels>,
+    response_size: HistogramFamily<PathLabels>,
+    responses: Family<ResponseLabels, Counter>,
+    verifications: Family<VerificationLabels, Counter>,
+    errors: Family<ErrorLabels, Counter>,
+    queue_depth: Gauge,
+    busy_workers: Gauge,
+}
+
+impl Metrics {
+    /// Creates a new `Metrics`, with all metrics registered.
+    pub fn new() -> Self {
+        let mut registry = Registry::with_prefix("proof_server");
+
+        let proving_duration = HistogramFamily::new_with_constructor(duration_histogram as _);
+        registry.register(
+            "proving_duration_seconds",
+            "Duration of the proof generations",
+            proving_duration.clone(),
+        );
+        let proving_cycles = HistogramFamily::new_with_constructor(cycles_histogram as _);
+        registry.register(
+            "proving_cycles",
+            "Total cycles of the proven sessions",
+            proving_cycles.clone(),
+        );
+        let request_size = HistogramFamily::new_with_constructor(size_histogram as _);
+        registry.register(
+            "request_size_bytes",
+            "Size of the request bodies",
+            request_size.clone(),
+        );
+        let response_size = HistogramFamily::new_with_constructor(size_histogram as _);
+        registry.register(
+            "response_size_bytes",
+            "Size of the response bodies",
+            response_size.clone(),
+        );
+        let responses = Family::default();
+        registry.register("responses", "Responses by HTTP status", responses.clone());
+        let verifications = Family::default();
+        registry.register(
+            "verifications",
+            "Outcomes of the receipt verifications",
+            verifications.clone(),
+        );
+        let errors = Family::default();
+        registry.register("errors", "Errors answered by code", errors.clone());
+        let queue_depth = Gauge::default();
+        registry.register(
+            "queue_depth",
+            "Number of jobs waiting for a worker",
+            queue_depth.clone(),
+        );
+        let busy_workers = Gauge::default();
+        registry.register(
+            "busy_workers",
+            "Number of workers generating a proof",
+            busy_workers.clone(),
+        );
+
+        Self {
+            registry,
+            proving_duration,
+            proving_cycles,
+            request_size,
+            response_size,
+            responses,
+            verifications,
+            errors,
+            queue_depth,
+            busy_workers,
+        }
+    }
+
+    /// Records a successful proof generation.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The proving request.
+    /// * `duration` - The time spent proving.
+    /// * `total_cycles` - The total cycles of the proven session.
+    pub fn record_proof(&self, request: &Request, duration: Duration, total_cycles: u64) {
+        let Some(labels) = proof_labels(request) else {
+            return;
+        };
+
+        self.proving_duration
+            .get_or_create(&labels)
+            .observe(duration.as_secs_f64());
+        self.proving_cycles
+            .get_or_create(&labels)
+            .observe(total_cycles as f64);
+    }
+
+    /// Records a request and its response.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the endpoint.
+    /// * `request_size` - The size of the request body.
+    /// * `response_size` - The size of the response body.
+    /// * `status` - The HTTP status of the response.
+    /// * `error_code` - The code of the error answered, if any.
+    pub fn record_response(
+        &self,
+        path: &str,
+        request_size: u64,
+        response_size: u64,
+        status: u16,
+        error_code: Option<ErrorCode>,
+    ) {
+        let labels = PathLabels {
+            path: path.to_string(),
+        };
+        self.request_size
+            .get_or_create(&labels)
+            .observe(request_size as f64);
+        self.response_size
+            .get_or_create(&labels)
+            .observe(response_size as f64);
+        self.responses
+            .get_or_create(&ResponseLabels {
+                path: labels.path,
+                status: status.to_string(),
+            })
+            .inc();
+
+        if let Some(code) = error_code {
+            self.errors
+                .get_or_create(&ErrorLabels {
+                    code: code.as_str(),
+                })
+                .inc();
+        }
+    }
+
+    /// Records the outcome of a receipt verification.
+    ///
+    /// # Arguments
+    ///
+    /// * `program` - The program the receipt was verified against.
+    /// * `is_valid` - Whether the receipt is valid.
+    pub fn record_verification(&self, program: &'static str, is_valid: bool) {
+        self.verifications
+            .get_or_create(&VerificationLabels {
+                program,
+                outcome: if is_valid { "valid" } else { "invalid" },
+            })
+            .inc();
+    }
+
+    /// Encodes the metrics in the Prometheus text format.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue_depth` - The current number of jobs waiting for a worker.
+    /// * `busy_workers` - The current number of workers generating a proof.
+    ///
+    /// # Returns
+    ///
+    /// The encoded metrics.
+    pub fn encode(
+        &self,
+        queue_depth: usize,
+        busy_workers: usize,
+    ) -> Result<String, std::fmt::Error> {
+        self.queue_depth.set(queue_depth as i64);
+        self.busy_workers.set(busy_workers as i64);
+
+        let mut buffer = String::new();
+        encode(&mut buffer, &self.registry)?;
+
+        Ok(buffer)
+    }
+}
+
+fn proof_labels(request: &Request) -> Option<ProofLabels> {
+    let (program, proving_mode) = match request {
+        Request::ProveInclusion(boxed) => ("inclusion", boxed.0),
+        Request::ProveEpochChange(boxed) => ("epoch_change", boxed.0),
+        _ => return None,
+    };
+
+    Some(ProofLabels {
+        program,
+        proving_mode: String::from(proving_mode),
+    })
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Metrics;
+    use host::error::ErrorCode;
+    use host::types::{EpochChangeData, ProvingMode, Request};
+    use std::time::Duration;
+
+    #[test]
+    fn test_metrics() {
+        let metrics = Metrics::new();
+        let request = Request::ProveEpochChange(Box::new((
+            ProvingMode::SNARK,
+            EpochChangeData {
+                trusted_state: vec![],
+                epoch_change_proof: vec![],
+            },
+        )));
+
+        metrics.record_proof(&request, Duration::from_secs(3), 1 << 20);
+        metrics.record_response("/epoch/proof", 100, 2000, 200, None);
+        metrics.record_response(
+            "/inclusion/verify",
+            100,
+            10,
+            422,
+            Some(ErrorCode::InvalidReceipt),
+        );
+        metrics.record_verification("inclusion", false);
+
+        let encoded = metrics.encode(2, 1).unwrap();
+        for line in [
+            r#"proof_server_proving_duration_seconds_count{program="epoch_change",proving_mode="SNARK"} 1"#,
+            r#"proof_server_proving_cycles_sum{program="epoch_change",proving_mode="SNARK"} 1048576.0"#,
+            r#"proof_server_responses_total{path="/inclusion/verify",status="422"} 1"#,
+            r#"proof_server_errors_total{code="invalid_receipt"} 1"#,
+            r#"proof_server_verifications_total{program="inclusion",outcome="invalid"} 1"#,
+            "proof_server_queue_depth 2",
+            "proof_server_busy_workers 1",
+        ] {
+            assert!(encoded.contains(line), "{line} not found in {encoded}");
+        }
+    }
+}
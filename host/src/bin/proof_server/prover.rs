@@ -0,0 +1,309 @@
+//! Proving of the requests received by the proof server.
+//!
+//! Proving is CPU bound and can take several minutes, so it never runs on
+//! the async runtime. The [`ProverPool`] runs it on blocking threads and
+//! bounds the number of proofs generated concurrently.
+
+use crate::metrics::Metrics;
+use anyhow::anyhow;
+use aptos_guests::{EPOCH_CHANGE_ELF, INCLUSION_ELF};
+use host::error::LightClientError;
+use host::native::{verify_epoch_change, verify_inclusion};
+use host::types::{EpochChangeData, InclusionData, ProvingMode, Request};
+use host::{epoch_change, inclusion};
+use risc0_zkvm::{default_prover, ExecutorEnv, ProverOpts, Receipt, VerifierContext};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::Semaphore;
+use tokio::task::spawn_blocking;
+
+/// Function generating the proof of a proving request.
+pub type ProveFn = Arc<dyn Fn(&Request) -> anyhow::Result<GeneratedProof> + Send + Sync>;
+
+/// A proof generated for a proving request.
+pub struct GeneratedProof {
+    /// The receipt of the proof.
+    pub receipt: Receipt,
+    /// The total number of cycles of the proven session.
+    pub total_cycles: u64,
+}
+
+/// A pool of blocking workers generating proofs, with at most `size`
+/// proofs generated at the same time.
+pub struct ProverPool {
+    size: usize,
+    permits: Arc<Semaphore>,
+    prove: ProveFn,
+    metrics: Arc<Metrics>,
+}
+
+impl ProverPool {
+    /// Creates a new `ProverPool`.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The maximum number of proofs generated concurrently.
+    /// * `prove` - The function generating the proof of a request.
+    /// * `metrics` - The metrics the proofs are recorded in.
+    ///
+    /// # Returns
+    ///
+    /// The `ProverPool`.
+    pub fn new(size: usize, prove: ProveFn, metrics: Arc<Metrics>) -> Self {
+        let size = size.max(1);
+
+        Self {
+            size,
+            permits: Arc::new(Semaphore::new(size)),
+            prove,
+            metrics,
+        }
+    }
+
+    /// Returns the maximum number of proofs generated concurrently.
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the number of workers that are not generating a proof.
+    pub fn available(&self) -> usize {
+        self.permits.available_permits()
+    }
+
+    /// Proves a request on a blocking thread, waiting for a worker to be
+    /// available first.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The proving request.
+    ///
+    /// # Returns
+    ///
+    /// The `Receipt` of the proof.
+    pub async fn prove(&self, request: Request) -> anyhow::Result<Receipt> {
+        let permit = self.permits.clone().acquire_owned().await?;
+        let prove = self.prove.clone();
+        let metrics = self.metrics.clone();
+
+        // The permit is moved to the blocking task, so that the worker is
+        // only released once the proof is done even if the caller is gone.
+        spawn_blocking(move || {
+            let _permit = permit;
+            let start = Instant::now();
+            let proof = prove(&request)?;
+            metrics.record_proof(&request, start.elapsed(), proof.total_cycles);
+
+            Ok(proof.receipt)
+        })
+        .await
+        .map_err(|err| LightClientError::ProvingError {
+            program: "prover-pool".to_string(),
+            source: err.into(),
+        })?
+    }
+}
+
+/// Generates the proof for a proving request. This call is blocking and
+/// can take several minutes.
+///
+/// # Arguments
+///
+/// * `request` - The proving request, either `Request::ProveInclusion` or `Request::ProveEpochChange`.
+///
+/// # Returns
+///
+/// The `GeneratedProof`.
+pub fn prove_request(request: &Request) -> anyhow::Result<GeneratedProof> {
+    match request {
+        Request::ProveInclusion(boxed) => {
+            let (proving_mode, inclusion_data) = boxed.as_ref();
+            let InclusionData {
+                sparse_merkle_proof_assets,
+                transaction_proof_assets,
+                validator_verifier_assets,
+            } = inclusion_data;
+            let env = inclusion::generate_stdin(
+                sparse_merkle_proof_assets,
+                transaction_proof_assets,
+                validator_verifier_assets,
+            );
+
+            prove(env, INCLUSION_ELF, proving_mode)
+                .map_err(|err| proving_error("inclusion", err, verify_inclusion(inclusion_data)))
+        }
+        Request::ProveEpochChange(boxed) => {
+            let (proving_mode, epoch_change_data) = boxed.as_ref();
+            let EpochChangeData {
+                trusted_state,
+                epoch_change_proof,
+            } = epoch_change_data;
+            let env = epoch_change::generate_stdin(trusted_state, epoch_change_proof);
+
+            prove(env, EPOCH_CHANGE_ELF, proving_mode).map_err(|err| {
+                proving_error("epoch-change", err, verify_epoch_change(epoch_change_data))
+            })
+        }
+        _ => Err(anyhow!("Request {} is not a proving request", request)),
+    }
+}
+
+/// Checks the inputs of a proving request natively, so that invalid
+/// requests are rejected with a precise error before reaching the prover.
+/// Requests that are not proving requests pass the check. This call is
+/// blocking.
+///
+/// # Arguments
+///
+/// * `request` - The proving request.
+pub fn preflight(request: &Request) -> Result<(), LightClientError> {
+    match request {
+        Request::ProveInclusion(boxed) => {
+            verify_inclusion(&boxed.1)?;
+        }
+        Request::ProveEpochChange(boxed) => {
+            verify_epoch_change(&boxed.1)?;
+        }
+        _ => (),
+    }
+
+    Ok(())
+}
+
+/// Wraps an error of the prover. The prover fails when the program panics
+/// on invalid inputs, so the inputs are checked natively to return the
+/// typed error of the check they fail rather than a bare prover failure.
+fn proving_error<T>(
+    program: &str,
+    err: anyhow::Error,
+    native_result: Result<T, LightClientError>,
+) -> anyhow::Error {
+    match native_result {
+        Err(native_err) => native_err.into(),
+        Ok(_) => LightClientError::ProvingError {
+            program: program.to_string(),
+            source: err.into(),
+        }
+        .into(),
+    }
+}
+
+fn prove(
+    env: ExecutorEnv<'_>,
+    elf: &[u8],
+    proving_mode: &ProvingMode,
+) -> anyhow::Result<GeneratedProof> {
+    let prover_client = default_prover();
+    let proof = if proving_mode == &ProvingMode::SNARK {
+        prover_client.prove_with_ctx(
+            env,
+            &VerifierContext::default(),
+            elf,
+            &ProverOpts::groth16(),
+        )
+    } else {
+        prover_client.prove(env, elf)
+    }?;
+
+    Ok(GeneratedProof {
+        receipt: proof.receipt,
+        total_cycles: proof.stats.total_cycles,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{preflight, GeneratedProof, ProverPool};
+    use crate::metrics::Metrics;
+    use aptos_guests::EPOCH_CHANGE_ID;
+    use host::error::ErrorCode;
+    use host::types::{EpochChangeData, EpochChangeExpectations, ProvingMode, Request};
+    use risc0_zkvm::{FakeReceipt, InnerReceipt, Receipt, ReceiptClaim};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_prover_pool() {
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+
+        let pool = {
+            let running = running.clone();
+            let max_running = max_running.clone();
+            Arc::new(ProverPool::new(
+                2,
+                Arc::new(move |_| {
+                    let current = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_running.fetch_max(current, Ordering::SeqCst);
+                    std::thread::sleep(Duration::from_millis(50));
+                    running.fetch_sub(1, Ordering::SeqCst);
+
+                    let journal = vec![current as u8];
+                    Ok(GeneratedProof {
+                        receipt: Receipt::new(
+                            InnerReceipt::Fake(FakeReceipt::new(ReceiptClaim::ok(
+                                EPOCH_CHANGE_ID,
+                                journal.clone(),
+                            ))),
+                            journal,
+                        ),
+                        total_cycles: 0,
+                    })
+                }),
+                Arc::new(Metrics::new()),
+            ))
+        };
+        assert_eq!(pool.available(), 2);
+
+        let handles = (0..6)
+            .map(|_| {
+                let pool = pool.clone();
+                let request = Request::VerifyEpochChange(Box::new((
+                    Receipt::new(
+                        InnerReceipt::Fake(FakeReceipt::new(ReceiptClaim::ok(
+                            EPOCH_CHANGE_ID,
+                            vec![],
+                        ))),
+                        vec![],
+                    ),
+                    EpochChangeExpectations::default(),
+                )));
+                tokio::spawn(async move { pool.prove(request).await })
+            })
+            .collect::<Vec<_>>();
+
+        // The runtime stays responsive while proofs are generated
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(pool.available(), 0);
+
+        for handle in handles {
+            handle.await.unwrap().unwrap();
+        }
+        assert_eq!(max_running.load(Ordering::SeqCst), 2);
+        assert_eq!(pool.available(), 2);
+    }
+
+    #[test]
+    fn test_preflight() {
+        let request = Request::ProveEpochChange(Box::new((
+            ProvingMode::STARK,
+            EpochChangeData {
+                // Unknown variant of trusted state
+                trusted_state: vec![2; 64],
+                epoch_change_proof: vec![],
+            },
+        )));
+        let err = preflight(&request).unwrap_err();
+        assert_eq!(ErrorCode::of_error(&err), ErrorCode::MalformedProof);
+
+        // Only proving requests are checked
+        let request = Request::VerifyEpochChange(Box::new((
+            Receipt::new(
+                InnerReceipt::Fake(FakeReceipt::new(ReceiptClaim::ok(EPOCH_CHANGE_ID, vec![]))),
+                vec![],
+            ),
+            EpochChangeExpectations::default(),
+        )));
+        assert!(preflight(&request).is_ok());
+    }
+}
@@ -1,9 +1,14 @@
 use crate::error::LightClientError;
+use crate::native::verify_epoch_change;
+use crate::types::EpochChangeData;
+use aptos_guests::EPOCH_CHANGE_ELF;
 use aptos_lc_core::types::output::EpochChangeOutput;
 use risc0_zkvm::{ExecutorEnv, Prover, Receipt};
-use aptos_guests::EPOCH_CHANGE_ELF;
 
-pub fn generate_stdin<'a>(current_trusted_state: &'a [u8], epoch_change_proof: &'a [u8]) -> ExecutorEnv<'a> {
+pub fn generate_stdin<'a>(
+    current_trusted_state: &'a [u8],
+    epoch_change_proof: &'a [u8],
+) -> ExecutorEnv<'a> {
     ExecutorEnv::builder()
         .write(&current_trusted_state.to_vec())
         .unwrap()
@@ -13,17 +18,25 @@ pub fn generate_stdin<'a>(current_trusted_state: &'a [u8], epoch_change_proof: &
         .unwrap()
 }
 
+/// Proves the ratcheting of a trusted state with an epoch change proof. With
+/// `preflight`, the inputs are first checked natively by
+/// [`crate::native::verify_epoch_change`] and invalid ones are rejected with
+/// the error of the failing check before the prover is invoked.
 #[allow(dead_code)]
 pub fn prove_epoch_change(
     client: &dyn Prover,
     trusted_state: &[u8],
     epoch_change_proof: &[u8],
+    preflight: bool,
 ) -> Result<(Receipt, EpochChangeOutput), LightClientError> {
+    if preflight {
+        verify_epoch_change(&EpochChangeData {
+            trusted_state: trusted_state.to_vec(),
+            epoch_change_proof: epoch_change_proof.to_vec(),
+        })?;
+    }
 
-    let env = generate_stdin(
-        trusted_state,
-        epoch_change_proof,
-    );
+    let env = generate_stdin(trusted_state, epoch_change_proof);
 
     let mut proof =
         client
@@ -33,14 +46,13 @@ pub fn prove_epoch_change(
                 source: err.into(),
             })?;
 
-    // Read output.
-    let output: EpochChangeOutput = proof.receipt.journal.decode().map_err(|err| LightClientError::DecodeError {
-        program: "prove-epoch-change".to_string(),
-        source: err.into(),
+    // Read output, committed with its stable encoding.
+    let output = EpochChangeOutput::from_bytes(&proof.receipt.journal.bytes).map_err(|err| {
+        LightClientError::DecodeError {
+            program: "prove-epoch-change".to_string(),
+            source: err.into(),
+        }
     })?;
 
-    Ok((
-        proof.receipt,
-        output,
-    ))
-}
\ No newline at end of file
+    Ok((proof.receipt, output))
+}
@@ -0,0 +1,173 @@
+use crate::error::LightClientError;
+use aptos_guests::{EPOCH_CHANGE_CHAIN_ELF, EPOCH_CHANGE_CHAIN_ID};
+use aptos_lc_core::types::output::EpochChangeChainOutput;
+use risc0_zkvm::{ExecutorEnv, Prover, ProverOpts, Receipt};
+
+/// Generates the input of the epoch change chain program.
+///
+/// # Arguments
+///
+/// * `prev` - The previous receipt of the chain and its decoded journal, if any.
+/// * `current_trusted_state` - The trusted state at the end of the previous link, or the
+///   initial trusted state when starting a new chain.
+/// * `epoch_change_proof` - The epoch change proof to ratchet the trusted state with.
+///
+/// # Returns
+///
+/// The `ExecutorEnv` to run the program with.
+pub fn generate_stdin<'a>(
+    prev: Option<(&Receipt, &EpochChangeChainOutput)>,
+    current_trusted_state: &'a [u8],
+    epoch_change_proof: &'a [u8],
+) -> ExecutorEnv<'a> {
+    let mut builder = ExecutorEnv::builder();
+
+    builder
+        .write(&EPOCH_CHANGE_CHAIN_ID)
+        .unwrap()
+        .write(&prev.map(|(_, output)| output))
+        .unwrap()
+        .write(&current_trusted_state.to_vec())
+        .unwrap()
+        .write(&epoch_change_proof.to_vec())
+        .unwrap();
+
+    if let Some((receipt, _)) = prev {
+        builder.add_assumption(receipt.clone());
+    }
+
+    builder.build().unwrap()
+}
+
+/// Proves a new link of the epoch change chain. Receipts are proven as
+/// succinct receipts so that their size does not grow with the chain.
+fn prove_epoch_change_chain(
+    client: &dyn Prover,
+    prev_receipt: Option<&Receipt>,
+    trusted_state: &[u8],
+    epoch_change_proof: &[u8],
+) -> Result<(Receipt, EpochChangeChainOutput), LightClientError> {
+    let prev_output = prev_receipt
+        .map(|receipt| {
+            receipt
+                .journal
+                .decode::<EpochChangeChainOutput>()
+                .map_err(|err| LightClientError::DecodeError {
+                    program: "prove-epoch-change-chain".to_string(),
+                    source: err.into(),
+                })
+        })
+        .transpose()?;
+
+    let env = generate_stdin(
+        prev_receipt.zip(prev_output.as_ref()),
+        trusted_state,
+        epoch_change_proof,
+    );
+
+    let proof = client
+        .prove_with_opts(env, EPOCH_CHANGE_CHAIN_ELF, &ProverOpts::succinct())
+        .map_err(|err| LightClientError::ProvingError {
+            program: "prove-epoch-change-chain".to_string(),
+            source: err.into(),
+        })?;
+
+    // Read output.
+    let output: EpochChangeChainOutput =
+        proof
+            .receipt
+            .journal
+            .decode()
+            .map_err(|err| LightClientError::DecodeError {
+                program: "prove-epoch-change-chain".to_string(),
+                source: err.into(),
+            })?;
+
+    Ok((proof.receipt, output))
+}
+
+/// Starts a new epoch change chain from the given trusted state.
+///
+/// # Arguments
+///
+/// * `client` - The prover to use.
+/// * `trusted_state` - The initial trusted state of the chain.
+/// * `epoch_change_proof` - The epoch change proof to ratchet the trusted state with.
+///
+/// # Returns
+///
+/// The receipt of the first link of the chain and its journal.
+pub fn prove_epoch_change_chain_start(
+    client: &dyn Prover,
+    trusted_state: &[u8],
+    epoch_change_proof: &[u8],
+) -> Result<(Receipt, EpochChangeChainOutput), LightClientError> {
+    prove_epoch_change_chain(client, None, trusted_state, epoch_change_proof)
+}
+
+/// Extends an existing epoch change chain with a new epoch change proof.
+/// The resulting receipt attests to the whole chain, from the start of
+/// `prev_receipt` to the end of `epoch_change_proof`.
+///
+/// # Arguments
+///
+/// * `client` - The prover to use.
+/// * `prev_receipt` - The receipt of the last link of the chain.
+/// * `trusted_state` - The trusted state at the end of the chain.
+/// * `epoch_change_proof` - The epoch change proof to ratchet the trusted state with.
+///
+/// # Returns
+///
+/// The receipt of the new link of the chain and its journal.
+pub fn prove_epoch_change_chain_extend(
+    client: &dyn Prover,
+    prev_receipt: &Receipt,
+    trusted_state: &[u8],
+    epoch_change_proof: &[u8],
+) -> Result<(Receipt, EpochChangeChainOutput), LightClientError> {
+    prove_epoch_change_chain(
+        client,
+        Some(prev_receipt),
+        trusted_state,
+        epoch_change_proof,
+    )
+}
+
+/// Verifies a receipt of the epoch change chain program, checking that
+/// the image ID committed to in its journal is the one of the program.
+///
+/// # Arguments
+///
+/// * `receipt` - The receipt to verify.
+///
+/// # Returns
+///
+/// The journal of the receipt.
+pub fn verify_epoch_change_chain(
+    receipt: &Receipt,
+) -> Result<EpochChangeChainOutput, LightClientError> {
+    receipt
+        .verify(EPOCH_CHANGE_CHAIN_ID)
+        .map_err(|err| LightClientError::VerificationError {
+            program: "verify-epoch-change-chain".to_string(),
+            source: err.into(),
+        })?;
+
+    let output: EpochChangeChainOutput =
+        receipt
+            .journal
+            .decode()
+            .map_err(|err| LightClientError::DecodeError {
+                program: "verify-epoch-change-chain".to_string(),
+                source: err.into(),
+            })?;
+
+    if output.image_id != EPOCH_CHANGE_CHAIN_ID {
+        return Err(LightClientError::VerificationError {
+            program: "verify-epoch-change-chain".to_string(),
+            source: "unexpected image ID committed to by the chain".into(),
+        });
+    }
+
+    Ok(output)
+}
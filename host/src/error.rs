@@ -1,5 +1,8 @@
-use thiserror::Error;
+use crate::types::{JobId, JobStatus};
 use aptos_lc_core::crypto::hash::HashValue;
+use aptos_lc_core::types::error::{TypesError, VerifyError};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum LightClientError {
@@ -15,6 +18,12 @@ pub enum LightClientError {
         #[source]
         source: Box<dyn std::error::Error + Sync + Send>,
     },
+    #[error("[{program}] Failed to verify receipt: {source}")]
+    VerificationError {
+        program: String,
+        #[source]
+        source: Box<dyn std::error::Error + Sync + Send>,
+    },
 }
 
 /// Error type for the client.
@@ -50,9 +59,300 @@ pub enum ClientError {
     Ratchet {
         source: Box<dyn std::error::Error + Sync + Send>,
     },
+    #[error("Error while accessing the light client store: {source}")]
+    Store {
+        #[source]
+        source: StoreError,
+    },
     #[error("Internal error: {source}")]
     Internal {
         #[source]
         source: Box<dyn std::error::Error + Sync + Send>,
     },
-}
\ No newline at end of file
+}
+
+/// Error type for the light client stores.
+#[derive(Debug, Error)]
+pub enum StoreError {
+    #[error("Error while accessing {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Error with the database: {source}")]
+    Database {
+        #[source]
+        source: Box<dyn std::error::Error + Sync + Send>,
+    },
+    #[error("Error while (de)serializing a record: {source}")]
+    Serialization {
+        #[source]
+        source: Box<dyn std::error::Error + Sync + Send>,
+    },
+    #[error(
+        "Record for epoch {epoch} can not be appended after the record for epoch {latest_epoch}"
+    )]
+    OutOfOrder { latest_epoch: u64, epoch: u64 },
+}
+
+/// Error type for the proving jobs of the proof server.
+#[derive(Debug, Error)]
+pub enum JobError {
+    #[error("The job queue is full")]
+    QueueFull,
+    #[error("Job {id} does not exist")]
+    NotFound { id: JobId },
+    #[error("Job {id} is {status:?}")]
+    InvalidStatus { id: JobId, status: JobStatus },
+    #[error("Request {request} is not a proving request")]
+    InvalidRequest { request: String },
+    #[error("Invalid cancel token for job {id}")]
+    InvalidCancelToken { id: JobId },
+    #[error("Error while accessing {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Error while (de)serializing a receipt: {source}")]
+    Serialization {
+        #[source]
+        source: Box<dyn std::error::Error + Sync + Send>,
+    },
+}
+
+/// Error type for the export of receipts to on-chain verifiers.
+#[derive(Debug, Error)]
+pub enum EvmError {
+    #[error("A {kind} receipt can not be verified on-chain, only Groth16 receipts can")]
+    UnsupportedReceipt { kind: String },
+    #[error("Error while decoding the journal: {source}")]
+    Journal {
+        #[source]
+        source: TypesError,
+    },
+    #[error("The receipt does not claim the image ID of a light client program")]
+    UnknownProgram,
+}
+
+/// Machine-readable code of an error returned by the proof server.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// The request could not be decoded.
+    MalformedRequest,
+    /// The request is not of the type expected by the endpoint.
+    InvalidRequestType,
+    /// A proof or one of its inputs could not be deserialized.
+    MalformedProof,
+    /// The signatures of a ledger info do not reach the voting power quorum.
+    QuorumNotMet,
+    /// The signatures of a ledger info are invalid.
+    InvalidSignature,
+    /// A proof is well-formed but does not verify.
+    ProofVerificationFailed,
+    /// A receipt does not verify against the image ID of its program.
+    InvalidReceipt,
+    /// A receipt is not of a kind supported by the endpoint, e.g. a STARK
+    /// receipt sent for on-chain verification.
+    UnsupportedReceipt,
+    /// The prover failed while generating a valid proof.
+    ProverFailed,
+    /// The job queue is full.
+    QueueFull,
+    /// The job does not exist.
+    JobNotFound,
+    /// The job is not in a status allowing the request.
+    InvalidJobStatus,
+    /// The cancel token of a job is missing or does not match the one
+    /// returned on its submission.
+    InvalidCancelToken,
+    /// No backend could handle the request.
+    BackendUnavailable,
+    /// Any other error.
+    Internal,
+}
+
+impl ErrorCode {
+    /// Returns the code of an error, looking through its chain of sources
+    /// for the most specific known error.
+    ///
+    /// # Arguments
+    ///
+    /// * `err` - The error to classify.
+    ///
+    /// # Returns
+    ///
+    /// The `ErrorCode` of the error.
+    pub fn of_error(err: &(dyn std::error::Error + 'static)) -> Self {
+        let mut fallback = ErrorCode::Internal;
+        let mut current = Some(err);
+
+        while let Some(err) = current {
+            if let Some(err) = err.downcast_ref::<VerifyError>() {
+                return match err {
+                    VerifyError::TooLittleVotingPower { .. } => ErrorCode::QuorumNotMet,
+                    VerifyError::InconsistentBlockInfo => ErrorCode::ProofVerificationFailed,
+                    VerifyError::InvalidBitVec => ErrorCode::MalformedProof,
+                    _ => ErrorCode::InvalidSignature,
+                };
+            }
+            if let Some(err) = err.downcast_ref::<EvmError>() {
+                return match err {
+                    EvmError::UnsupportedReceipt { .. } => ErrorCode::UnsupportedReceipt,
+                    EvmError::Journal { .. } | EvmError::UnknownProgram => {
+                        ErrorCode::InvalidReceipt
+                    }
+                };
+            }
+            if err.downcast_ref::<TypesError>().is_some() {
+                return ErrorCode::MalformedProof;
+            }
+            // Keep the outermost light client error in case no more
+            // specific error is found
+            if fallback == ErrorCode::Internal {
+                match err.downcast_ref::<LightClientError>() {
+                    Some(LightClientError::ProvingError { .. }) => {
+                        fallback = ErrorCode::ProverFailed
+                    }
+                    Some(LightClientError::VerificationError { .. }) => {
+                        fallback = ErrorCode::ProofVerificationFailed
+                    }
+                    _ => (),
+                }
+            }
+
+            current = err.source();
+        }
+
+        fallback
+    }
+
+    /// Returns the name of the code, as it is serialized.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::MalformedRequest => "malformed_request",
+            ErrorCode::InvalidRequestType => "invalid_request_type",
+            ErrorCode::MalformedProof => "malformed_proof",
+            ErrorCode::QuorumNotMet => "quorum_not_met",
+            ErrorCode::InvalidSignature => "invalid_signature",
+            ErrorCode::ProofVerificationFailed => "proof_verification_failed",
+            ErrorCode::InvalidReceipt => "invalid_receipt",
+            ErrorCode::UnsupportedReceipt => "unsupported_receipt",
+            ErrorCode::ProverFailed => "prover_failed",
+            ErrorCode::QueueFull => "queue_full",
+            ErrorCode::JobNotFound => "job_not_found",
+            ErrorCode::InvalidJobStatus => "invalid_job_status",
+            ErrorCode::InvalidCancelToken => "invalid_cancel_token",
+            ErrorCode::BackendUnavailable => "backend_unavailable",
+            ErrorCode::Internal => "internal",
+        }
+    }
+}
+
+/// Body of the error responses of the proof server.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ErrorResponse {
+    pub code: ErrorCode,
+    pub message: String,
+}
+
+impl ErrorResponse {
+    /// Creates a new `ErrorResponse`.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The code of the error.
+    /// * `message` - The human-readable description of the error.
+    ///
+    /// # Returns
+    ///
+    /// The `ErrorResponse`.
+    pub fn new(code: ErrorCode, message: impl ToString) -> Self {
+        Self {
+            code,
+            message: message.to_string(),
+        }
+    }
+
+    /// Creates an `ErrorResponse` from an error, see [`ErrorCode::of_error`].
+    ///
+    /// # Arguments
+    ///
+    /// * `err` - The error.
+    ///
+    /// # Returns
+    ///
+    /// The `ErrorResponse`.
+    pub fn from_error(err: &(dyn std::error::Error + 'static)) -> Self {
+        Self::new(ErrorCode::of_error(err), err)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::{ErrorCode, EvmError, LightClientError};
+    use aptos_lc_core::types::error::{TypesError, VerifyError};
+
+    #[test]
+    fn test_error_code() {
+        // Native verification errors carry the errors of the core
+        let err = LightClientError::VerificationError {
+            program: "native-inclusion".to_string(),
+            source: Box::new(VerifyError::TooLittleVotingPower {
+                voting_power: 1,
+                expected_voting_power: 3,
+            }),
+        };
+        assert_eq!(ErrorCode::of_error(&err), ErrorCode::QuorumNotMet);
+
+        let err = LightClientError::VerificationError {
+            program: "native-epoch-change".to_string(),
+            source: Box::new(TypesError::InvalidLength {
+                structure: "HashValue".to_string(),
+                expected: 32,
+                actual: 3,
+            }),
+        };
+        assert_eq!(ErrorCode::of_error(&err), ErrorCode::MalformedProof);
+
+        let err = LightClientError::VerificationError {
+            program: "native-inclusion".to_string(),
+            source: anyhow::anyhow!("Root hashes do not match").into(),
+        };
+        assert_eq!(
+            ErrorCode::of_error(&err),
+            ErrorCode::ProofVerificationFailed
+        );
+
+        let err = LightClientError::ProvingError {
+            program: "inclusion".to_string(),
+            source: "Out of memory".into(),
+        };
+        assert_eq!(ErrorCode::of_error(&err), ErrorCode::ProverFailed);
+
+        // Journals of receipts are not proof inputs
+        let err = EvmError::Journal {
+            source: TypesError::InvalidLength {
+                structure: "InclusionOutput".to_string(),
+                expected: 217,
+                actual: 3,
+            },
+        };
+        assert_eq!(ErrorCode::of_error(&err), ErrorCode::InvalidReceipt);
+        assert_eq!(
+            ErrorCode::of_error(&EvmError::UnknownProgram),
+            ErrorCode::InvalidReceipt
+        );
+
+        let err = std::io::Error::new(std::io::ErrorKind::Other, "Disk full");
+        assert_eq!(ErrorCode::of_error(&err), ErrorCode::Internal);
+
+        // Metric labels match the serialized codes
+        assert_eq!(
+            serde_json::to_string(&ErrorCode::ProofVerificationFailed).unwrap(),
+            format!("\"{}\"", ErrorCode::ProofVerificationFailed.as_str())
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use crate::error::LightClientError;
+use crate::inclusion::{TransactionProofAssets, ValidatorVerifierAssets};
+use aptos_guests::EVENT_INCLUSION_ELF;
+use aptos_lc_core::types::output::EventInclusionOutput;
+use risc0_zkvm::{ExecutorEnv, Prover, Receipt};
+
+pub use aptos_lc_core::types::assets::EventProofAssets;
+
+/// Generates the inputs of the event inclusion program. The
+/// `transaction_index` of the `TransactionProofAssets` is the version of
+/// the transaction that emitted the event.
+pub fn generate_stdin<'a>(
+    event_proof_assets: &'a EventProofAssets,
+    transaction_proof_assets: &'a TransactionProofAssets,
+    validator_verifier_assets: &'a ValidatorVerifierAssets,
+) -> ExecutorEnv<'a> {
+    ExecutorEnv::builder()
+        .write(event_proof_assets.event())
+        .unwrap()
+        .write(event_proof_assets.event_index())
+        .unwrap()
+        .write(event_proof_assets.event_proof())
+        .unwrap()
+        .write(transaction_proof_assets.transaction())
+        .unwrap()
+        .write(transaction_proof_assets.transaction_index())
+        .unwrap()
+        .write(transaction_proof_assets.transaction_proof())
+        .unwrap()
+        .write(transaction_proof_assets.latest_li())
+        .unwrap()
+        .write(validator_verifier_assets.validator_verifier())
+        .unwrap()
+        .build()
+        .unwrap()
+}
+
+/// Proves that an event was emitted by the transaction committed at a
+/// given version.
+///
+/// # Arguments
+///
+/// * `client` - The prover.
+/// * `event_proof_assets` - The serialized `ContractEvent`, its index among
+///   the events of the transaction and its proof in the event accumulator
+///   of the `TransactionInfo`.
+/// * `transaction_proof_assets` - The `TransactionInfo` of the transaction,
+///   its version and its proof in the transaction accumulator of the signed
+///   ledger info.
+/// * `validator_verifier_assets` - The validator verifier that signed the
+///   ledger info.
+///
+/// # Returns
+///
+/// The receipt and the `EventInclusionOutput` it commits to.
+pub fn prove_event_inclusion(
+    client: &dyn Prover,
+    event_proof_assets: &EventProofAssets,
+    transaction_proof_assets: &TransactionProofAssets,
+    validator_verifier_assets: &ValidatorVerifierAssets,
+) -> Result<(Receipt, EventInclusionOutput), LightClientError> {
+    let env = generate_stdin(
+        event_proof_assets,
+        transaction_proof_assets,
+        validator_verifier_assets,
+    );
+
+    let proof =
+        client
+            .prove(env, EVENT_INCLUSION_ELF)
+            .map_err(|err| LightClientError::ProvingError {
+                program: "prove-event-inclusion".to_string(),
+                source: err.into(),
+            })?;
+
+    // Read output, committed with its stable encoding.
+    let output = EventInclusionOutput::from_bytes(&proof.receipt.journal.bytes).map_err(|err| {
+        LightClientError::DecodeError {
+            program: "prove-event-inclusion".to_string(),
+            source: err.into(),
+        }
+    })?;
+
+    Ok((proof.receipt, output))
+}
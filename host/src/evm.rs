@@ -0,0 +1,449 @@
+//! Export of receipts for on-chain verification on EVM chains.
+//!
+//! Only Groth16 receipts, generated with [`crate::types::ProvingMode::SNARK`],
+//! can be verified on-chain. Contracts call
+//! `verify(bytes seal, bytes32 imageId, bytes32 journalDigest)` on the
+//! RISC Zero verifier with the calldata of [`VerifierCalldata`], then read
+//! the fields of the journal, whose ABI encoding is given by
+//! [`JournalOutput::abi_encode`].
+
+use crate::error::EvmError;
+use aptos_guests::{
+    ACCUMULATOR_CONSISTENCY_ID, EPOCH_CHANGE_ID, EVENT_INCLUSION_ID, INCLUSION_ID,
+    TRANSACTION_EXECUTION_ID, TRANSACTION_RANGE_ID,
+};
+use aptos_lc_core::types::output::{
+    AccumulatorConsistencyOutput, EpochChangeOutput, EventInclusionOutput, InclusionOutput,
+    TransactionExecutionOutput, TransactionRangeOutput, EVENT_INCLUSION_OUTPUT_FIXED_SIZE,
+    TRANSACTION_EXECUTION_OUTPUT_FIXED_SIZE,
+};
+use getset::Getters;
+use risc0_zkvm::sha::{self, Digestible};
+use risc0_zkvm::{InnerReceipt, Receipt};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Selector of `verify(bytes,bytes32,bytes32)` on the RISC Zero verifier.
+pub const VERIFY_SELECTOR: [u8; 4] = [0xab, 0x75, 0x0e, 0x75];
+
+/// Length in bytes of an ABI word.
+const WORD_SIZE: usize = 32;
+
+/// Arguments of a call to the RISC Zero verifier.
+#[derive(Clone, Debug, Eq, PartialEq, Getters, Serialize, Deserialize)]
+#[getset(get = "pub")]
+pub struct VerifierCalldata {
+    /// Groth16 seal, prefixed with the selector of the verifier it targets.
+    seal: Vec<u8>,
+    image_id: [u8; 32],
+    journal_digest: [u8; 32],
+}
+
+impl VerifierCalldata {
+    /// Creates the `VerifierCalldata` of a Groth16 receipt. The receipt is
+    /// not verified.
+    ///
+    /// # Arguments
+    ///
+    /// * `receipt` - The Groth16 receipt.
+    /// * `image_id` - The image ID of the program that generated the receipt.
+    ///
+    /// # Returns
+    ///
+    /// The `VerifierCalldata`, or an error if the receipt is not a Groth16
+    /// receipt.
+    pub fn new(receipt: &Receipt, image_id: [u32; 8]) -> Result<Self, EvmError> {
+        let InnerReceipt::Groth16(groth16) = &receipt.inner else {
+            return Err(EvmError::UnsupportedReceipt {
+                kind: receipt_kind(&receipt.inner).to_string(),
+            });
+        };
+
+        Ok(Self {
+            seal: [
+                &groth16.verifier_parameters.as_bytes()[..4],
+                groth16.seal.as_slice(),
+            ]
+            .concat(),
+            image_id: image_id_bytes(image_id),
+            journal_digest: Sha256::digest(&receipt.journal.bytes).into(),
+        })
+    }
+
+    /// ABI-encodes the call to `verify(bytes,bytes32,bytes32)`.
+    ///
+    /// # Returns
+    ///
+    /// The calldata, starting with [`VERIFY_SELECTOR`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = VERIFY_SELECTOR.to_vec();
+        // The seal is dynamic, so the head holds its offset
+        bytes.extend_from_slice(&abi_uint(3 * WORD_SIZE as u64));
+        bytes.extend_from_slice(&self.image_id);
+        bytes.extend_from_slice(&self.journal_digest);
+        bytes.extend_from_slice(&abi_uint(self.seal.len() as u64));
+        bytes.extend_from_slice(&self.seal);
+        bytes.resize(bytes.len() + padding(self.seal.len()), 0);
+
+        bytes
+    }
+}
+
+/// Output of a light client program, decoded from the journal of a
+/// receipt.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum JournalOutput {
+    Inclusion(InclusionOutput),
+    EpochChange(EpochChangeOutput),
+    TransactionExecution(TransactionExecutionOutput),
+    EventInclusion(EventInclusionOutput),
+    TransactionRange(TransactionRangeOutput),
+    AccumulatorConsistency(AccumulatorConsistencyOutput),
+}
+
+impl JournalOutput {
+    /// Decodes the journal of a receipt of a light client program. Several
+    /// encodings share a length, so the program is told apart by its image
+    /// ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `image_id` - The image ID of the program.
+    /// * `journal` - The bytes of the journal.
+    ///
+    /// # Returns
+    ///
+    /// The `JournalOutput`.
+    pub fn decode(image_id: [u32; 8], journal: &[u8]) -> Result<Self, EvmError> {
+        let res = match image_id {
+            INCLUSION_ID => InclusionOutput::from_bytes(journal).map(Self::Inclusion),
+            EPOCH_CHANGE_ID => EpochChangeOutput::from_bytes(journal).map(Self::EpochChange),
+            TRANSACTION_EXECUTION_ID => {
+                TransactionExecutionOutput::from_bytes(journal).map(Self::TransactionExecution)
+            }
+            EVENT_INCLUSION_ID => {
+                EventInclusionOutput::from_bytes(journal).map(Self::EventInclusion)
+            }
+            TRANSACTION_RANGE_ID => {
+                TransactionRangeOutput::from_bytes(journal).map(Self::TransactionRange)
+            }
+            ACCUMULATOR_CONSISTENCY_ID => {
+                AccumulatorConsistencyOutput::from_bytes(journal).map(Self::AccumulatorConsistency)
+            }
+            _ => return Err(EvmError::UnknownProgram),
+        };
+
+        res.map_err(|source| EvmError::Journal { source })
+    }
+
+    /// Decodes the journal of a receipt, for the image ID its claim commits
+    /// to. The receipt is not verified.
+    ///
+    /// # Arguments
+    ///
+    /// * `receipt` - The receipt.
+    ///
+    /// # Returns
+    ///
+    /// The `JournalOutput`.
+    pub fn of_receipt(receipt: &Receipt) -> Result<Self, EvmError> {
+        let claim = receipt.claim().map_err(|_| EvmError::UnknownProgram)?;
+        let pre = &claim.as_value().map_err(|_| EvmError::UnknownProgram)?.pre;
+        let image_id = <[u32; 8]>::try_from(pre.digest::<sha::Impl>().as_words())
+            .map_err(|_| EvmError::UnknownProgram)?;
+
+        Self::decode(image_id, &receipt.journal.bytes)
+    }
+
+    /// Returns the image ID of the program committing to the output.
+    pub const fn image_id(&self) -> [u32; 8] {
+        match self {
+            JournalOutput::Inclusion(_) => INCLUSION_ID,
+            JournalOutput::EpochChange(_) => EPOCH_CHANGE_ID,
+            JournalOutput::TransactionExecution(_) => TRANSACTION_EXECUTION_ID,
+            JournalOutput::EventInclusion(_) => EVENT_INCLUSION_ID,
+            JournalOutput::TransactionRange(_) => TRANSACTION_RANGE_ID,
+            JournalOutput::AccumulatorConsistency(_) => ACCUMULATOR_CONSISTENCY_ID,
+        }
+    }
+
+    /// ABI-encodes the fields of the output, integers as `uint64` first,
+    /// then hashes as `bytes32`. The execution status of a transaction and
+    /// an event, which have a variable length, come last as `bytes`, in the
+    /// encoding of the journal. E.g. an inclusion output is decoded in
+    /// Solidity with
+    /// `abi.decode(data, (uint64, uint64, uint64, bytes32, bytes32, bytes32, bytes32, bytes32, bytes32))`,
+    /// and a transaction execution output with
+    /// `abi.decode(data, (uint64, uint64, bytes32, bytes32, bytes32, bytes32, bytes))`.
+    ///
+    /// # Returns
+    ///
+    /// The ABI encoding of the output.
+    pub fn abi_encode(&self) -> Vec<u8> {
+        let (integers, hashes, tail) = match self {
+            JournalOutput::Inclusion(output) => (
+                vec![output.epoch, output.version, output.timestamp_usecs],
+                vec![
+                    output.current_block_id,
+                    output.transaction_accumulator_hash,
+                    output.validator_verifier_hash,
+                    output.reconstructed_root_hash,
+                    output.key,
+                    output.leaf_value_hash,
+                ],
+                None,
+            ),
+            JournalOutput::EpochChange(output) => (
+                vec![
+                    output.start_epoch,
+                    output.end_epoch,
+                    output.version,
+                    output.timestamp_usecs,
+                ],
+                vec![
+                    output.block_id,
+                    output.transaction_accumulator_hash,
+                    output.prev_epoch_validator_verifier_hash,
+                    output.validator_verifier_hash,
+                    output.new_waypoint,
+                ],
+                None,
+            ),
+            JournalOutput::TransactionExecution(output) => (
+                vec![output.version, output.gas_used],
+                vec![
+                    output.validator_verifier_hash,
+                    output.current_block_id,
+                    output.transaction_accumulator_hash,
+                    output.transaction_hash,
+                ],
+                Some(
+                    output
+                        .to_bytes()
+                        .split_off(TRANSACTION_EXECUTION_OUTPUT_FIXED_SIZE),
+                ),
+            ),
+            JournalOutput::EventInclusion(output) => (
+                vec![output.version, output.event_index],
+                vec![
+                    output.validator_verifier_hash,
+                    output.current_block_id,
+                    output.transaction_accumulator_hash,
+                ],
+                Some(
+                    output
+                        .to_bytes()
+                        .split_off(EVENT_INCLUSION_OUTPUT_FIXED_SIZE),
+                ),
+            ),
+            JournalOutput::TransactionRange(output) => (
+                vec![output.first_version, output.last_version],
+                vec![
+                    output.validator_verifier_hash,
+                    output.current_block_id,
+                    output.transaction_accumulator_hash,
+                    output.transaction_infos_digest,
+                ],
+                None,
+            ),
+            JournalOutput::AccumulatorConsistency(output) => (
+                vec![output.prev_version, output.version],
+                vec![
+                    output.prev_validator_verifier_hash,
+                    output.prev_transaction_accumulator_hash,
+                    output.validator_verifier_hash,
+                    output.transaction_accumulator_hash,
+                ],
+                None,
+            ),
+        };
+
+        let mut bytes: Vec<u8> = integers
+            .into_iter()
+            .map(abi_uint)
+            .chain(hashes)
+            .flatten()
+            .collect();
+        if let Some(tail) = tail {
+            // The tail is dynamic, so the head holds its offset
+            bytes.extend_from_slice(&abi_uint((bytes.len() + WORD_SIZE) as u64));
+            bytes.extend_from_slice(&abi_uint(tail.len() as u64));
+            bytes.extend_from_slice(&tail);
+            bytes.resize(bytes.len() + padding(tail.len()), 0);
+        }
+
+        bytes
+    }
+}
+
+/// Response of the proof server to an EVM export request.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct EvmExport {
+    /// The ABI-encoded call to the verifier, see [`VerifierCalldata::to_bytes`].
+    pub calldata: Vec<u8>,
+    pub verifier_calldata: VerifierCalldata,
+    /// The ABI-encoded output, see [`JournalOutput::abi_encode`].
+    pub output: Vec<u8>,
+}
+
+/// Returns the bytes of an image ID, as the verifier expects them.
+fn image_id_bytes(image_id: [u32; 8]) -> [u8; 32] {
+    let mut bytes = [0; 32];
+    for (chunk, word) in bytes.chunks_exact_mut(4).zip(image_id) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+
+    bytes
+}
+
+/// Encodes an integer in an ABI word.
+fn abi_uint(value: u64) -> [u8; WORD_SIZE] {
+    let mut word = [0; WORD_SIZE];
+    word[WORD_SIZE - 8..].copy_from_slice(&value.to_be_bytes());
+
+    word
+}
+
+/// Returns the number of zero bytes padding `len` bytes to a whole number
+/// of ABI words.
+const fn padding(len: usize) -> usize {
+    (WORD_SIZE - len % WORD_SIZE) % WORD_SIZE
+}
+
+fn receipt_kind(inner: &InnerReceipt) -> &'static str {
+    match inner {
+        InnerReceipt::Composite(_) => "composite",
+        InnerReceipt::Succinct(_) => "succinct",
+        InnerReceipt::Groth16(_) => "groth16",
+        InnerReceipt::Fake(_) => "fake",
+        _ => "unknown",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::EvmError;
+    use crate::evm::{JournalOutput, VerifierCalldata, VERIFY_SELECTOR};
+    use aptos_guests::{
+        ACCUMULATOR_CONSISTENCY_ID, EPOCH_CHANGE_ID, INCLUSION_ID, TRANSACTION_EXECUTION_ID,
+        TRANSACTION_RANGE_ID,
+    };
+    use aptos_lc_core::types::output::{
+        AccumulatorConsistencyOutput, EpochChangeOutput, InclusionOutput,
+        TransactionExecutionOutput,
+    };
+    use risc0_zkvm::{FakeReceipt, InnerReceipt, Receipt, ReceiptClaim};
+
+    #[test]
+    fn test_verifier_calldata() {
+        let calldata = VerifierCalldata {
+            seal: vec![7; 40],
+            image_id: [1; 32],
+            journal_digest: [2; 32],
+        }
+        .to_bytes();
+
+        assert_eq!(calldata.len(), 4 + 5 * 32 + 64);
+        assert_eq!(calldata[..4], VERIFY_SELECTOR);
+        // Offset of the seal, after the three words of the head
+        assert_eq!(calldata[4 + 31], 96);
+        assert_eq!(calldata[36..68], [1; 32]);
+        assert_eq!(calldata[68..100], [2; 32]);
+        assert_eq!(calldata[100 + 31], 40);
+        assert_eq!(calldata[132..172], [7; 40]);
+        assert!(calldata[172..].iter().all(|byte| *byte == 0));
+
+        // Only Groth16 receipts can be verified on-chain
+        let journal = InclusionOutput::default().to_bytes();
+        let receipt = Receipt::new(
+            InnerReceipt::Fake(FakeReceipt::new(ReceiptClaim::ok(
+                INCLUSION_ID,
+                journal.clone(),
+            ))),
+            journal,
+        );
+        assert!(matches!(
+            VerifierCalldata::new(&receipt, INCLUSION_ID),
+            Err(EvmError::UnsupportedReceipt { kind }) if kind == "fake"
+        ));
+    }
+
+    #[test]
+    fn test_journal_output() {
+        let output = EpochChangeOutput {
+            start_epoch: 1,
+            end_epoch: 3,
+            new_waypoint: [4; 32],
+            ..Default::default()
+        };
+        let decoded = JournalOutput::decode(EPOCH_CHANGE_ID, &output.to_bytes()).unwrap();
+        assert_eq!(decoded, JournalOutput::EpochChange(output));
+        assert_eq!(decoded.image_id(), EPOCH_CHANGE_ID);
+
+        let encoded = decoded.abi_encode();
+        assert_eq!(encoded.len(), 9 * 32);
+        assert_eq!(encoded[31], 1);
+        assert_eq!(encoded[63], 3);
+        assert_eq!(encoded[8 * 32..], [4; 32]);
+
+        let output = InclusionOutput {
+            leaf_value_hash: [5; 32],
+            ..Default::default()
+        };
+        let decoded = JournalOutput::decode(INCLUSION_ID, &output.to_bytes()).unwrap();
+        assert_eq!(decoded.image_id(), INCLUSION_ID);
+        assert_eq!(decoded.abi_encode()[8 * 32..], [5; 32]);
+
+        // The execution status is appended as dynamic bytes
+        let output = TransactionExecutionOutput {
+            version: 7,
+            gas_used: 11,
+            ..Default::default()
+        };
+        let journal = output.to_bytes();
+        let receipt = Receipt::new(
+            InnerReceipt::Fake(FakeReceipt::new(ReceiptClaim::ok(
+                TRANSACTION_EXECUTION_ID,
+                journal.clone(),
+            ))),
+            journal,
+        );
+        let decoded = JournalOutput::of_receipt(&receipt).unwrap();
+        assert_eq!(decoded, JournalOutput::TransactionExecution(output));
+
+        let encoded = decoded.abi_encode();
+        assert_eq!(encoded.len(), 9 * 32);
+        assert_eq!(encoded[31], 7);
+        assert_eq!(encoded[63], 11);
+        // Offset of the status, after the seven words of the head
+        assert_eq!(encoded[6 * 32 + 31], 224);
+        assert_eq!(encoded[7 * 32 + 31], 1);
+        assert_eq!(encoded[8 * 32..], [0; 32]);
+
+        // Encodings of the same length are told apart by the image ID
+        let output = AccumulatorConsistencyOutput {
+            prev_version: 2,
+            version: 5,
+            ..Default::default()
+        };
+        let decoded =
+            JournalOutput::decode(ACCUMULATOR_CONSISTENCY_ID, &output.to_bytes()).unwrap();
+        assert_eq!(
+            decoded,
+            JournalOutput::AccumulatorConsistency(output.clone())
+        );
+        assert!(matches!(
+            JournalOutput::decode(TRANSACTION_RANGE_ID, &output.to_bytes()).unwrap(),
+            JournalOutput::TransactionRange(_)
+        ));
+
+        assert!(matches!(
+            JournalOutput::decode(INCLUSION_ID, &[1, 2, 3]),
+            Err(EvmError::Journal { .. })
+        ));
+        assert!(matches!(
+            JournalOutput::decode([0; 8], &output.to_bytes()),
+            Err(EvmError::UnknownProgram)
+        ));
+    }
+}
@@ -1,127 +1,335 @@
 use crate::error::LightClientError;
-use aptos_lc_core::types::output::InclusionOutput;
-use getset::Getters;
+use crate::native::verify_inclusion;
+use crate::types::InclusionData;
+use aptos_guests::{
+    BATCH_INCLUSION_ELF, COMPOSED_INCLUSION_ELF, COMPOSED_INCLUSION_ID, EPOCH_CHANGE_CHAIN_ID,
+    INCLUSION_ELF,
+};
+use aptos_lc_core::types::output::{
+    BatchInclusionOutput, ComposedInclusionOutput, EpochChangeChainOutput, InclusionOutput,
+};
 use risc0_zkvm::{ExecutorEnv, Prover, Receipt};
-use serde::{Deserialize, Serialize};
-use aptos_guests::INCLUSION_ELF;
 
+pub use aptos_lc_core::types::assets::{
+    SparseMerkleProofAssets, TransactionProofAssets, ValidatorVerifierAssets,
+};
 
-#[derive(Clone, Debug, Getters, Serialize, Deserialize)]
-#[getset(get = "pub")]
-pub struct SparseMerkleProofAssets {
-    sparse_merkle_proof: Vec<u8>,
-    leaf_key: [u8; 32],
-    leaf_hash: [u8; 32],
+pub fn generate_stdin<'a>(
+    sparse_merkle_proof_assets: &'a SparseMerkleProofAssets,
+    transaction_proof_assets: &'a TransactionProofAssets,
+    validator_verifier_assets: &'a ValidatorVerifierAssets,
+) -> ExecutorEnv<'a> {
+    ExecutorEnv::builder()
+        .write(sparse_merkle_proof_assets.sparse_merkle_proof())
+        .unwrap()
+        .write(sparse_merkle_proof_assets.leaf_key())
+        .unwrap()
+        .write(sparse_merkle_proof_assets.leaf_hash())
+        .unwrap()
+        .write(transaction_proof_assets.transaction())
+        .unwrap()
+        .write(transaction_proof_assets.transaction_index())
+        .unwrap()
+        .write(transaction_proof_assets.transaction_proof())
+        .unwrap()
+        .write(transaction_proof_assets.latest_li())
+        .unwrap()
+        .write(validator_verifier_assets.validator_verifier())
+        .unwrap()
+        .build()
+        .unwrap()
 }
 
-impl SparseMerkleProofAssets {
-    pub const fn new(
-        sparse_merkle_proof: Vec<u8>,
-        leaf_key: [u8; 32],
-        leaf_hash: [u8; 32],
-    ) -> SparseMerkleProofAssets {
-        SparseMerkleProofAssets {
-            sparse_merkle_proof,
-            leaf_key,
-            leaf_hash,
-        }
+/// Proves the inclusion of a leaf in the state checkpoint of a transaction. With
+/// `preflight`, the inputs are first checked natively by
+/// [`crate::native::verify_inclusion`] and invalid ones are rejected with the
+/// error of the failing check before the prover is invoked.
+#[allow(dead_code)]
+pub fn prove_inclusion(
+    client: &dyn Prover,
+    sparse_merkle_proof_assets: &SparseMerkleProofAssets,
+    transaction_proof_assets: &TransactionProofAssets,
+    validator_verifier_assets: &ValidatorVerifierAssets,
+    preflight: bool,
+) -> Result<(Receipt, InclusionOutput), LightClientError> {
+    if preflight {
+        verify_inclusion(&InclusionData {
+            sparse_merkle_proof_assets: sparse_merkle_proof_assets.clone(),
+            transaction_proof_assets: transaction_proof_assets.clone(),
+            validator_verifier_assets: validator_verifier_assets.clone(),
+        })?;
     }
-}
 
-#[derive(Clone, Debug, Getters, Serialize, Deserialize)]
-#[getset(get = "pub")]
-pub struct TransactionProofAssets {
-    transaction: Vec<u8>,
-    transaction_index: u64,
-    transaction_proof: Vec<u8>,
-    latest_li: Vec<u8>,
-}
+    let env = generate_stdin(
+        sparse_merkle_proof_assets,
+        transaction_proof_assets,
+        validator_verifier_assets,
+    );
 
-impl TransactionProofAssets {
-    pub const fn new(
-        transaction: Vec<u8>,
-        transaction_index: u64,
-        transaction_proof: Vec<u8>,
-        latest_li: Vec<u8>,
-    ) -> TransactionProofAssets {
-        TransactionProofAssets {
-            transaction,
-            transaction_index,
-            transaction_proof,
-            latest_li,
+    let proof = client
+        .prove(env, INCLUSION_ELF)
+        .map_err(|err| LightClientError::ProvingError {
+            program: "prove-merkle-inclusion".to_string(),
+            source: err.into(),
+        })?;
+
+    // Read output, committed with its stable encoding.
+    let output = InclusionOutput::from_bytes(&proof.receipt.journal.bytes).map_err(|err| {
+        LightClientError::DecodeError {
+            program: "prove-merkle-inclusion".to_string(),
+            source: err.into(),
         }
-    }
+    })?;
+
+    Ok((proof.receipt, output))
 }
 
-#[derive(Clone, Debug, Getters, Serialize, Deserialize)]
-#[getset(get = "pub")]
-pub struct ValidatorVerifierAssets {
-    validator_verifier: Vec<u8>,
+pub fn generate_batch_stdin<'a>(
+    sparse_merkle_proof_assets: &[SparseMerkleProofAssets],
+    transaction_proof_assets: &'a TransactionProofAssets,
+    validator_verifier_assets: &'a ValidatorVerifierAssets,
+) -> ExecutorEnv<'a> {
+    let sparse_merkle_proofs: Vec<(&Vec<u8>, &[u8; 32], &[u8; 32])> = sparse_merkle_proof_assets
+        .iter()
+        .map(|assets| {
+            (
+                assets.sparse_merkle_proof(),
+                assets.leaf_key(),
+                assets.leaf_hash(),
+            )
+        })
+        .collect();
+
+    ExecutorEnv::builder()
+        .write(&sparse_merkle_proofs)
+        .unwrap()
+        .write(transaction_proof_assets.transaction())
+        .unwrap()
+        .write(transaction_proof_assets.transaction_index())
+        .unwrap()
+        .write(transaction_proof_assets.transaction_proof())
+        .unwrap()
+        .write(transaction_proof_assets.latest_li())
+        .unwrap()
+        .write(validator_verifier_assets.validator_verifier())
+        .unwrap()
+        .build()
+        .unwrap()
 }
 
-impl ValidatorVerifierAssets {
-    pub const fn new(validator_verifier: Vec<u8>) -> ValidatorVerifierAssets {
-        ValidatorVerifierAssets { validator_verifier }
-    }
+/// Proves the inclusion of several leaves in the state checkpoint of the same transaction,
+/// verifying the signatures over the `LedgerInfoWithSignatures` only once.
+#[allow(dead_code)]
+pub fn prove_batch_inclusion(
+    client: &dyn Prover,
+    sparse_merkle_proof_assets: &[SparseMerkleProofAssets],
+    transaction_proof_assets: &TransactionProofAssets,
+    validator_verifier_assets: &ValidatorVerifierAssets,
+) -> Result<(Receipt, BatchInclusionOutput), LightClientError> {
+    let env = generate_batch_stdin(
+        sparse_merkle_proof_assets,
+        transaction_proof_assets,
+        validator_verifier_assets,
+    );
+
+    let proof =
+        client
+            .prove(env, BATCH_INCLUSION_ELF)
+            .map_err(|err| LightClientError::ProvingError {
+                program: "prove-merkle-batch-inclusion".to_string(),
+                source: err.into(),
+            })?;
+
+    // Read output.
+    let output: BatchInclusionOutput =
+        proof
+            .receipt
+            .journal
+            .decode()
+            .map_err(|err| LightClientError::DecodeError {
+                program: "prove-merkle-batch-inclusion".to_string(),
+                source: err.into(),
+            })?;
+
+    Ok((proof.receipt, output))
 }
 
-pub fn generate_stdin<'a>(
+pub fn generate_composed_stdin<'a>(
+    epoch_change_receipt: &Receipt,
+    epoch_change_output: &EpochChangeChainOutput,
     sparse_merkle_proof_assets: &'a SparseMerkleProofAssets,
     transaction_proof_assets: &'a TransactionProofAssets,
     validator_verifier_assets: &'a ValidatorVerifierAssets,
 ) -> ExecutorEnv<'a> {
     ExecutorEnv::builder()
-        .write(&sparse_merkle_proof_assets.sparse_merkle_proof)
+        .add_assumption(epoch_change_receipt.clone())
+        .write(epoch_change_output)
+        .unwrap()
+        .write(sparse_merkle_proof_assets.sparse_merkle_proof())
         .unwrap()
-        .write(&sparse_merkle_proof_assets.leaf_key)
+        .write(sparse_merkle_proof_assets.leaf_key())
         .unwrap()
-        .write(&sparse_merkle_proof_assets.leaf_hash)
+        .write(sparse_merkle_proof_assets.leaf_hash())
         .unwrap()
-        .write(&transaction_proof_assets.transaction)
+        .write(transaction_proof_assets.transaction())
         .unwrap()
-        .write(&transaction_proof_assets.transaction_index)
+        .write(transaction_proof_assets.transaction_index())
         .unwrap()
-        .write(&transaction_proof_assets.transaction_proof)
+        .write(transaction_proof_assets.transaction_proof())
         .unwrap()
-        .write(&transaction_proof_assets.latest_li)
+        .write(transaction_proof_assets.latest_li())
         .unwrap()
-        .write(&validator_verifier_assets.validator_verifier)
+        .write(validator_verifier_assets.validator_verifier())
         .unwrap()
         .build()
         .unwrap()
 }
 
+/// Proves the inclusion of a leaf in a ledger signed by the validator set at the end of
+/// an epoch change chain. The receipt of the chain, generated by
+/// [`crate::epoch_change_chain`], is verified as an assumption so that the resulting
+/// receipt alone attests to the inclusion from the start of the chain. Such receipts
+/// have to be checked with [`verify_composed_inclusion`].
 #[allow(dead_code)]
-pub fn prove_inclusion(
+pub fn prove_composed_inclusion(
     client: &dyn Prover,
+    epoch_change_receipt: &Receipt,
     sparse_merkle_proof_assets: &SparseMerkleProofAssets,
     transaction_proof_assets: &TransactionProofAssets,
     validator_verifier_assets: &ValidatorVerifierAssets,
-) -> Result<(Receipt, InclusionOutput), LightClientError> {
+) -> Result<(Receipt, ComposedInclusionOutput), LightClientError> {
+    let epoch_change_output: EpochChangeChainOutput = epoch_change_receipt
+        .journal
+        .decode()
+        .map_err(|err| LightClientError::DecodeError {
+            program: "prove-merkle-composed-inclusion".to_string(),
+            source: err.into(),
+        })?;
 
-    let env = generate_stdin(
+    let env = generate_composed_stdin(
+        epoch_change_receipt,
+        &epoch_change_output,
         sparse_merkle_proof_assets,
         transaction_proof_assets,
         validator_verifier_assets,
     );
 
-    let proof =
-        client
-            .prove(env, INCLUSION_ELF)
-            .map_err(|err| LightClientError::ProvingError {
-                program: "prove-merkle-inclusion".to_string(),
+    let proof = client.prove(env, COMPOSED_INCLUSION_ELF).map_err(|err| {
+        LightClientError::ProvingError {
+            program: "prove-merkle-composed-inclusion".to_string(),
+            source: err.into(),
+        }
+    })?;
+
+    // Read output.
+    let output: ComposedInclusionOutput =
+        proof
+            .receipt
+            .journal
+            .decode()
+            .map_err(|err| LightClientError::DecodeError {
+                program: "prove-merkle-composed-inclusion".to_string(),
                 source: err.into(),
             })?;
 
-    // Read output.
-    let output: InclusionOutput = proof.receipt.journal.decode().map_err(|err| LightClientError::DecodeError {
-        program: "prove-merkle-inclusion".to_string(),
-        source: err.into(),
-    })?;
+    Ok((proof.receipt, output))
+}
+
+/// Verifies a receipt of the composed inclusion program, checking that the
+/// epoch change chain receipt it was composed with comes from the epoch
+/// change chain program.
+///
+/// The program verifies the epoch change chain receipt against the image
+/// ID committed to in its journal, as it can not embed the image IDs of the
+/// programs it is built with. Any program committing a journal shaped like
+/// an `EpochChangeChainOutput` could otherwise stand in for the chain.
+///
+/// # Arguments
+///
+/// * `receipt` - The receipt to verify.
+///
+/// # Returns
+///
+/// The journal of the receipt.
+pub fn verify_composed_inclusion(
+    receipt: &Receipt,
+) -> Result<ComposedInclusionOutput, LightClientError> {
+    receipt
+        .verify(COMPOSED_INCLUSION_ID)
+        .map_err(|err| LightClientError::VerificationError {
+            program: "verify-merkle-composed-inclusion".to_string(),
+            source: err.into(),
+        })?;
+
+    let output: ComposedInclusionOutput =
+        receipt
+            .journal
+            .decode()
+            .map_err(|err| LightClientError::DecodeError {
+                program: "verify-merkle-composed-inclusion".to_string(),
+                source: err.into(),
+            })?;
+
+    if output.epoch_change_image_id != EPOCH_CHANGE_CHAIN_ID {
+        return Err(LightClientError::VerificationError {
+            program: "verify-merkle-composed-inclusion".to_string(),
+            source: "unexpected epoch change chain image ID".into(),
+        });
+    }
 
-    Ok((
-        proof.receipt,
-        output,
-    ))
+    Ok(output)
 }
 
+#[cfg(test)]
+mod test {
+    use crate::error::LightClientError;
+    use crate::inclusion::verify_composed_inclusion;
+    use aptos_guests::{COMPOSED_INCLUSION_ID, EPOCH_CHANGE_CHAIN_ID};
+    use aptos_lc_core::types::output::ComposedInclusionOutput;
+    use risc0_zkvm::{FakeReceipt, InnerReceipt, Receipt, ReceiptClaim};
+    use serial_test::serial;
+
+    fn fake_receipt(output: &ComposedInclusionOutput) -> Receipt {
+        let journal: Vec<u8> = risc0_zkvm::serde::to_vec(output)
+            .unwrap()
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .collect();
+
+        Receipt::new(
+            InnerReceipt::Fake(FakeReceipt::new(ReceiptClaim::ok(
+                COMPOSED_INCLUSION_ID,
+                journal.clone(),
+            ))),
+            journal,
+        )
+    }
+
+    #[test]
+    #[serial(risc0_dev_mode)]
+    fn test_verify_composed_inclusion() {
+        // Fake receipts only verify in dev mode
+        std::env::set_var("RISC0_DEV_MODE", "1");
+
+        let output = ComposedInclusionOutput {
+            epoch_change_image_id: EPOCH_CHANGE_CHAIN_ID,
+            epoch: 2,
+            ..Default::default()
+        };
+        assert_eq!(
+            verify_composed_inclusion(&fake_receipt(&output)).unwrap(),
+            output
+        );
+
+        // A receipt composed with another program than the epoch change
+        // chain is rejected
+        let mut forged_output = output.clone();
+        forged_output.epoch_change_image_id[0] ^= 1;
+        match verify_composed_inclusion(&fake_receipt(&forged_output)) {
+            Err(LightClientError::VerificationError { source, .. }) => {
+                assert_eq!(source.to_string(), "unexpected epoch change chain image ID")
+            }
+            res => panic!("Expected image ID verification error, got {res:?}"),
+        }
+    }
+}
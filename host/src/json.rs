@@ -0,0 +1,702 @@
+//! JSON encoding of the proof server API.
+//!
+//! The proof server natively speaks BCS. For clients without a BCS
+//! implementation, every [`Request`] and response also has a JSON
+//! representation in which byte fields are `0x`-prefixed hex strings.
+//! Receipts are exchanged as the hex encoding of their BCS serialization,
+//! along with their journal.
+//!
+//! An epoch change proof request for example reads:
+//!
+//! ```json
+//! {
+//!   "type": "prove_epoch_change",
+//!   "proving_mode": "STARK",
+//!   "trusted_state": "0x...",
+//!   "epoch_change_proof": "0x..."
+//! }
+//! ```
+
+use crate::evm::EvmExport;
+use crate::inclusion::{SparseMerkleProofAssets, TransactionProofAssets, ValidatorVerifierAssets};
+use crate::types::{
+    EpochChangeData, EpochChangeExpectations, InclusionData, InclusionExpectations, ProvingMode,
+    Request, VerificationResponse,
+};
+use aptos_lc_core::types::output::{EpochChangeOutput, InclusionOutput};
+use risc0_zkvm::Receipt;
+use serde::{Deserialize, Serialize};
+
+/// Content type of BCS payloads.
+pub const BCS_CONTENT_TYPE: &str = "application/octet-stream";
+/// Content type of JSON payloads.
+pub const JSON_CONTENT_TYPE: &str = "application/json";
+
+/// JSON representation of a [`Request`].
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JsonRequest {
+    ProveInclusion {
+        proving_mode: ProvingMode,
+        sparse_merkle_proof_assets: JsonSparseMerkleProofAssets,
+        transaction_proof_assets: JsonTransactionProofAssets,
+        validator_verifier_assets: JsonValidatorVerifierAssets,
+    },
+    ProveEpochChange {
+        proving_mode: ProvingMode,
+        #[serde(with = "hex_bytes")]
+        trusted_state: Vec<u8>,
+        #[serde(with = "hex_bytes")]
+        epoch_change_proof: Vec<u8>,
+    },
+    VerifyInclusion {
+        #[serde(with = "hex_bytes")]
+        receipt: Vec<u8>,
+        #[serde(default)]
+        expectations: JsonInclusionExpectations,
+    },
+    VerifyEpochChange {
+        #[serde(with = "hex_bytes")]
+        receipt: Vec<u8>,
+        #[serde(default)]
+        expectations: JsonEpochChangeExpectations,
+    },
+    ExportEvmCalldata {
+        #[serde(with = "hex_bytes")]
+        receipt: Vec<u8>,
+    },
+}
+
+/// JSON representation of [`SparseMerkleProofAssets`].
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct JsonSparseMerkleProofAssets {
+    #[serde(with = "hex_bytes")]
+    pub sparse_merkle_proof: Vec<u8>,
+    #[serde(with = "hex_bytes")]
+    pub leaf_key: [u8; 32],
+    #[serde(with = "hex_bytes")]
+    pub leaf_hash: [u8; 32],
+}
+
+/// JSON representation of [`TransactionProofAssets`].
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct JsonTransactionProofAssets {
+    #[serde(with = "hex_bytes")]
+    pub transaction: Vec<u8>,
+    pub transaction_index: u64,
+    #[serde(with = "hex_bytes")]
+    pub transaction_proof: Vec<u8>,
+    #[serde(with = "hex_bytes")]
+    pub latest_li: Vec<u8>,
+}
+
+/// JSON representation of [`ValidatorVerifierAssets`].
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct JsonValidatorVerifierAssets {
+    #[serde(with = "hex_bytes")]
+    pub validator_verifier: Vec<u8>,
+}
+
+/// JSON representation of [`InclusionExpectations`]. Missing fields are
+/// not checked.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct JsonInclusionExpectations {
+    #[serde(
+        default,
+        with = "option_hex_bytes",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub validator_verifier_hash: Option<[u8; 32]>,
+    #[serde(
+        default,
+        with = "option_hex_bytes",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub key: Option<[u8; 32]>,
+    #[serde(
+        default,
+        with = "option_hex_bytes",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub leaf_value_hash: Option<[u8; 32]>,
+    #[serde(
+        default,
+        with = "option_hex_bytes",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub block_id: Option<[u8; 32]>,
+}
+
+impl From<&InclusionExpectations> for JsonInclusionExpectations {
+    fn from(expectations: &InclusionExpectations) -> Self {
+        Self {
+            validator_verifier_hash: expectations.validator_verifier_hash,
+            key: expectations.key,
+            leaf_value_hash: expectations.leaf_value_hash,
+            block_id: expectations.block_id,
+        }
+    }
+}
+
+impl From<JsonInclusionExpectations> for InclusionExpectations {
+    fn from(expectations: JsonInclusionExpectations) -> Self {
+        Self {
+            validator_verifier_hash: expectations.validator_verifier_hash,
+            key: expectations.key,
+            leaf_value_hash: expectations.leaf_value_hash,
+            block_id: expectations.block_id,
+        }
+    }
+}
+
+/// JSON representation of [`EpochChangeExpectations`]. Missing fields are
+/// not checked.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct JsonEpochChangeExpectations {
+    #[serde(
+        default,
+        with = "option_hex_bytes",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub prev_epoch_validator_verifier_hash: Option<[u8; 32]>,
+}
+
+impl From<&EpochChangeExpectations> for JsonEpochChangeExpectations {
+    fn from(expectations: &EpochChangeExpectations) -> Self {
+        Self {
+            prev_epoch_validator_verifier_hash: expectations.prev_epoch_validator_verifier_hash,
+        }
+    }
+}
+
+impl From<JsonEpochChangeExpectations> for EpochChangeExpectations {
+    fn from(expectations: JsonEpochChangeExpectations) -> Self {
+        Self {
+            prev_epoch_validator_verifier_hash: expectations.prev_epoch_validator_verifier_hash,
+        }
+    }
+}
+
+/// JSON representation of an [`InclusionOutput`].
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct JsonInclusionOutput {
+    pub epoch: u64,
+    pub version: u64,
+    pub timestamp_usecs: u64,
+    #[serde(with = "hex_bytes")]
+    pub current_block_id: [u8; 32],
+    #[serde(with = "hex_bytes")]
+    pub transaction_accumulator_hash: [u8; 32],
+    #[serde(with = "hex_bytes")]
+    pub validator_verifier_hash: [u8; 32],
+    #[serde(with = "hex_bytes")]
+    pub reconstructed_root_hash: [u8; 32],
+    #[serde(with = "hex_bytes")]
+    pub key: [u8; 32],
+    #[serde(with = "hex_bytes")]
+    pub leaf_value_hash: [u8; 32],
+}
+
+impl From<&InclusionOutput> for JsonInclusionOutput {
+    fn from(output: &InclusionOutput) -> Self {
+        Self {
+            epoch: output.epoch,
+            version: output.version,
+            timestamp_usecs: output.timestamp_usecs,
+            current_block_id: output.current_block_id,
+            transaction_accumulator_hash: output.transaction_accumulator_hash,
+            validator_verifier_hash: output.validator_verifier_hash,
+            reconstructed_root_hash: output.reconstructed_root_hash,
+            key: output.key,
+            leaf_value_hash: output.leaf_value_hash,
+        }
+    }
+}
+
+/// JSON representation of an [`EpochChangeOutput`].
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct JsonEpochChangeOutput {
+    pub start_epoch: u64,
+    pub end_epoch: u64,
+    pub version: u64,
+    pub timestamp_usecs: u64,
+    #[serde(with = "hex_bytes")]
+    pub block_id: [u8; 32],
+    #[serde(with = "hex_bytes")]
+    pub transaction_accumulator_hash: [u8; 32],
+    #[serde(with = "hex_bytes")]
+    pub prev_epoch_validator_verifier_hash: [u8; 32],
+    #[serde(with = "hex_bytes")]
+    pub validator_verifier_hash: [u8; 32],
+    #[serde(with = "hex_bytes")]
+    pub new_waypoint: [u8; 32],
+}
+
+impl From<&EpochChangeOutput> for JsonEpochChangeOutput {
+    fn from(output: &EpochChangeOutput) -> Self {
+        Self {
+            start_epoch: output.start_epoch,
+            end_epoch: output.end_epoch,
+            version: output.version,
+            timestamp_usecs: output.timestamp_usecs,
+            block_id: output.block_id,
+            transaction_accumulator_hash: output.transaction_accumulator_hash,
+            prev_epoch_validator_verifier_hash: output.prev_epoch_validator_verifier_hash,
+            validator_verifier_hash: output.validator_verifier_hash,
+            new_waypoint: output.new_waypoint,
+        }
+    }
+}
+
+/// JSON representation of a [`VerificationResponse`], with the output as
+/// a [`JsonInclusionOutput`] or a [`JsonEpochChangeOutput`].
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct JsonVerificationResponse<O> {
+    pub output: O,
+    pub failed_expectations: Vec<String>,
+}
+
+impl<'a, O, J: From<&'a O>> From<&'a VerificationResponse<O>> for JsonVerificationResponse<J> {
+    fn from(response: &'a VerificationResponse<O>) -> Self {
+        Self {
+            output: J::from(&response.output),
+            failed_expectations: response.failed_expectations.clone(),
+        }
+    }
+}
+
+/// JSON representation of an [`EvmExport`].
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct JsonEvmExport {
+    #[serde(with = "hex_bytes")]
+    pub calldata: Vec<u8>,
+    #[serde(with = "hex_bytes")]
+    pub seal: Vec<u8>,
+    #[serde(with = "hex_bytes")]
+    pub image_id: [u8; 32],
+    #[serde(with = "hex_bytes")]
+    pub journal_digest: [u8; 32],
+    #[serde(with = "hex_bytes")]
+    pub output: Vec<u8>,
+}
+
+impl From<&EvmExport> for JsonEvmExport {
+    fn from(export: &EvmExport) -> Self {
+        Self {
+            calldata: export.calldata.clone(),
+            seal: export.verifier_calldata.seal().clone(),
+            image_id: *export.verifier_calldata.image_id(),
+            journal_digest: *export.verifier_calldata.journal_digest(),
+            output: export.output.clone(),
+        }
+    }
+}
+
+/// JSON representation of a [`Receipt`].
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct JsonReceipt {
+    /// BCS serialization of the receipt.
+    #[serde(with = "hex_bytes")]
+    pub receipt: Vec<u8>,
+    /// Journal of the receipt.
+    #[serde(with = "hex_bytes")]
+    pub journal: Vec<u8>,
+}
+
+impl TryFrom<&Receipt> for JsonReceipt {
+    type Error = anyhow::Error;
+
+    fn try_from(receipt: &Receipt) -> Result<Self, Self::Error> {
+        Ok(Self {
+            receipt: bcs::to_bytes(receipt)?,
+            journal: receipt.journal.bytes.clone(),
+        })
+    }
+}
+
+impl TryFrom<&JsonReceipt> for Receipt {
+    type Error = anyhow::Error;
+
+    fn try_from(json_receipt: &JsonReceipt) -> Result<Self, Self::Error> {
+        let receipt: Receipt = bcs::from_bytes(&json_receipt.receipt)?;
+        if receipt.journal.bytes != json_receipt.journal {
+            return Err(anyhow::anyhow!("Journal does not match the receipt"));
+        }
+
+        Ok(receipt)
+    }
+}
+
+impl TryFrom<&Request> for JsonRequest {
+    type Error = anyhow::Error;
+
+    fn try_from(request: &Request) -> Result<Self, Self::Error> {
+        Ok(match request {
+            Request::ProveInclusion(boxed) => {
+                let (proving_mode, inclusion_data) = boxed.as_ref();
+                let InclusionData {
+                    sparse_merkle_proof_assets,
+                    transaction_proof_assets,
+                    validator_verifier_assets,
+                } = inclusion_data;
+
+                JsonRequest::ProveInclusion {
+                    proving_mode: *proving_mode,
+                    sparse_merkle_proof_assets: JsonSparseMerkleProofAssets {
+                        sparse_merkle_proof: sparse_merkle_proof_assets
+                            .sparse_merkle_proof()
+                            .clone(),
+                        leaf_key: *sparse_merkle_proof_assets.leaf_key(),
+                        leaf_hash: *sparse_merkle_proof_assets.leaf_hash(),
+                    },
+                    transaction_proof_assets: JsonTransactionProofAssets {
+                        transaction: transaction_proof_assets.transaction().clone(),
+                        transaction_index: *transaction_proof_assets.transaction_index(),
+                        transaction_proof: transaction_proof_assets.transaction_proof().clone(),
+                        latest_li: transaction_proof_assets.latest_li().clone(),
+                    },
+                    validator_verifier_assets: JsonValidatorVerifierAssets {
+                        validator_verifier: validator_verifier_assets.validator_verifier().clone(),
+                    },
+                }
+            }
+            Request::ProveEpochChange(boxed) => {
+                let (proving_mode, epoch_change_data) = boxed.as_ref();
+
+                JsonRequest::ProveEpochChange {
+                    proving_mode: *proving_mode,
+                    trusted_state: epoch_change_data.trusted_state.clone(),
+                    epoch_change_proof: epoch_change_data.epoch_change_proof.clone(),
+                }
+            }
+            Request::VerifyInclusion(boxed) => {
+                let (receipt, expectations) = boxed.as_ref();
+
+                JsonRequest::VerifyInclusion {
+                    receipt: bcs::to_bytes(receipt)?,
+                    expectations: expectations.into(),
+                }
+            }
+            Request::VerifyEpochChange(boxed) => {
+                let (receipt, expectations) = boxed.as_ref();
+
+                JsonRequest::VerifyEpochChange {
+                    receipt: bcs::to_bytes(receipt)?,
+                    expectations: expectations.into(),
+                }
+            }
+            Request::ExportEvmCalldata(receipt) => JsonRequest::ExportEvmCalldata {
+                receipt: bcs::to_bytes(receipt)?,
+            },
+        })
+    }
+}
+
+impl TryFrom<JsonRequest> for Request {
+    type Error = anyhow::Error;
+
+    fn try_from(json_request: JsonRequest) -> Result<Self, Self::Error> {
+        Ok(match json_request {
+            JsonRequest::ProveInclusion {
+                proving_mode,
+                sparse_merkle_proof_assets,
+                transaction_proof_assets,
+                validator_verifier_assets,
+            } => Request::ProveInclusion(Box::new((
+                proving_mode,
+                InclusionData {
+                    sparse_merkle_proof_assets: SparseMerkleProofAssets::new(
+                        sparse_merkle_proof_assets.sparse_merkle_proof,
+                        sparse_merkle_proof_assets.leaf_key,
+                        sparse_merkle_proof_assets.leaf_hash,
+                    ),
+                    transaction_proof_assets: TransactionProofAssets::new(
+                        transaction_proof_assets.transaction,
+                        transaction_proof_assets.transaction_index,
+                        transaction_proof_assets.transaction_proof,
+                        transaction_proof_assets.latest_li,
+                    ),
+                    validator_verifier_assets: ValidatorVerifierAssets::new(
+                        validator_verifier_assets.validator_verifier,
+                    ),
+                },
+            ))),
+            JsonRequest::ProveEpochChange {
+                proving_mode,
+                trusted_state,
+                epoch_change_proof,
+            } => Request::ProveEpochChange(Box::new((
+                proving_mode,
+                EpochChangeData {
+                    trusted_state,
+                    epoch_change_proof,
+                },
+            ))),
+            JsonRequest::VerifyInclusion {
+                receipt,
+                expectations,
+            } => Request::VerifyInclusion(Box::new((
+                bcs::from_bytes(&receipt)?,
+                expectations.into(),
+            ))),
+            JsonRequest::VerifyEpochChange {
+                receipt,
+                expectations,
+            } => Request::VerifyEpochChange(Box::new((
+                bcs::from_bytes(&receipt)?,
+                expectations.into(),
+            ))),
+            JsonRequest::ExportEvmCalldata { receipt } => {
+                Request::ExportEvmCalldata(bcs::from_bytes(&receipt)?)
+            }
+        })
+    }
+}
+
+/// (De)serialization of byte fields as `0x`-prefixed hex strings. The
+/// prefix is optional when deserializing.
+mod hex_bytes {
+    use hex::{FromHex, ToHex};
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer, T: AsRef<[u8]>>(
+        bytes: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("0x{}", bytes.as_ref().encode_hex::<String>()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: FromHex>(
+        deserializer: D,
+    ) -> Result<T, D::Error>
+    where
+        T::Error: std::fmt::Display,
+    {
+        let hex = String::deserialize(deserializer)?;
+
+        from_hex(&hex).map_err(de::Error::custom)
+    }
+
+    pub fn from_hex<T: FromHex>(hex: &str) -> Result<T, T::Error> {
+        T::from_hex(hex.strip_prefix("0x").unwrap_or(hex))
+    }
+}
+
+/// (De)serialization of optional byte fields, see [`hex_bytes`].
+mod option_hex_bytes {
+    use hex::FromHex;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer, T: AsRef<[u8]>>(
+        bytes: &Option<T>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match bytes {
+            Some(bytes) => super::hex_bytes::serialize(bytes, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: FromHex>(
+        deserializer: D,
+    ) -> Result<Option<T>, D::Error>
+    where
+        T::Error: std::fmt::Display,
+    {
+        Option::<String>::deserialize(deserializer)?
+            .map(|hex| super::hex_bytes::from_hex(&hex).map_err(de::Error::custom))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::inclusion::{
+        SparseMerkleProofAssets, TransactionProofAssets, ValidatorVerifierAssets,
+    };
+    use crate::json::{
+        JsonInclusionExpectations, JsonInclusionOutput, JsonReceipt, JsonRequest,
+        JsonVerificationResponse,
+    };
+    use crate::types::{
+        EpochChangeData, EpochChangeExpectations, InclusionData, InclusionExpectations,
+        ProvingMode, Request, VerificationResponse,
+    };
+    use aptos_guests::EPOCH_CHANGE_ID;
+    use aptos_lc_core::types::output::InclusionOutput;
+    use risc0_zkvm::{FakeReceipt, InnerReceipt, Receipt, ReceiptClaim};
+
+    fn receipt() -> Receipt {
+        let journal = vec![1, 2, 3];
+        Receipt::new(
+            InnerReceipt::Fake(FakeReceipt::new(ReceiptClaim::ok(
+                EPOCH_CHANGE_ID,
+                journal.clone(),
+            ))),
+            journal,
+        )
+    }
+
+    /// Converts a request to JSON and back, and checks that its BCS
+    /// serialization is unchanged.
+    fn assert_round_trip(request: Request) {
+        let json = serde_json::to_string(&JsonRequest::try_from(&request).unwrap()).unwrap();
+        let json_request: JsonRequest = serde_json::from_str(&json).unwrap();
+        let decoded = Request::try_from(json_request).unwrap();
+
+        assert_eq!(
+            bcs::to_bytes(&decoded).unwrap(),
+            bcs::to_bytes(&request).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_json_request_round_trip() {
+        assert_round_trip(Request::ProveInclusion(Box::new((
+            ProvingMode::SNARK,
+            InclusionData {
+                sparse_merkle_proof_assets: SparseMerkleProofAssets::new(
+                    vec![1, 2, 3],
+                    [4; 32],
+                    [5; 32],
+                ),
+                transaction_proof_assets: TransactionProofAssets::new(
+                    vec![6],
+                    7,
+                    vec![8, 9],
+                    vec![10],
+                ),
+                validator_verifier_assets: ValidatorVerifierAssets::new(vec![11, 12]),
+            },
+        ))));
+        assert_round_trip(Request::ProveEpochChange(Box::new((
+            ProvingMode::STARK,
+            EpochChangeData {
+                trusted_state: vec![1, 2],
+                epoch_change_proof: vec![],
+            },
+        ))));
+        assert_round_trip(Request::VerifyInclusion(Box::new((
+            receipt(),
+            InclusionExpectations {
+                key: Some([1; 32]),
+                block_id: Some([2; 32]),
+                ..Default::default()
+            },
+        ))));
+        assert_round_trip(Request::VerifyEpochChange(Box::new((
+            receipt(),
+            EpochChangeExpectations::default(),
+        ))));
+        assert_round_trip(Request::ExportEvmCalldata(receipt()));
+    }
+
+    #[test]
+    fn test_json_request_format() {
+        let json_request: JsonRequest = serde_json::from_str(
+            r#"{
+                "type": "prove_epoch_change",
+                "proving_mode": "STARK",
+                "trusted_state": "0x0102",
+                "epoch_change_proof": "ff"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            json_request,
+            JsonRequest::ProveEpochChange {
+                proving_mode: ProvingMode::STARK,
+                trusted_state: vec![1, 2],
+                epoch_change_proof: vec![255],
+            }
+        );
+        assert!(serde_json::to_string(&json_request)
+            .unwrap()
+            .contains(r#""trusted_state":"0x0102""#));
+
+        // Fixed size fields have to be of the right length
+        assert!(serde_json::from_str::<JsonRequest>(
+            r#"{
+                "type": "prove_inclusion",
+                "proving_mode": "SNARK",
+                "sparse_merkle_proof_assets": {
+                    "sparse_merkle_proof": "0x",
+                    "leaf_key": "0x01",
+                    "leaf_hash": "0x01"
+                },
+                "transaction_proof_assets": {
+                    "transaction": "0x",
+                    "transaction_index": 0,
+                    "transaction_proof": "0x",
+                    "latest_li": "0x"
+                },
+                "validator_verifier_assets": { "validator_verifier": "0x" }
+            }"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_json_verification_format() {
+        // Expectations are optional
+        let json_request: JsonRequest = serde_json::from_str(&format!(
+            r#"{{
+                "type": "verify_inclusion",
+                "receipt": "0x{}",
+                "expectations": {{ "key": "0x{}" }}
+            }}"#,
+            hex::encode(bcs::to_bytes(&receipt()).unwrap()),
+            hex::encode([3; 32]),
+        ))
+        .unwrap();
+        let JsonRequest::VerifyInclusion { expectations, .. } = json_request else {
+            panic!("Unexpected request {json_request:?}");
+        };
+        assert_eq!(
+            expectations,
+            JsonInclusionExpectations {
+                key: Some([3; 32]),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            serde_json::to_string(&expectations).unwrap(),
+            format!(r#"{{"key":"0x{}"}}"#, hex::encode([3; 32]))
+        );
+
+        let response = VerificationResponse {
+            output: InclusionOutput {
+                epoch: 2,
+                ..Default::default()
+            },
+            failed_expectations: vec!["key".to_string()],
+        };
+        let json = serde_json::to_string(&JsonVerificationResponse::<JsonInclusionOutput>::from(
+            &response,
+        ))
+        .unwrap();
+        assert!(json.contains(r#""epoch":2"#));
+        assert!(json.contains(&format!(r#""key":"0x{}""#, hex::encode([0; 32]))));
+        assert!(json.contains(r#""failed_expectations":["key"]"#));
+    }
+
+    #[test]
+    fn test_json_receipt_round_trip() {
+        let receipt = receipt();
+
+        let json_receipt = JsonReceipt::try_from(&receipt).unwrap();
+        assert_eq!(json_receipt.journal, vec![1, 2, 3]);
+        let json = serde_json::to_string(&json_receipt).unwrap();
+        let decoded =
+            Receipt::try_from(&serde_json::from_str::<JsonReceipt>(&json).unwrap()).unwrap();
+        assert_eq!(
+            bcs::to_bytes(&decoded).unwrap(),
+            bcs::to_bytes(&receipt).unwrap()
+        );
+
+        // The journal has to match the receipt
+        let mut wrong_json_receipt = json_receipt;
+        wrong_json_receipt.journal = vec![];
+        assert!(Receipt::try_from(&wrong_json_receipt).is_err());
+    }
+}
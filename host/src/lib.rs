@@ -1,5 +1,16 @@
-pub mod types;
-pub mod error;
+pub mod accumulator_consistency;
 pub mod aptos;
+pub mod epoch_change;
+pub mod epoch_change_chain;
+pub mod error;
+pub mod event_inclusion;
+pub mod evm;
 pub mod inclusion;
-pub mod epoch_change;
\ No newline at end of file
+pub mod json;
+pub mod light_client;
+pub mod native;
+pub mod non_inclusion;
+pub mod store;
+pub mod transaction_execution;
+pub mod transaction_range;
+pub mod types;
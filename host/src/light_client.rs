@@ -0,0 +1,320 @@
+//! A light client persisting its `TrustedState` across ratchets.
+
+use crate::epoch_change::prove_epoch_change;
+use crate::error::ClientError;
+use crate::store::{LightClientStore, RatchetRecord};
+use aptos_guests::EPOCH_CHANGE_ID;
+use aptos_lc_core::crypto::hash::CryptoHash;
+use aptos_lc_core::types::output::EpochChangeOutput;
+use aptos_lc_core::types::trusted_state::{EpochChangeProof, TrustedState};
+use aptos_lc_core::verify::ratchet_trusted_state;
+use risc0_zkvm::{Prover, Receipt};
+
+/// A light client ratcheting its `TrustedState` with epoch change proofs.
+///
+/// Every ratchet is recorded in a [`LightClientStore`] before the
+/// in-memory state is updated, so that the light client can be resumed
+/// from the store after a restart or a crash.
+pub struct LightClient<S: LightClientStore> {
+    store: S,
+    initial_trusted_state: TrustedState,
+    trusted_state: TrustedState,
+}
+
+impl<S: LightClientStore> LightClient<S> {
+    /// Creates a new `LightClient`, resuming from the latest record of the
+    /// store if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - The store recording the ratchets of the light client.
+    /// * `initial_trusted_state` - The trusted state to start from if the store is empty.
+    ///
+    /// # Returns
+    ///
+    /// The `LightClient`.
+    pub fn new(store: S, initial_trusted_state: TrustedState) -> Result<Self, ClientError> {
+        let trusted_state = match store
+            .latest()
+            .map_err(|err| ClientError::Store { source: err })?
+        {
+            Some(record) => TrustedState::from_bytes(record.trusted_state())
+                .map_err(|err| ClientError::Internal { source: err.into() })?,
+            None => initial_trusted_state.clone(),
+        };
+
+        Ok(Self {
+            store,
+            initial_trusted_state,
+            trusted_state,
+        })
+    }
+
+    /// Returns the current trusted state of the light client.
+    pub const fn trusted_state(&self) -> &TrustedState {
+        &self.trusted_state
+    }
+
+    /// Returns the store of the light client.
+    pub const fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Returns the history of the ratchets of the light client.
+    pub fn history(&self) -> Result<Vec<RatchetRecord>, ClientError> {
+        self.store
+            .history()
+            .map_err(|err| ClientError::Store { source: err })
+    }
+
+    /// Proves an epoch change from the current trusted state and ratchets
+    /// the light client with it.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The prover to use.
+    /// * `epoch_change_proof` - The serialized `EpochChangeProof` to ratchet with.
+    ///
+    /// # Returns
+    ///
+    /// The record of the ratchet.
+    pub fn ratchet(
+        &mut self,
+        client: &dyn Prover,
+        epoch_change_proof: &[u8],
+    ) -> Result<RatchetRecord, ClientError> {
+        let (output, new_state) = self.verify_ratchet(epoch_change_proof)?;
+        // The epoch change was just verified, no need for a preflight
+        let (receipt, _) = prove_epoch_change(
+            client,
+            &self.trusted_state.to_bytes(),
+            epoch_change_proof,
+            false,
+        )
+        .map_err(|err| ClientError::Ratchet { source: err.into() })?;
+
+        self.apply_ratchet(receipt, output, new_state)
+    }
+
+    /// Ratchets the light client with an epoch change receipt generated
+    /// elsewhere, e.g. by a proof server.
+    ///
+    /// The receipt has to be valid and its journal has to match the
+    /// ratcheting of the current trusted state with `epoch_change_proof`.
+    ///
+    /// # Arguments
+    ///
+    /// * `receipt` - The receipt of the epoch change program.
+    /// * `epoch_change_proof` - The serialized `EpochChangeProof` the receipt was generated for.
+    ///
+    /// # Returns
+    ///
+    /// The record of the ratchet.
+    pub fn ratchet_with_receipt(
+        &mut self,
+        receipt: Receipt,
+        epoch_change_proof: &[u8],
+    ) -> Result<RatchetRecord, ClientError> {
+        let (output, new_state) = self.verify_ratchet(epoch_change_proof)?;
+
+        self.apply_ratchet(receipt, output, new_state)
+    }
+
+    /// Natively ratchets the current trusted state with `epoch_change_proof`,
+    /// returning the expected output of the epoch change program along with
+    /// the new trusted state.
+    fn verify_ratchet(
+        &self,
+        epoch_change_proof: &[u8],
+    ) -> Result<(EpochChangeOutput, TrustedState), ClientError> {
+        let epoch_change_proof = EpochChangeProof::from_bytes(epoch_change_proof)
+            .map_err(|err| ClientError::Ratchet { source: err.into() })?;
+
+        ratchet_trusted_state(&self.trusted_state, &epoch_change_proof)
+            .map_err(|err| ClientError::Ratchet { source: err.into() })
+    }
+
+    /// Checks that `receipt` commits to `output`, then records the ratchet
+    /// to `new_state`.
+    fn apply_ratchet(
+        &mut self,
+        receipt: Receipt,
+        output: EpochChangeOutput,
+        new_state: TrustedState,
+    ) -> Result<RatchetRecord, ClientError> {
+        receipt
+            .verify(EPOCH_CHANGE_ID)
+            .map_err(|_| ClientError::Verification("epoch change".to_string()))?;
+        // The receipt has to ratchet our current trusted state
+        if receipt.journal.bytes != output.to_bytes() {
+            return Err(ClientError::Verification(
+                "epoch change for the current trusted state".to_string(),
+            ));
+        }
+
+        // Persist the ratchet before updating the in-memory state
+        let record = RatchetRecord::new(new_state.to_bytes(), output, receipt);
+        self.store
+            .append(&record)
+            .map_err(|err| ClientError::Store { source: err })?;
+        self.trusted_state = new_state;
+
+        Ok(record)
+    }
+
+    /// Returns the hash of the `ValidatorVerifier` that was trusted by the
+    /// light client for `epoch`.
+    ///
+    /// As a single ratchet can skip several epochs, only the epochs the
+    /// light client actually trusted a validator set for are known.
+    ///
+    /// # Arguments
+    ///
+    /// * `epoch` - The epoch to look up.
+    ///
+    /// # Returns
+    ///
+    /// The hash of the `ValidatorVerifier` if the light client trusted a
+    /// validator set for `epoch`, `None` otherwise.
+    pub fn validator_verifier_hash(&self, epoch: u64) -> Result<Option<[u8; 32]>, ClientError> {
+        if let Some(record) = self
+            .store
+            .get_by_end_epoch(epoch)
+            .map_err(|err| ClientError::Store { source: err })?
+        {
+            return Ok(Some(record.output().validator_verifier_hash));
+        }
+
+        match &self.initial_trusted_state {
+            TrustedState::EpochState { epoch_state, .. } if epoch_state.epoch == epoch => {
+                Ok(Some(*epoch_state.verifier().hash().as_ref()))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::ClientError;
+    use crate::light_client::LightClient;
+    use crate::native::verify_epoch_change;
+    use crate::store::FileStore;
+    use crate::types::EpochChangeData;
+    use aptos_guests::EPOCH_CHANGE_ID;
+    use aptos_lc_core::aptos_test_utils::wrapper::AptosWrapper;
+    use aptos_lc_core::crypto::hash::CryptoHash;
+    use aptos_lc_core::types::trusted_state::TrustedState;
+    use risc0_zkvm::{default_prover, FakeReceipt, InnerReceipt, Receipt, ReceiptClaim};
+    use serial_test::serial;
+
+    fn fake_receipt(journal: Vec<u8>) -> Receipt {
+        Receipt::new(
+            InnerReceipt::Fake(FakeReceipt::new(ReceiptClaim::ok(
+                EPOCH_CHANGE_ID,
+                journal.clone(),
+            ))),
+            journal,
+        )
+    }
+
+    #[test]
+    #[serial(risc0_dev_mode)]
+    fn test_light_client_history() {
+        // Fake receipts only verify in dev mode, in which the prover also
+        // generates them
+        std::env::set_var("RISC0_DEV_MODE", "1");
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("light_client");
+
+        let mut aptos_wrapper = AptosWrapper::new(4, 3, 3).unwrap();
+        let initial_trusted_state: TrustedState =
+            bcs::from_bytes(&bcs::to_bytes(aptos_wrapper.trusted_state()).unwrap()).unwrap();
+        let initial_verifier_hash = match &initial_trusted_state {
+            TrustedState::EpochState { epoch_state, .. } => *epoch_state.verifier().hash().as_ref(),
+            _ => panic!("Expected epoch state"),
+        };
+
+        let mut light_client =
+            LightClient::new(FileStore::new(&path), initial_trusted_state.clone()).unwrap();
+        assert_eq!(
+            light_client.validator_verifier_hash(1).unwrap(),
+            Some(initial_verifier_hash)
+        );
+        assert_eq!(light_client.validator_verifier_hash(2).unwrap(), None);
+
+        aptos_wrapper.generate_traffic().unwrap();
+        let state_proof = aptos_wrapper
+            .new_state_proof(initial_trusted_state.version())
+            .unwrap();
+        let epoch_change_proof = bcs::to_bytes(state_proof.epoch_changes()).unwrap();
+
+        let output = verify_epoch_change(&EpochChangeData {
+            trusted_state: initial_trusted_state.to_bytes(),
+            epoch_change_proof: epoch_change_proof.clone(),
+        })
+        .unwrap();
+
+        // A valid receipt that does not match the ratchet is rejected and
+        // not recorded
+        let mut wrong_output = output.clone();
+        wrong_output.end_epoch += 1;
+        match light_client
+            .ratchet_with_receipt(fake_receipt(wrong_output.to_bytes()), &epoch_change_proof)
+        {
+            Err(ClientError::Verification(message)) => {
+                assert_eq!(message, "epoch change for the current trusted state")
+            }
+            res => panic!("Expected output mismatch, got {res:?}"),
+        }
+        assert!(light_client.history().unwrap().is_empty());
+
+        // A matching receipt ratchets the light client and is recorded
+        let record = light_client
+            .ratchet_with_receipt(fake_receipt(output.to_bytes()), &epoch_change_proof)
+            .unwrap();
+        assert_eq!(record.output(), &output);
+        assert_eq!(light_client.trusted_state().epoch(), Some(output.end_epoch));
+        assert_eq!(light_client.history().unwrap().len(), 1);
+
+        // The light client proves the next epoch change itself
+        let trusted_state_version = light_client.trusted_state().version();
+        aptos_wrapper.commit_new_epoch().unwrap();
+        aptos_wrapper.generate_traffic().unwrap();
+        let state_proof = aptos_wrapper
+            .new_state_proof(trusted_state_version)
+            .unwrap();
+        let next_record = light_client
+            .ratchet(
+                default_prover().as_ref(),
+                &bcs::to_bytes(state_proof.epoch_changes()).unwrap(),
+            )
+            .unwrap();
+        assert_eq!(next_record.output().start_epoch, output.end_epoch);
+
+        // The light client resumes from the store
+        let light_client = LightClient::new(FileStore::new(&path), initial_trusted_state).unwrap();
+        assert_eq!(
+            light_client.trusted_state().epoch(),
+            Some(next_record.output().end_epoch)
+        );
+        assert_eq!(light_client.history().unwrap().len(), 2);
+        assert_eq!(
+            light_client
+                .validator_verifier_hash(output.end_epoch)
+                .unwrap(),
+            Some(output.validator_verifier_hash)
+        );
+        assert_eq!(
+            light_client
+                .validator_verifier_hash(next_record.output().end_epoch)
+                .unwrap(),
+            Some(next_record.output().validator_verifier_hash)
+        );
+        assert_eq!(
+            light_client.validator_verifier_hash(1).unwrap(),
+            Some(initial_verifier_hash)
+        );
+    }
+}
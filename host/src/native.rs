@@ -0,0 +1,613 @@
+//! Native verification of inclusion, transaction execution, transaction
+//! range, event inclusion, accumulator consistency and epoch change proofs.
+//!
+//! The functions of this module run the checks of the programs, shared in
+//! `aptos_lc_core::verify`, outside of the zkVM. They are meant for
+//! services that need trust-minimized verification but not a succinct
+//! proof, and return the same outputs the programs commit to. They also
+//! check the inputs of a proof before it is generated.
+
+use crate::accumulator_consistency::AccumulatorConsistencyProofAssets;
+use crate::error::LightClientError;
+use crate::event_inclusion::EventProofAssets;
+use crate::inclusion::{SparseMerkleProofAssets, TransactionProofAssets, ValidatorVerifierAssets};
+use crate::transaction_range::TransactionRangeProofAssets;
+use crate::types::{EpochChangeData, InclusionData};
+use aptos_lc_core::types::error::{TypesError, VerifyError};
+use aptos_lc_core::types::output::{
+    AccumulatorConsistencyOutput, EpochChangeOutput, EventInclusionOutput, InclusionOutput,
+    TransactionExecutionOutput, TransactionRangeOutput,
+};
+use aptos_lc_core::verify;
+
+/// Verifies the inclusion of a leaf in the state of a signed ledger info,
+/// running the checks of the inclusion program natively.
+///
+/// # Arguments
+///
+/// * `inclusion_data` - The data for the inclusion proof.
+///
+/// # Returns
+///
+/// The `InclusionOutput` the inclusion program would commit to.
+pub fn verify_inclusion(
+    inclusion_data: &InclusionData,
+) -> Result<InclusionOutput, LightClientError> {
+    verify::verify_inclusion(
+        &inclusion_data.sparse_merkle_proof_assets,
+        &inclusion_data.transaction_proof_assets,
+        &inclusion_data.validator_verifier_assets,
+    )
+    .map_err(|err| verification_error("native-inclusion", err))
+}
+
+/// Verifies that a transaction was committed at a given version of a signed
+/// ledger info, running the checks of the transaction execution program
+/// natively.
+///
+/// # Arguments
+///
+/// * `transaction_proof_assets` - The transaction proof assets, whose
+///   `transaction_index` is the version of the transaction.
+/// * `validator_verifier_assets` - The validator verifier assets.
+///
+/// # Returns
+///
+/// The `TransactionExecutionOutput` the transaction execution program would
+/// commit to.
+pub fn verify_transaction_execution(
+    transaction_proof_assets: &TransactionProofAssets,
+    validator_verifier_assets: &ValidatorVerifierAssets,
+) -> Result<TransactionExecutionOutput, LightClientError> {
+    verify::verify_transaction_execution(transaction_proof_assets, validator_verifier_assets)
+        .map_err(|err| verification_error("native-transaction-execution", err))
+}
+
+/// Verifies that a contiguous range of transactions was committed from a
+/// given version, running the checks of the transaction range program
+/// natively.
+///
+/// # Arguments
+///
+/// * `transaction_range_proof_assets` - The transaction range proof assets.
+/// * `validator_verifier_assets` - The validator verifier assets.
+///
+/// # Returns
+///
+/// The `TransactionRangeOutput` the transaction range program would commit
+/// to.
+pub fn verify_transaction_range(
+    transaction_range_proof_assets: &TransactionRangeProofAssets,
+    validator_verifier_assets: &ValidatorVerifierAssets,
+) -> Result<TransactionRangeOutput, LightClientError> {
+    verify::verify_transaction_range(transaction_range_proof_assets, validator_verifier_assets)
+        .map_err(|err| verification_error("native-transaction-range", err))
+}
+
+/// Verifies that an event was emitted by the transaction committed at a
+/// given version, running the checks of the event inclusion program
+/// natively.
+///
+/// # Arguments
+///
+/// * `event_proof_assets` - The event proof assets.
+/// * `transaction_proof_assets` - The transaction proof assets, whose
+///   `transaction_index` is the version of the transaction.
+/// * `validator_verifier_assets` - The validator verifier assets.
+///
+/// # Returns
+///
+/// The `EventInclusionOutput` the event inclusion program would commit to.
+pub fn verify_event_inclusion(
+    event_proof_assets: &EventProofAssets,
+    transaction_proof_assets: &TransactionProofAssets,
+    validator_verifier_assets: &ValidatorVerifierAssets,
+) -> Result<EventInclusionOutput, LightClientError> {
+    verify::verify_event_inclusion(
+        event_proof_assets,
+        transaction_proof_assets,
+        validator_verifier_assets,
+    )
+    .map_err(|err| verification_error("native-event-inclusion", err))
+}
+
+/// Verifies that the transaction accumulator of a signed ledger info extends
+/// the one of a previously signed ledger info, running the checks of the
+/// accumulator consistency program natively.
+///
+/// # Arguments
+///
+/// * `accumulator_consistency_proof_assets` - The accumulator consistency
+///   proof assets.
+/// * `prev_validator_verifier_assets` - The validator verifier assets of the
+///   previous ledger info.
+/// * `validator_verifier_assets` - The validator verifier assets of the
+///   latest ledger info.
+///
+/// # Returns
+///
+/// The `AccumulatorConsistencyOutput` the accumulator consistency program
+/// would commit to.
+pub fn verify_accumulator_consistency(
+    accumulator_consistency_proof_assets: &AccumulatorConsistencyProofAssets,
+    prev_validator_verifier_assets: &ValidatorVerifierAssets,
+    validator_verifier_assets: &ValidatorVerifierAssets,
+) -> Result<AccumulatorConsistencyOutput, LightClientError> {
+    verify::verify_accumulator_consistency(
+        accumulator_consistency_proof_assets,
+        prev_validator_verifier_assets,
+        validator_verifier_assets,
+    )
+    .map_err(|err| verification_error("native-accumulator-consistency", err))
+}
+
+/// Verifies that a trusted state can be ratcheted with an epoch change proof,
+/// running the checks of the epoch change program natively.
+///
+/// # Arguments
+///
+/// * `epoch_change_data` - The data for the epoch change proof.
+///
+/// # Returns
+///
+/// The `EpochChangeOutput` the epoch change program would commit to.
+pub fn verify_epoch_change(
+    epoch_change_data: &EpochChangeData,
+) -> Result<EpochChangeOutput, LightClientError> {
+    verify::verify_epoch_change(
+        &epoch_change_data.trusted_state,
+        &epoch_change_data.epoch_change_proof,
+    )
+    .map_err(|err| verification_error("native-epoch-change", err))
+}
+
+/// Wraps an error of the native checks. The errors of `aptos-lc-core` are
+/// unwrapped from `anyhow` so that they can still be downcast from the
+/// source of the `LightClientError`.
+fn verification_error(program: &str, err: anyhow::Error) -> LightClientError {
+    let source: Box<dyn std::error::Error + Sync + Send> = match err.downcast::<VerifyError>() {
+        Ok(err) => Box::new(err),
+        Err(err) => match err.downcast::<TypesError>() {
+            Ok(err) => Box::new(err),
+            Err(err) => err.into(),
+        },
+    };
+
+    LightClientError::VerificationError {
+        program: program.to_string(),
+        source,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::accumulator_consistency::AccumulatorConsistencyProofAssets;
+    use crate::error::ErrorCode;
+    use crate::event_inclusion::EventProofAssets;
+    use crate::inclusion::{
+        SparseMerkleProofAssets, TransactionProofAssets, ValidatorVerifierAssets,
+    };
+    use crate::native::{
+        verify_accumulator_consistency, verify_epoch_change, verify_event_inclusion,
+        verify_inclusion, verify_transaction_execution, verify_transaction_range,
+    };
+    use crate::transaction_range::TransactionRangeProofAssets;
+    use crate::types::{EpochChangeData, InclusionData};
+    use aptos_guests::{
+        ACCUMULATOR_CONSISTENCY_ELF, EPOCH_CHANGE_ELF, EVENT_INCLUSION_ELF, INCLUSION_ELF,
+        TRANSACTION_EXECUTION_ELF, TRANSACTION_RANGE_ELF,
+    };
+    use aptos_lc_core::aptos_test_utils::wrapper::AptosWrapper;
+    use aptos_lc_core::crypto::hash::HashValue;
+    use aptos_lc_core::merkle::consistency_proof::{
+        AccumulatorConsistencyProof, TransactionAccumulatorSummary,
+    };
+    use aptos_lc_core::types::ledger_info::LedgerInfoWithSignatures;
+    use aptos_lc_core::types::transaction::ExecutionStatus;
+    use aptos_lc_core::types::trusted_state::TrustedState;
+    use aptos_lc_core::types::waypoint::Waypoint;
+    use risc0_zkvm::{default_executor, ExecutorEnv};
+    use serial_test::parallel;
+
+    /// Executes `elf` with `env`, asserting that the native output `bytes`
+    /// is byte-identical to the journal of the program.
+    fn assert_journal_eq(elf: &[u8], env: ExecutorEnv, bytes: Vec<u8>) {
+        let session = default_executor().execute(env, elf).unwrap();
+        assert_eq!(session.journal.bytes, bytes);
+    }
+
+    fn inclusion_data(aptos_wrapper: &AptosWrapper) -> InclusionData {
+        let trusted_state = bcs::to_bytes(aptos_wrapper.trusted_state()).unwrap();
+        let validator_verifier = match TrustedState::from_bytes(&trusted_state).unwrap() {
+            TrustedState::EpochState { epoch_state, .. } => epoch_state.verifier().clone(),
+            _ => panic!("expected epoch state"),
+        };
+
+        let proof_assets = aptos_wrapper.get_latest_proof_account(3).unwrap();
+
+        InclusionData {
+            sparse_merkle_proof_assets: SparseMerkleProofAssets::new(
+                bcs::to_bytes(proof_assets.state_proof()).unwrap(),
+                *proof_assets.key().as_ref(),
+                *proof_assets.state_value_hash().unwrap().as_ref(),
+            ),
+            transaction_proof_assets: TransactionProofAssets::new(
+                bcs::to_bytes(&proof_assets.transaction()).unwrap(),
+                *proof_assets.transaction_version(),
+                bcs::to_bytes(&proof_assets.transaction_proof()).unwrap(),
+                aptos_wrapper.get_latest_li_bytes().unwrap(),
+            ),
+            validator_verifier_assets: ValidatorVerifierAssets::new(validator_verifier.to_bytes()),
+        }
+    }
+
+    #[test]
+    #[parallel(risc0_dev_mode)]
+    fn test_native_inclusion() {
+        let mut aptos_wrapper = AptosWrapper::new(5, 3, 3).unwrap();
+        aptos_wrapper.generate_traffic().unwrap();
+
+        let inclusion_data = inclusion_data(&aptos_wrapper);
+
+        let output = verify_inclusion(&inclusion_data).unwrap();
+
+        assert_journal_eq(
+            INCLUSION_ELF,
+            crate::inclusion::generate_stdin(
+                &inclusion_data.sparse_merkle_proof_assets,
+                &inclusion_data.transaction_proof_assets,
+                &inclusion_data.validator_verifier_assets,
+            ),
+            output.to_bytes(),
+        );
+
+        // Tampering with the leaf has to be caught
+        let mut wrong_inclusion_data = inclusion_data;
+        wrong_inclusion_data.sparse_merkle_proof_assets = SparseMerkleProofAssets::new(
+            wrong_inclusion_data
+                .sparse_merkle_proof_assets
+                .sparse_merkle_proof()
+                .clone(),
+            *wrong_inclusion_data.sparse_merkle_proof_assets.leaf_key(),
+            [0; 32],
+        );
+        assert!(verify_inclusion(&wrong_inclusion_data).is_err());
+
+        // Errors of the core can be told apart
+        wrong_inclusion_data.validator_verifier_assets = ValidatorVerifierAssets::new(vec![]);
+        let err = verify_inclusion(&wrong_inclusion_data).unwrap_err();
+        assert_eq!(ErrorCode::of_error(&err), ErrorCode::MalformedProof);
+    }
+
+    #[test]
+    #[parallel(risc0_dev_mode)]
+    fn test_native_transaction_execution() {
+        let mut aptos_wrapper = AptosWrapper::new(5, 3, 3).unwrap();
+        aptos_wrapper.generate_traffic().unwrap();
+
+        let InclusionData {
+            transaction_proof_assets,
+            validator_verifier_assets,
+            ..
+        } = inclusion_data(&aptos_wrapper);
+
+        let output =
+            verify_transaction_execution(&transaction_proof_assets, &validator_verifier_assets)
+                .unwrap();
+        assert_eq!(
+            output.version,
+            *transaction_proof_assets.transaction_index()
+        );
+        assert_eq!(output.status, ExecutionStatus::Success);
+
+        assert_journal_eq(
+            TRANSACTION_EXECUTION_ELF,
+            crate::transaction_execution::generate_stdin(
+                &transaction_proof_assets,
+                &validator_verifier_assets,
+            ),
+            output.to_bytes(),
+        );
+
+        // The proof only holds for the version of the transaction
+        let wrong_transaction_proof_assets = TransactionProofAssets::new(
+            transaction_proof_assets.transaction().clone(),
+            transaction_proof_assets.transaction_index() - 1,
+            transaction_proof_assets.transaction_proof().clone(),
+            transaction_proof_assets.latest_li().clone(),
+        );
+        assert!(verify_transaction_execution(
+            &wrong_transaction_proof_assets,
+            &validator_verifier_assets
+        )
+        .is_err());
+
+        // Aborted transactions are committed with their status
+        let version = aptos_wrapper.generate_aborted_transaction().unwrap();
+        let info_with_proof = aptos_wrapper
+            .get_transaction_info_with_proof(version)
+            .unwrap();
+        let aborted_transaction_proof_assets = TransactionProofAssets::new(
+            bcs::to_bytes(&info_with_proof.transaction_info).unwrap(),
+            version,
+            bcs::to_bytes(&info_with_proof.ledger_info_to_transaction_info_proof).unwrap(),
+            aptos_wrapper.get_latest_li_bytes().unwrap(),
+        );
+
+        let output = verify_transaction_execution(
+            &aborted_transaction_proof_assets,
+            &validator_verifier_assets,
+        )
+        .unwrap();
+        assert_eq!(output.version, version);
+        assert!(matches!(output.status, ExecutionStatus::MoveAbort { .. }));
+
+        assert_journal_eq(
+            TRANSACTION_EXECUTION_ELF,
+            crate::transaction_execution::generate_stdin(
+                &aborted_transaction_proof_assets,
+                &validator_verifier_assets,
+            ),
+            output.to_bytes(),
+        );
+    }
+
+    #[test]
+    #[parallel(risc0_dev_mode)]
+    fn test_native_event_inclusion() {
+        let mut aptos_wrapper = AptosWrapper::new(5, 3, 3).unwrap();
+        aptos_wrapper.generate_traffic().unwrap();
+
+        let InclusionData {
+            validator_verifier_assets,
+            ..
+        } = inclusion_data(&aptos_wrapper);
+        // The last transfer of the traffic comes right before the state
+        // checkpoint ending its block
+        let version = aptos_wrapper.current_version() - 1;
+        let (event, event_proof) = aptos_wrapper.get_event_with_proof(version, 0).unwrap();
+        let info_with_proof = aptos_wrapper
+            .get_transaction_info_with_proof(version)
+            .unwrap();
+        let event_proof_assets = EventProofAssets::new(
+            bcs::to_bytes(&event).unwrap(),
+            0,
+            bcs::to_bytes(&event_proof).unwrap(),
+        );
+        let transaction_proof_assets = TransactionProofAssets::new(
+            bcs::to_bytes(&info_with_proof.transaction_info).unwrap(),
+            version,
+            bcs::to_bytes(&info_with_proof.ledger_info_to_transaction_info_proof).unwrap(),
+            aptos_wrapper.get_latest_li_bytes().unwrap(),
+        );
+
+        let output = verify_event_inclusion(
+            &event_proof_assets,
+            &transaction_proof_assets,
+            &validator_verifier_assets,
+        )
+        .unwrap();
+        assert_eq!((output.version, output.event_index), (version, 0));
+
+        assert_journal_eq(
+            EVENT_INCLUSION_ELF,
+            crate::event_inclusion::generate_stdin(
+                &event_proof_assets,
+                &transaction_proof_assets,
+                &validator_verifier_assets,
+            ),
+            output.to_bytes(),
+        );
+
+        // The proof only holds for the index of the event
+        let wrong_event_proof_assets = EventProofAssets::new(
+            event_proof_assets.event().clone(),
+            1,
+            event_proof_assets.event_proof().clone(),
+        );
+        assert!(verify_event_inclusion(
+            &wrong_event_proof_assets,
+            &transaction_proof_assets,
+            &validator_verifier_assets
+        )
+        .is_err());
+    }
+
+    #[test]
+    #[parallel(risc0_dev_mode)]
+    fn test_native_transaction_range() {
+        let mut aptos_wrapper = AptosWrapper::new(5, 3, 3).unwrap();
+        aptos_wrapper.generate_traffic().unwrap();
+
+        let InclusionData {
+            transaction_proof_assets,
+            validator_verifier_assets,
+            ..
+        } = inclusion_data(&aptos_wrapper);
+        let infos_with_proof = aptos_wrapper
+            .get_latest_transaction_infos_with_proof(2, 4)
+            .unwrap();
+        let transaction_range_proof_assets = TransactionRangeProofAssets::new(
+            infos_with_proof
+                .transaction_infos
+                .iter()
+                .map(|info| bcs::to_bytes(info).unwrap())
+                .collect(),
+            2,
+            bcs::to_bytes(&infos_with_proof.ledger_info_to_transaction_infos_proof).unwrap(),
+            transaction_proof_assets.latest_li().clone(),
+        );
+
+        let output =
+            verify_transaction_range(&transaction_range_proof_assets, &validator_verifier_assets)
+                .unwrap();
+        assert_eq!((output.first_version, output.last_version), (2, 5));
+
+        assert_journal_eq(
+            TRANSACTION_RANGE_ELF,
+            crate::transaction_range::generate_stdin(
+                &transaction_range_proof_assets,
+                &validator_verifier_assets,
+            ),
+            output.to_bytes(),
+        );
+
+        // The proof only holds for the first version of the range
+        let wrong_transaction_range_proof_assets = TransactionRangeProofAssets::new(
+            transaction_range_proof_assets.transactions().clone(),
+            3,
+            transaction_range_proof_assets.range_proof().clone(),
+            transaction_range_proof_assets.latest_li().clone(),
+        );
+        assert!(verify_transaction_range(
+            &wrong_transaction_range_proof_assets,
+            &validator_verifier_assets
+        )
+        .is_err());
+    }
+
+    #[test]
+    #[parallel(risc0_dev_mode)]
+    fn test_native_accumulator_consistency() {
+        let mut aptos_wrapper = AptosWrapper::new(5, 3, 3).unwrap();
+        aptos_wrapper.generate_traffic().unwrap();
+
+        let InclusionData {
+            validator_verifier_assets,
+            ..
+        } = inclusion_data(&aptos_wrapper);
+        let prev_li = aptos_wrapper.get_latest_li_bytes().unwrap();
+        let prev_version = LedgerInfoWithSignatures::from_bytes(&prev_li)
+            .unwrap()
+            .ledger_info()
+            .version();
+
+        aptos_wrapper.generate_traffic().unwrap();
+        let latest_li = aptos_wrapper.get_latest_li_bytes().unwrap();
+        let version = LedgerInfoWithSignatures::from_bytes(&latest_li)
+            .unwrap()
+            .ledger_info()
+            .version();
+
+        // From no known version, the subtrees are the frozen subtrees of the
+        // accumulator at `prev_version`
+        let frozen_subtrees = AccumulatorConsistencyProof::from_bytes(
+            &bcs::to_bytes(
+                &aptos_wrapper
+                    .get_accumulator_consistency_proof(None, prev_version)
+                    .unwrap(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let accumulator_summary = TransactionAccumulatorSummary::new(
+            frozen_subtrees.subtrees().clone(),
+            prev_version + 1,
+        )
+        .unwrap();
+        let consistency_proof = aptos_wrapper
+            .get_accumulator_consistency_proof(Some(prev_version), version)
+            .unwrap();
+        let accumulator_consistency_proof_assets = AccumulatorConsistencyProofAssets::new(
+            accumulator_summary.to_bytes(),
+            prev_li,
+            bcs::to_bytes(&consistency_proof).unwrap(),
+            latest_li,
+        );
+
+        let output = verify_accumulator_consistency(
+            &accumulator_consistency_proof_assets,
+            &validator_verifier_assets,
+            &validator_verifier_assets,
+        )
+        .unwrap();
+        assert_eq!(
+            (output.prev_version, output.version),
+            (prev_version, version)
+        );
+
+        assert_journal_eq(
+            ACCUMULATOR_CONSISTENCY_ELF,
+            crate::accumulator_consistency::generate_stdin(
+                &accumulator_consistency_proof_assets,
+                &validator_verifier_assets,
+                &validator_verifier_assets,
+            ),
+            output.to_bytes(),
+        );
+
+        // The summary has to be the one of the previous ledger info
+        let wrong_accumulator_consistency_proof_assets = AccumulatorConsistencyProofAssets::new(
+            accumulator_consistency_proof_assets
+                .accumulator_summary()
+                .clone(),
+            accumulator_consistency_proof_assets.latest_li().clone(),
+            accumulator_consistency_proof_assets
+                .consistency_proof()
+                .clone(),
+            accumulator_consistency_proof_assets.latest_li().clone(),
+        );
+        assert!(verify_accumulator_consistency(
+            &wrong_accumulator_consistency_proof_assets,
+            &validator_verifier_assets,
+            &validator_verifier_assets,
+        )
+        .is_err());
+    }
+
+    #[test]
+    #[parallel(risc0_dev_mode)]
+    fn test_native_epoch_change() {
+        let mut aptos_wrapper = AptosWrapper::new(4, 3, 3).unwrap();
+        let trusted_state = bcs::to_bytes(aptos_wrapper.trusted_state()).unwrap();
+        let trusted_state_version = *aptos_wrapper.current_version();
+
+        aptos_wrapper.generate_traffic().unwrap();
+        aptos_wrapper.commit_new_epoch().unwrap();
+        aptos_wrapper.generate_traffic().unwrap();
+
+        let state_proof = aptos_wrapper
+            .new_state_proof(trusted_state_version)
+            .unwrap();
+
+        let epoch_change_data = EpochChangeData {
+            trusted_state,
+            epoch_change_proof: bcs::to_bytes(state_proof.epoch_changes()).unwrap(),
+        };
+
+        let output = verify_epoch_change(&epoch_change_data).unwrap();
+        assert_eq!(output.start_epoch, 1);
+        assert_eq!(output.end_epoch, 3);
+
+        assert_journal_eq(
+            EPOCH_CHANGE_ELF,
+            crate::epoch_change::generate_stdin(
+                &epoch_change_data.trusted_state,
+                &epoch_change_data.epoch_change_proof,
+            ),
+            output.to_bytes(),
+        );
+
+        // The proof can not be used to ratchet from a waypoint it does not start from
+        let wrong_epoch_change_data = EpochChangeData {
+            trusted_state: TrustedState::EpochWaypoint(Waypoint::new(
+                trusted_state_version,
+                HashValue::default(),
+            ))
+            .to_bytes(),
+            epoch_change_proof: epoch_change_data.epoch_change_proof.clone(),
+        };
+        assert!(verify_epoch_change(&wrong_epoch_change_data).is_err());
+
+        // Truncated inputs are rejected as malformed rather than crashing
+        // the caller
+        let mut truncated_trusted_state = epoch_change_data.trusted_state.clone();
+        truncated_trusted_state.truncate(truncated_trusted_state.len() / 2);
+        let err = verify_epoch_change(&EpochChangeData {
+            trusted_state: truncated_trusted_state,
+            epoch_change_proof: epoch_change_data.epoch_change_proof,
+        })
+        .unwrap_err();
+        assert_eq!(ErrorCode::of_error(&err), ErrorCode::MalformedProof);
+    }
+}
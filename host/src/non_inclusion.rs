@@ -0,0 +1,86 @@
+use crate::error::LightClientError;
+use crate::inclusion::{TransactionProofAssets, ValidatorVerifierAssets};
+use aptos_guests::NON_INCLUSION_ELF;
+use aptos_lc_core::types::output::NonInclusionOutput;
+use getset::Getters;
+use risc0_zkvm::{ExecutorEnv, Prover, Receipt};
+use serde::{Deserialize, Serialize};
+
+/// Assets needed to prove that no leaf exists for `leaf_key` in the state tree.
+#[derive(Clone, Debug, Getters, Serialize, Deserialize)]
+#[getset(get = "pub")]
+pub struct SparseMerkleNonInclusionProofAssets {
+    sparse_merkle_proof: Vec<u8>,
+    leaf_key: [u8; 32],
+}
+
+impl SparseMerkleNonInclusionProofAssets {
+    pub const fn new(
+        sparse_merkle_proof: Vec<u8>,
+        leaf_key: [u8; 32],
+    ) -> SparseMerkleNonInclusionProofAssets {
+        SparseMerkleNonInclusionProofAssets {
+            sparse_merkle_proof,
+            leaf_key,
+        }
+    }
+}
+
+pub fn generate_stdin<'a>(
+    sparse_merkle_proof_assets: &'a SparseMerkleNonInclusionProofAssets,
+    transaction_proof_assets: &'a TransactionProofAssets,
+    validator_verifier_assets: &'a ValidatorVerifierAssets,
+) -> ExecutorEnv<'a> {
+    ExecutorEnv::builder()
+        .write(&sparse_merkle_proof_assets.sparse_merkle_proof)
+        .unwrap()
+        .write(&sparse_merkle_proof_assets.leaf_key)
+        .unwrap()
+        .write(transaction_proof_assets.transaction())
+        .unwrap()
+        .write(transaction_proof_assets.transaction_index())
+        .unwrap()
+        .write(transaction_proof_assets.transaction_proof())
+        .unwrap()
+        .write(transaction_proof_assets.latest_li())
+        .unwrap()
+        .write(validator_verifier_assets.validator_verifier())
+        .unwrap()
+        .build()
+        .unwrap()
+}
+
+#[allow(dead_code)]
+pub fn prove_non_inclusion(
+    client: &dyn Prover,
+    sparse_merkle_proof_assets: &SparseMerkleNonInclusionProofAssets,
+    transaction_proof_assets: &TransactionProofAssets,
+    validator_verifier_assets: &ValidatorVerifierAssets,
+) -> Result<(Receipt, NonInclusionOutput), LightClientError> {
+    let env = generate_stdin(
+        sparse_merkle_proof_assets,
+        transaction_proof_assets,
+        validator_verifier_assets,
+    );
+
+    let proof =
+        client
+            .prove(env, NON_INCLUSION_ELF)
+            .map_err(|err| LightClientError::ProvingError {
+                program: "prove-merkle-non-inclusion".to_string(),
+                source: err.into(),
+            })?;
+
+    // Read output.
+    let output: NonInclusionOutput =
+        proof
+            .receipt
+            .journal
+            .decode()
+            .map_err(|err| LightClientError::DecodeError {
+                program: "prove-merkle-non-inclusion".to_string(),
+                source: err.into(),
+            })?;
+
+    Ok((proof.receipt, output))
+}
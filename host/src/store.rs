@@ -0,0 +1,324 @@
+//! Persistent storage for the state of a [`crate::light_client::LightClient`].
+//!
+//! Every time the light client ratchets its `TrustedState`, a
+//! [`RatchetRecord`] is appended to a [`LightClientStore`]. The history of
+//! records is used both to resume the light client after a restart and to
+//! answer which validator set was trusted for a given epoch.
+
+use crate::error::StoreError;
+use aptos_lc_core::types::output::EpochChangeOutput;
+use getset::Getters;
+use risc0_zkvm::Receipt;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// A ratcheting of the `TrustedState` of the light client.
+#[derive(Clone, Debug, Getters, Serialize, Deserialize)]
+#[getset(get = "pub")]
+pub struct RatchetRecord {
+    /// Serialized `TrustedState` after ratcheting.
+    trusted_state: Vec<u8>,
+    /// Output of the epoch change proof used to ratchet.
+    output: EpochChangeOutput,
+    /// Receipt of the epoch change proof used to ratchet.
+    receipt: Receipt,
+}
+
+impl RatchetRecord {
+    pub const fn new(
+        trusted_state: Vec<u8>,
+        output: EpochChangeOutput,
+        receipt: Receipt,
+    ) -> RatchetRecord {
+        RatchetRecord {
+            trusted_state,
+            output,
+            receipt,
+        }
+    }
+}
+
+/// A store for the history of [`RatchetRecord`]s of a light client.
+///
+/// Records are appended in increasing order of `end_epoch`. Implementations
+/// have to make `append` atomic, so that a crash never leaves a partially
+/// written record behind.
+pub trait LightClientStore {
+    /// Appends a record to the store. The `end_epoch` of the record has to
+    /// be greater than the one of the latest record.
+    fn append(&mut self, record: &RatchetRecord) -> Result<(), StoreError>;
+
+    /// Returns the latest record of the store, if any.
+    fn latest(&self) -> Result<Option<RatchetRecord>, StoreError>;
+
+    /// Returns the record whose `end_epoch` is `epoch`, if any.
+    fn get_by_end_epoch(&self, epoch: u64) -> Result<Option<RatchetRecord>, StoreError>;
+
+    /// Returns all the records of the store, in the order they were appended.
+    fn history(&self) -> Result<Vec<RatchetRecord>, StoreError>;
+}
+
+/// Checks that `record` can be appended after `latest`.
+fn check_append(latest: Option<&RatchetRecord>, record: &RatchetRecord) -> Result<(), StoreError> {
+    match latest {
+        Some(latest) if latest.output.end_epoch >= record.output.end_epoch => {
+            Err(StoreError::OutOfOrder {
+                latest_epoch: latest.output.end_epoch,
+                epoch: record.output.end_epoch,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// A [`LightClientStore`] keeping the whole history in a single file.
+///
+/// Appending rewrites the history to a temporary file which is then
+/// renamed over the previous one, so the file always holds a complete
+/// history.
+#[derive(Debug, Clone)]
+pub struct FileStore {
+    path: PathBuf,
+}
+
+impl FileStore {
+    /// Opens the store at `path`. The file is created on the first append.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file holding the history.
+    ///
+    /// # Returns
+    ///
+    /// The `FileStore`.
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    fn io_error(&self, source: std::io::Error) -> StoreError {
+        StoreError::Io {
+            path: self.path.display().to_string(),
+            source,
+        }
+    }
+
+    fn write_history(&self, history: &[RatchetRecord]) -> Result<(), StoreError> {
+        let bytes = bcs::to_bytes(history)
+            .map_err(|err| StoreError::Serialization { source: err.into() })?;
+
+        let tmp_path = self.path.with_extension("tmp");
+        let mut tmp_file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&tmp_path)
+            .map_err(|err| self.io_error(err))?;
+        tmp_file
+            .write_all(&bytes)
+            .map_err(|err| self.io_error(err))?;
+        tmp_file.sync_all().map_err(|err| self.io_error(err))?;
+
+        fs::rename(&tmp_path, &self.path).map_err(|err| self.io_error(err))?;
+
+        // Persist the rename itself
+        if let Some(parent) = self
+            .path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            File::open(parent)
+                .and_then(|dir| dir.sync_all())
+                .map_err(|err| self.io_error(err))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl LightClientStore for FileStore {
+    fn append(&mut self, record: &RatchetRecord) -> Result<(), StoreError> {
+        let mut history = self.history()?;
+        check_append(history.last(), record)?;
+        history.push(record.clone());
+
+        self.write_history(&history)
+    }
+
+    fn latest(&self) -> Result<Option<RatchetRecord>, StoreError> {
+        Ok(self.history()?.pop())
+    }
+
+    fn get_by_end_epoch(&self, epoch: u64) -> Result<Option<RatchetRecord>, StoreError> {
+        Ok(self
+            .history()?
+            .into_iter()
+            .find(|record| record.output.end_epoch == epoch))
+    }
+
+    fn history(&self) -> Result<Vec<RatchetRecord>, StoreError> {
+        let bytes = match fs::read(&self.path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(self.io_error(err)),
+        };
+
+        bcs::from_bytes(&bytes).map_err(|err| StoreError::Serialization { source: err.into() })
+    }
+}
+
+/// A [`LightClientStore`] backed by a `sled` database, with one entry per
+/// record keyed by its `end_epoch`.
+#[derive(Debug, Clone)]
+pub struct SledStore {
+    db: sled::Db,
+}
+
+impl SledStore {
+    /// Opens, or creates, the database at `path`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the database.
+    ///
+    /// # Returns
+    ///
+    /// The `SledStore`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, StoreError> {
+        let db = sled::open(path).map_err(|err| StoreError::Database { source: err.into() })?;
+
+        Ok(Self { db })
+    }
+
+    fn decode(bytes: &[u8]) -> Result<RatchetRecord, StoreError> {
+        bcs::from_bytes(bytes).map_err(|err| StoreError::Serialization { source: err.into() })
+    }
+}
+
+impl LightClientStore for SledStore {
+    fn append(&mut self, record: &RatchetRecord) -> Result<(), StoreError> {
+        check_append(self.latest()?.as_ref(), record)?;
+
+        let bytes = bcs::to_bytes(record)
+            .map_err(|err| StoreError::Serialization { source: err.into() })?;
+        // Keys are big endian so that records are ordered by epoch
+        self.db
+            .insert(record.output.end_epoch.to_be_bytes(), bytes)
+            .map_err(|err| StoreError::Database { source: err.into() })?;
+        self.db
+            .flush()
+            .map_err(|err| StoreError::Database { source: err.into() })?;
+
+        Ok(())
+    }
+
+    fn latest(&self) -> Result<Option<RatchetRecord>, StoreError> {
+        self.db
+            .last()
+            .map_err(|err| StoreError::Database { source: err.into() })?
+            .map(|(_, bytes)| Self::decode(&bytes))
+            .transpose()
+    }
+
+    fn get_by_end_epoch(&self, epoch: u64) -> Result<Option<RatchetRecord>, StoreError> {
+        self.db
+            .get(epoch.to_be_bytes())
+            .map_err(|err| StoreError::Database { source: err.into() })?
+            .map(|bytes| Self::decode(&bytes))
+            .transpose()
+    }
+
+    fn history(&self) -> Result<Vec<RatchetRecord>, StoreError> {
+        self.db
+            .iter()
+            .map(|entry| {
+                let (_, bytes) =
+                    entry.map_err(|err| StoreError::Database { source: err.into() })?;
+                Self::decode(&bytes)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::store::{FileStore, LightClientStore, RatchetRecord, SledStore};
+    use aptos_guests::EPOCH_CHANGE_ID;
+    use aptos_lc_core::types::output::EpochChangeOutput;
+    use risc0_zkvm::{FakeReceipt, InnerReceipt, Receipt, ReceiptClaim};
+
+    fn record(start_epoch: u64, end_epoch: u64) -> RatchetRecord {
+        let output = EpochChangeOutput {
+            start_epoch,
+            end_epoch,
+            validator_verifier_hash: [end_epoch as u8; 32],
+            ..Default::default()
+        };
+        let journal = output.to_bytes();
+        let receipt = Receipt::new(
+            InnerReceipt::Fake(FakeReceipt::new(ReceiptClaim::ok(
+                EPOCH_CHANGE_ID,
+                journal.clone(),
+            ))),
+            journal,
+        );
+
+        RatchetRecord::new(vec![end_epoch as u8], output, receipt)
+    }
+
+    fn test_store(mut store: impl LightClientStore) {
+        assert!(store.latest().unwrap().is_none());
+        assert!(store.history().unwrap().is_empty());
+
+        store.append(&record(1, 2)).unwrap();
+        store.append(&record(2, 5)).unwrap();
+
+        // Records have to be appended in order
+        assert!(store.append(&record(4, 5)).is_err());
+        assert!(store.append(&record(1, 3)).is_err());
+
+        assert_eq!(store.latest().unwrap().unwrap().output().end_epoch, 5);
+        assert_eq!(
+            store.get_by_end_epoch(2).unwrap().unwrap().trusted_state(),
+            &vec![2]
+        );
+        assert!(store.get_by_end_epoch(3).unwrap().is_none());
+        assert_eq!(
+            store
+                .history()
+                .unwrap()
+                .iter()
+                .map(|record| record.output().end_epoch)
+                .collect::<Vec<_>>(),
+            vec![2, 5]
+        );
+    }
+
+    #[test]
+    fn test_file_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("light_client");
+
+        test_store(FileStore::new(&path));
+
+        // The history survives re-opening the store
+        let store = FileStore::new(&path);
+        assert_eq!(store.history().unwrap().len(), 2);
+        assert!(!path.with_extension("tmp").exists());
+    }
+
+    #[test]
+    fn test_sled_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("light_client");
+
+        test_store(SledStore::open(&path).unwrap());
+
+        // The history survives re-opening the store
+        let store = SledStore::open(&path).unwrap();
+        assert_eq!(store.history().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,68 @@
+use crate::error::LightClientError;
+use crate::inclusion::{TransactionProofAssets, ValidatorVerifierAssets};
+use aptos_guests::TRANSACTION_EXECUTION_ELF;
+use aptos_lc_core::types::output::TransactionExecutionOutput;
+use risc0_zkvm::{ExecutorEnv, Prover, Receipt};
+
+/// Generates the inputs of the transaction execution program. The
+/// `transaction_index` of the `TransactionProofAssets` is the version at
+/// which the transaction was committed.
+pub fn generate_stdin<'a>(
+    transaction_proof_assets: &'a TransactionProofAssets,
+    validator_verifier_assets: &'a ValidatorVerifierAssets,
+) -> ExecutorEnv<'a> {
+    ExecutorEnv::builder()
+        .write(transaction_proof_assets.transaction())
+        .unwrap()
+        .write(transaction_proof_assets.transaction_index())
+        .unwrap()
+        .write(transaction_proof_assets.transaction_proof())
+        .unwrap()
+        .write(transaction_proof_assets.latest_li())
+        .unwrap()
+        .write(validator_verifier_assets.validator_verifier())
+        .unwrap()
+        .build()
+        .unwrap()
+}
+
+/// Proves that a transaction was committed at a given version, with the
+/// execution status and gas used of its `TransactionInfo`.
+///
+/// # Arguments
+///
+/// * `client` - The prover.
+/// * `transaction_proof_assets` - The `TransactionInfo` of the transaction,
+///   its version and its proof in the transaction accumulator of the signed
+///   ledger info.
+/// * `validator_verifier_assets` - The validator verifier that signed the
+///   ledger info.
+///
+/// # Returns
+///
+/// The receipt and the `TransactionExecutionOutput` it commits to.
+pub fn prove_transaction_execution(
+    client: &dyn Prover,
+    transaction_proof_assets: &TransactionProofAssets,
+    validator_verifier_assets: &ValidatorVerifierAssets,
+) -> Result<(Receipt, TransactionExecutionOutput), LightClientError> {
+    let env = generate_stdin(transaction_proof_assets, validator_verifier_assets);
+
+    let proof = client
+        .prove(env, TRANSACTION_EXECUTION_ELF)
+        .map_err(|err| LightClientError::ProvingError {
+            program: "prove-transaction-execution".to_string(),
+            source: err.into(),
+        })?;
+
+    // Read output, committed with its stable encoding.
+    let output =
+        TransactionExecutionOutput::from_bytes(&proof.receipt.journal.bytes).map_err(|err| {
+            LightClientError::DecodeError {
+                program: "prove-transaction-execution".to_string(),
+                source: err.into(),
+            }
+        })?;
+
+    Ok((proof.receipt, output))
+}
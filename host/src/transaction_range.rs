@@ -0,0 +1,69 @@
+use crate::error::LightClientError;
+use crate::inclusion::ValidatorVerifierAssets;
+use aptos_guests::TRANSACTION_RANGE_ELF;
+use aptos_lc_core::types::output::TransactionRangeOutput;
+use risc0_zkvm::{ExecutorEnv, Prover, Receipt};
+
+pub use aptos_lc_core::types::assets::TransactionRangeProofAssets;
+
+/// Generates the inputs of the transaction range program.
+pub fn generate_stdin<'a>(
+    transaction_range_proof_assets: &'a TransactionRangeProofAssets,
+    validator_verifier_assets: &'a ValidatorVerifierAssets,
+) -> ExecutorEnv<'a> {
+    ExecutorEnv::builder()
+        .write(transaction_range_proof_assets.transactions())
+        .unwrap()
+        .write(transaction_range_proof_assets.first_version())
+        .unwrap()
+        .write(transaction_range_proof_assets.range_proof())
+        .unwrap()
+        .write(transaction_range_proof_assets.latest_li())
+        .unwrap()
+        .write(validator_verifier_assets.validator_verifier())
+        .unwrap()
+        .build()
+        .unwrap()
+}
+
+/// Proves that a contiguous range of transactions was committed from a
+/// given version, with a single range proof in the transaction accumulator.
+///
+/// # Arguments
+///
+/// * `client` - The prover.
+/// * `transaction_range_proof_assets` - The `TransactionInfo`s of the range,
+///   the version of the first one and their range proof in the transaction
+///   accumulator of the signed ledger info.
+/// * `validator_verifier_assets` - The validator verifier that signed the
+///   ledger info.
+///
+/// # Returns
+///
+/// The receipt and the `TransactionRangeOutput` it commits to.
+pub fn prove_transaction_range(
+    client: &dyn Prover,
+    transaction_range_proof_assets: &TransactionRangeProofAssets,
+    validator_verifier_assets: &ValidatorVerifierAssets,
+) -> Result<(Receipt, TransactionRangeOutput), LightClientError> {
+    let env = generate_stdin(transaction_range_proof_assets, validator_verifier_assets);
+
+    let proof =
+        client
+            .prove(env, TRANSACTION_RANGE_ELF)
+            .map_err(|err| LightClientError::ProvingError {
+                program: "prove-transaction-range".to_string(),
+                source: err.into(),
+            })?;
+
+    // Read output, committed with its stable encoding.
+    let output =
+        TransactionRangeOutput::from_bytes(&proof.receipt.journal.bytes).map_err(|err| {
+            LightClientError::DecodeError {
+                program: "prove-transaction-range".to_string(),
+                source: err.into(),
+            }
+        })?;
+
+    Ok((proof.receipt, output))
+}
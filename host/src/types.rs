@@ -1,8 +1,9 @@
-use std::fmt::Display;
+use crate::inclusion::{SparseMerkleProofAssets, TransactionProofAssets, ValidatorVerifierAssets};
 use anyhow::anyhow;
+use aptos_lc_core::types::output::{EpochChangeOutput, InclusionOutput};
 use risc0_zkvm::Receipt;
 use serde::{Deserialize, Serialize};
-use crate::inclusion::{SparseMerkleProofAssets, TransactionProofAssets, ValidatorVerifierAssets};
+use std::fmt::Display;
 
 #[derive(Serialize, Deserialize)]
 pub struct EpochChangeData {
@@ -92,8 +93,9 @@ impl TryFrom<&str> for ProvingMode {
 pub enum Request {
     ProveInclusion(Box<(ProvingMode, InclusionData)>),
     ProveEpochChange(Box<(ProvingMode, EpochChangeData)>),
-    VerifyInclusion(Receipt),
-    VerifyEpochChange(Receipt),
+    VerifyInclusion(Box<(Receipt, InclusionExpectations)>),
+    VerifyEpochChange(Box<(Receipt, EpochChangeExpectations)>),
+    ExportEvmCalldata(Receipt),
 }
 
 impl Display for &Request {
@@ -103,6 +105,126 @@ impl Display for &Request {
             Request::ProveEpochChange(_) => write!(f, "ProveEpochChange"),
             Request::VerifyInclusion(_) => write!(f, "VerifyInclusion"),
             Request::VerifyEpochChange(_) => write!(f, "VerifyEpochChange"),
+            Request::ExportEvmCalldata(_) => write!(f, "ExportEvmCalldata"),
         }
     }
-}
\ No newline at end of file
+}
+
+/// Expected values of the journal of an inclusion receipt, checked by the
+/// proof server on verification. `None` values are not checked.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct InclusionExpectations {
+    pub validator_verifier_hash: Option<[u8; 32]>,
+    pub key: Option<[u8; 32]>,
+    pub leaf_value_hash: Option<[u8; 32]>,
+    pub block_id: Option<[u8; 32]>,
+}
+
+impl InclusionExpectations {
+    /// Checks the expectations against the output of the inclusion program.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The `InclusionOutput` committed to by the receipt.
+    ///
+    /// # Returns
+    ///
+    /// The names of the expectations that are not met.
+    pub fn check(&self, output: &InclusionOutput) -> Vec<String> {
+        failed_expectations(&[
+            (
+                "validator_verifier_hash",
+                self.validator_verifier_hash,
+                output.validator_verifier_hash,
+            ),
+            ("key", self.key, output.key),
+            (
+                "leaf_value_hash",
+                self.leaf_value_hash,
+                output.leaf_value_hash,
+            ),
+            ("block_id", self.block_id, output.current_block_id),
+        ])
+    }
+}
+
+/// Expected values of the journal of an epoch change receipt, checked by
+/// the proof server on verification. `None` values are not checked.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct EpochChangeExpectations {
+    pub prev_epoch_validator_verifier_hash: Option<[u8; 32]>,
+}
+
+impl EpochChangeExpectations {
+    /// Checks the expectations against the output of the epoch change program.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The `EpochChangeOutput` committed to by the receipt.
+    ///
+    /// # Returns
+    ///
+    /// The names of the expectations that are not met.
+    pub fn check(&self, output: &EpochChangeOutput) -> Vec<String> {
+        failed_expectations(&[(
+            "prev_epoch_validator_verifier_hash",
+            self.prev_epoch_validator_verifier_hash,
+            output.prev_epoch_validator_verifier_hash,
+        )])
+    }
+}
+
+fn failed_expectations(expectations: &[(&str, Option<[u8; 32]>, [u8; 32])]) -> Vec<String> {
+    expectations
+        .iter()
+        .filter(|(_, expected, actual)| expected.is_some_and(|expected| expected != *actual))
+        .map(|(name, _, _)| name.to_string())
+        .collect()
+}
+
+/// Response of the proof server to a verification request, once the
+/// receipt is verified: the output committed to by the receipt and the
+/// expectations of the request it does not meet.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct VerificationResponse<O> {
+    pub output: O,
+    pub failed_expectations: Vec<String>,
+}
+
+/// Identifier of a proving job submitted to the proof server.
+pub type JobId = u64;
+
+/// Response of the proof server to the submission of a proving job. The
+/// cancel token is only known to the submitter, and is required to cancel
+/// the job.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct JobSubmission {
+    pub id: JobId,
+    pub cancel_token: String,
+}
+
+/// Status of a proving job submitted to the proof server.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum JobStatus {
+    /// The job is waiting for a worker.
+    Queued,
+    /// The job is being proven.
+    Running,
+    /// The job is done and its receipt can be fetched.
+    Done,
+    /// The job failed, with the reason of the failure.
+    Failed(String),
+    /// The job was cancelled before completion.
+    Cancelled,
+}
+
+impl JobStatus {
+    /// Returns a boolean indicating if the job reached a final status.
+    ///
+    /// # Returns
+    ///
+    /// A boolean indicating if the job is done, failed or cancelled.
+    pub const fn is_finished(&self) -> bool {
+        !matches!(self, JobStatus::Queued | JobStatus::Running)
+    }
+}
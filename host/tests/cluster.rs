@@ -0,0 +1,461 @@
+//! Integration test of a proof server forwarding proofs to several backend
+//! proof servers.
+//!
+//! The servers run with `RISC0_DEV_MODE=1`, so that they generate fake
+//! receipts carrying the journal of the programs.
+
+use aptos_lc_core::aptos_test_utils::wrapper::AptosWrapper;
+use aptos_lc_core::types::output::InclusionOutput;
+use aptos_lc_core::types::trusted_state::TrustedState;
+use host::error::{ErrorCode, ErrorResponse};
+use host::inclusion::{SparseMerkleProofAssets, TransactionProofAssets, ValidatorVerifierAssets};
+use host::json::{JsonReceipt, JsonRequest, JSON_CONTENT_TYPE};
+use host::native::{verify_epoch_change, verify_inclusion};
+use host::types::{
+    EpochChangeData, InclusionData, InclusionExpectations, ProvingMode, Request,
+    VerificationResponse,
+};
+use reqwest::header::CONTENT_TYPE;
+use reqwest::StatusCode;
+use risc0_zkvm::Receipt;
+use std::net::TcpListener;
+use std::process::{Child, Command};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// A proof server process, killed when dropped.
+struct Server {
+    addr: String,
+    process: Child,
+    _dir: tempfile::TempDir,
+}
+
+impl Server {
+    fn spawn(args: &[&str]) -> Self {
+        let addr = free_addr();
+        let dir = tempfile::tempdir().unwrap();
+        let process = Command::new(env!("CARGO_BIN_EXE_proof_server"))
+            .args(["--addr", &addr, "--jobs-dir"])
+            .arg(dir.path().join("jobs"))
+            .arg("--cache-dir")
+            .arg(dir.path().join("cache"))
+            .args(args)
+            .env("RISC0_DEV_MODE", "1")
+            .spawn()
+            .unwrap();
+
+        Self {
+            addr,
+            process,
+            _dir: dir,
+        }
+    }
+
+    async fn wait_until_healthy(&self, client: &reqwest::Client) {
+        for _ in 0..100 {
+            if let Ok(response) = client
+                .get(format!("http://{}/health", self.addr))
+                .send()
+                .await
+            {
+                if response.status().is_success() {
+                    return;
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        panic!("Server {} did not start", self.addr);
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+    }
+}
+
+fn free_addr() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.local_addr().unwrap().to_string()
+}
+
+/// The proving requests sent to the servers and the journals of their
+/// receipts.
+struct Requests {
+    inclusion: Vec<u8>,
+    epoch_change: Vec<u8>,
+    expected_inclusion: Vec<u8>,
+    expected_epoch_change: Vec<u8>,
+}
+
+/// Generates the requests once for all the tests.
+fn requests() -> &'static Requests {
+    static REQUESTS: OnceLock<Requests> = OnceLock::new();
+
+    REQUESTS.get_or_init(|| {
+        let mut aptos_wrapper = AptosWrapper::new(4, 3, 3).unwrap();
+        let trusted_state = bcs::to_bytes(aptos_wrapper.trusted_state()).unwrap();
+        let trusted_state_version = *aptos_wrapper.current_version();
+        let validator_verifier = match TrustedState::from_bytes(&trusted_state).unwrap() {
+            TrustedState::EpochState { epoch_state, .. } => epoch_state.verifier().clone(),
+            _ => panic!("expected epoch state"),
+        };
+
+        aptos_wrapper.generate_traffic().unwrap();
+        let proof_assets = aptos_wrapper.get_latest_proof_account(3).unwrap();
+        let inclusion_data = InclusionData {
+            sparse_merkle_proof_assets: SparseMerkleProofAssets::new(
+                bcs::to_bytes(proof_assets.state_proof()).unwrap(),
+                *proof_assets.key().as_ref(),
+                *proof_assets.state_value_hash().unwrap().as_ref(),
+            ),
+            transaction_proof_assets: TransactionProofAssets::new(
+                bcs::to_bytes(&proof_assets.transaction()).unwrap(),
+                *proof_assets.transaction_version(),
+                bcs::to_bytes(&proof_assets.transaction_proof()).unwrap(),
+                aptos_wrapper.get_latest_li_bytes().unwrap(),
+            ),
+            validator_verifier_assets: ValidatorVerifierAssets::new(validator_verifier.to_bytes()),
+        };
+        let state_proof = aptos_wrapper
+            .new_state_proof(trusted_state_version)
+            .unwrap();
+        let epoch_change_data = EpochChangeData {
+            trusted_state,
+            epoch_change_proof: bcs::to_bytes(state_proof.epoch_changes()).unwrap(),
+        };
+
+        Requests {
+            expected_inclusion: verify_inclusion(&inclusion_data).unwrap().to_bytes(),
+            expected_epoch_change: verify_epoch_change(&epoch_change_data).unwrap().to_bytes(),
+            inclusion: bcs::to_bytes(&Request::ProveInclusion(Box::new((
+                ProvingMode::STARK,
+                inclusion_data,
+            ))))
+            .unwrap(),
+            epoch_change: bcs::to_bytes(&Request::ProveEpochChange(Box::new((
+                ProvingMode::STARK,
+                epoch_change_data,
+            ))))
+            .unwrap(),
+        }
+    })
+}
+
+/// A primary proof server in split mode and its backends.
+struct Cluster {
+    client: reqwest::Client,
+    primary: Server,
+    backends: Vec<Server>,
+}
+
+impl Cluster {
+    async fn spawn() -> Self {
+        let client = reqwest::Client::new();
+
+        let backends = vec![
+            Server::spawn(&["--mode", "single"]),
+            Server::spawn(&["--mode", "single"]),
+        ];
+        // A backend that is down, which the primary has to route around
+        let dead_backend = free_addr();
+        let backend_addrs = format!("{},{},{}", dead_backend, backends[0].addr, backends[1].addr);
+        // The cache of the primary is disabled so that every request
+        // reaches the backends
+        let primary = Server::spawn(&[
+            "--mode",
+            "split",
+            "--backends",
+            &backend_addrs,
+            "--health-check-interval",
+            "1",
+            "--cache-size",
+            "0",
+        ]);
+        for server in backends.iter().chain([&primary]) {
+            server.wait_until_healthy(&client).await;
+        }
+
+        Self {
+            client,
+            primary,
+            backends,
+        }
+    }
+
+    async fn post(&self, server: &Server, path: &str, body: Vec<u8>) -> reqwest::Response {
+        self.client
+            .post(format!("http://{}{}", server.addr, path))
+            .body(body)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    async fn prove(&self, path: &str, body: Vec<u8>) -> Receipt {
+        self.prove_cached(&self.primary, path, body).await.0
+    }
+
+    /// Proves a request on a server, also returning the cache status of
+    /// the receipt.
+    async fn prove_cached(&self, server: &Server, path: &str, body: Vec<u8>) -> (Receipt, String) {
+        let response = self.post(server, path, body).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let cache_status = response.headers()["x-cache"].to_str().unwrap().to_string();
+
+        (
+            bcs::from_bytes(&response.bytes().await.unwrap()).unwrap(),
+            cache_status,
+        )
+    }
+
+    async fn metrics(&self, server: &Server) -> String {
+        self.client
+            .get(format!("http://{}/metrics", server.addr))
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap()
+    }
+
+    /// Returns the number of proofs generated by a server, of every
+    /// program.
+    async fn proof_count(&self, server: &Server) -> u64 {
+        self.metrics(server)
+            .await
+            .lines()
+            .filter(|line| line.starts_with("proof_server_proving_duration_seconds_count"))
+            .map(|line| line.rsplit(' ').next().unwrap().parse::<u64>().unwrap())
+            .sum()
+    }
+}
+
+fn error_response(bytes: &[u8]) -> ErrorResponse {
+    bcs::from_bytes(bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_cluster_encoding() {
+    let requests = requests();
+    let cluster = Cluster::spawn().await;
+
+    // Both proof types are offloaded to the backends
+    let receipt = cluster
+        .prove("/inclusion/proof", requests.inclusion.clone())
+        .await;
+    assert_eq!(receipt.journal.bytes, requests.expected_inclusion);
+    let receipt = cluster
+        .prove("/epoch/proof", requests.epoch_change.clone())
+        .await;
+    assert_eq!(receipt.journal.bytes, requests.expected_epoch_change);
+
+    // JSON requests go through the same path
+    let json_request =
+        JsonRequest::try_from(&bcs::from_bytes::<Request>(&requests.epoch_change).unwrap())
+            .unwrap();
+    let response = cluster
+        .client
+        .post(format!("http://{}/epoch/proof", cluster.primary.addr))
+        .header(CONTENT_TYPE, JSON_CONTENT_TYPE)
+        .body(serde_json::to_vec(&json_request).unwrap())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let json_receipt: JsonReceipt =
+        serde_json::from_slice(&response.bytes().await.unwrap()).unwrap();
+    assert_eq!(json_receipt.journal, requests.expected_epoch_change);
+    assert_eq!(
+        Receipt::try_from(&json_receipt).unwrap().journal.bytes,
+        requests.expected_epoch_change
+    );
+}
+
+#[tokio::test]
+async fn test_cluster_load_balancing() {
+    let requests = requests();
+    let mut cluster = Cluster::spawn().await;
+
+    // Concurrent requests are spread over the backends
+    let (inclusion, epoch_change) = tokio::join!(
+        cluster.prove("/inclusion/proof", requests.inclusion.clone()),
+        cluster.prove("/epoch/proof", requests.epoch_change.clone()),
+    );
+    assert_eq!(inclusion.journal.bytes, requests.expected_inclusion);
+    assert_eq!(epoch_change.journal.bytes, requests.expected_epoch_change);
+    for backend in &cluster.backends {
+        assert_eq!(cluster.proof_count(backend).await, 1);
+    }
+    assert_eq!(cluster.proof_count(&cluster.primary).await, 0);
+
+    // Losing a backend does not prevent proving
+    drop(cluster.backends.remove(0));
+    let receipt = cluster
+        .prove("/inclusion/proof", requests.inclusion.clone())
+        .await;
+    assert_eq!(receipt.journal.bytes, requests.expected_inclusion);
+}
+
+#[tokio::test]
+async fn test_cluster_errors() {
+    let requests = requests();
+    let cluster = Cluster::spawn().await;
+
+    // Invalid requests are rejected without being retried
+    let response = cluster
+        .post(&cluster.primary, "/epoch/proof", requests.inclusion.clone())
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let err = error_response(&response.bytes().await.unwrap());
+    assert_eq!(err.code, ErrorCode::InvalidRequestType);
+
+    // Inputs failing the native checks are rejected before proving
+    let malformed_request = bcs::to_bytes(&Request::ProveEpochChange(Box::new((
+        ProvingMode::STARK,
+        EpochChangeData {
+            // Unknown variant of trusted state
+            trusted_state: vec![2; 64],
+            epoch_change_proof: vec![],
+        },
+    ))))
+    .unwrap();
+    let response = cluster
+        .post(&cluster.primary, "/epoch/proof", malformed_request)
+        .await;
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let err = error_response(&response.bytes().await.unwrap());
+    assert_eq!(err.code, ErrorCode::MalformedProof);
+
+    for backend in &cluster.backends {
+        assert_eq!(cluster.proof_count(backend).await, 0);
+    }
+}
+
+#[tokio::test]
+async fn test_cluster_metrics() {
+    let requests = requests();
+    let cluster = Cluster::spawn().await;
+
+    cluster
+        .prove("/inclusion/proof", requests.inclusion.clone())
+        .await;
+    let response = cluster
+        .post(&cluster.primary, "/epoch/proof", requests.inclusion.clone())
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // Proofs are recorded by the backend generating them, responses by
+    // every server
+    let mut inclusion_proofs = 0;
+    for backend in &cluster.backends {
+        let metrics = cluster.metrics(backend).await;
+        if metrics.contains(
+            r#"proof_server_proving_duration_seconds_count{program="inclusion",proving_mode="STARK"} 1"#,
+        ) {
+            assert!(metrics.contains(
+                r#"proof_server_responses_total{path="/inclusion/proof",status="200"} 1"#
+            ));
+            inclusion_proofs += 1;
+        }
+    }
+    assert_eq!(inclusion_proofs, 1);
+
+    let metrics = cluster.metrics(&cluster.primary).await;
+    assert!(!metrics.contains("proof_server_proving_duration_seconds_count{"));
+    assert!(
+        metrics.contains(r#"proof_server_responses_total{path="/inclusion/proof",status="200"} 1"#)
+    );
+    assert!(metrics.contains(r#"proof_server_responses_total{path="/epoch/proof",status="400"} 1"#));
+    assert!(metrics.contains(r#"proof_server_errors_total{code="invalid_request_type"} 1"#));
+}
+
+#[tokio::test]
+async fn test_cluster_cache() {
+    let requests = requests();
+    let cluster = Cluster::spawn().await;
+
+    // The primary does not cache receipts
+    let (receipt, cache_status) = cluster
+        .prove_cached(
+            &cluster.primary,
+            "/inclusion/proof",
+            requests.inclusion.clone(),
+        )
+        .await;
+    assert_eq!(receipt.journal.bytes, requests.expected_inclusion);
+    assert_eq!(cache_status, "miss");
+
+    // Identical requests are served from the cache of the backend that
+    // proved them
+    let mut proving_backend = None;
+    for backend in &cluster.backends {
+        if cluster.proof_count(backend).await == 1 {
+            proving_backend = Some(backend);
+        }
+    }
+    let backend = proving_backend.unwrap();
+    let (receipt, cache_status) = cluster
+        .prove_cached(backend, "/inclusion/proof", requests.inclusion.clone())
+        .await;
+    assert_eq!(receipt.journal.bytes, requests.expected_inclusion);
+    assert_eq!(cache_status, "hit");
+    assert_eq!(cluster.proof_count(backend).await, 1);
+}
+
+#[tokio::test]
+async fn test_cluster_verify() {
+    let requests = requests();
+    let cluster = Cluster::spawn().await;
+
+    let receipt = cluster
+        .prove("/inclusion/proof", requests.inclusion.clone())
+        .await;
+
+    // Verification decodes the journal and reports the unmet expectations
+    let output = InclusionOutput::from_bytes(&requests.expected_inclusion).unwrap();
+    let verify_request = bcs::to_bytes(&Request::VerifyInclusion(Box::new((
+        receipt,
+        InclusionExpectations {
+            key: Some(output.key),
+            block_id: Some([0; 32]),
+            ..Default::default()
+        },
+    ))))
+    .unwrap();
+    let response = cluster
+        .post(&cluster.primary, "/inclusion/verify", verify_request)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let verification: VerificationResponse<InclusionOutput> =
+        bcs::from_bytes(&response.bytes().await.unwrap()).unwrap();
+    assert_eq!(verification.output, output);
+    assert_eq!(
+        verification.failed_expectations,
+        vec!["block_id".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn test_cluster_evm() {
+    let requests = requests();
+    let cluster = Cluster::spawn().await;
+
+    let receipt = cluster
+        .prove("/epoch/proof", requests.epoch_change.clone())
+        .await;
+
+    // Only SNARK receipts can be exported for on-chain verification
+    let response = cluster
+        .post(
+            &cluster.primary,
+            "/evm/calldata",
+            bcs::to_bytes(&Request::ExportEvmCalldata(receipt)).unwrap(),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let err = error_response(&response.bytes().await.unwrap());
+    assert_eq!(err.code, ErrorCode::UnsupportedReceipt);
+}